@@ -0,0 +1,35 @@
+//! Derive macros for `bmbp_sql`.
+
+mod row;
+mod table;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives `RdbcTableIdent` for a struct and generates a `<Struct>Column`
+/// enum implementing `RdbcColumnIdent`, one variant per field.
+///
+/// Struct attributes: `#[rdbc(table = "t_user", schema = "app", alias = "u")]`.
+/// Field attributes: `#[rdbc(column = "user_name")]`, `#[rdbc(primary_key)]`,
+/// `#[rdbc(ignore)]`, `#[rdbc(column_type = "Decimal(10, 2)")]`,
+/// `#[rdbc(length = 64)]`, `#[rdbc(nullable)]`, `#[rdbc(not_null)]` and
+/// `#[rdbc(default = "CURRENT_TIMESTAMP")]`.
+#[proc_macro_derive(RdbcTable, attributes(rdbc))]
+pub fn derive_rdbc_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    table::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FromRdbcRow` for a struct with named fields. Each field reads the
+/// column named by `#[rdbc(column = "user_name")]`, or else the field name;
+/// `Option` fields take NULL or missing columns as `None`. Fields marked
+/// `#[rdbc(ignore)]` are filled with `Default::default()`.
+#[proc_macro_derive(FromRdbcRow, attributes(rdbc))]
+pub fn derive_from_rdbc_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    row::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,58 @@
+use crate::table::parse_field_attrs;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "FromRdbcRow requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "FromRdbcRow can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut inits = vec![];
+    for field in fields.iter() {
+        let attrs = parse_field_attrs(field)?;
+        let field_ident = field.ident.as_ref().unwrap();
+        if attrs.ignore {
+            inits.push(quote!(#field_ident: ::std::default::Default::default()));
+            continue;
+        }
+        let field_name = field_ident.to_string();
+        let column = attrs
+            .column
+            .unwrap_or_else(|| field_name.trim_start_matches("r#").to_string());
+        let ty = &field.ty;
+        let type_name = quote!(#ty).to_string().replace(' ', "");
+        inits.push(quote! {
+            #field_ident: ::bmbp_sql::row_column::<#ty>(&mut row, #column, #type_name)?
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::bmbp_sql::FromRdbcRow for #ident #ty_generics #where_clause {
+            #[allow(unused_mut, unused_variables)]
+            fn from_rdbc_row(
+                mut row: ::std::collections::HashMap<::std::string::String, ::bmbp_sql::RdbcValue>,
+            ) -> ::bmbp_sql::RdbcResult<Self> {
+                ::std::result::Result::Ok(#ident {
+                    #(#inits,)*
+                })
+            }
+        }
+    })
+}
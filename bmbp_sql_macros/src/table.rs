@@ -0,0 +1,309 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, GenericArgument, Ident, LitInt, LitStr, PathArguments, Type};
+
+struct TableAttrs {
+    table: Option<String>,
+    schema: String,
+    alias: String,
+}
+
+pub(crate) struct FieldAttrs {
+    pub(crate) column: Option<String>,
+    primary_key: bool,
+    pub(crate) ignore: bool,
+    column_type: Option<TokenStream>,
+    length: Option<u32>,
+    nullable: Option<bool>,
+    default: Option<String>,
+}
+
+struct ColumnDef {
+    variant: Ident,
+    name: String,
+    column_type: TokenStream,
+    nullable: bool,
+    default: Option<String>,
+    primary_key: bool,
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "RdbcTable requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "RdbcTable can only be derived for structs",
+            ))
+        }
+    };
+    let table_attrs = parse_table_attrs(&input)?;
+
+    let mut columns = vec![];
+    for field in fields.iter() {
+        let attrs = parse_field_attrs(field)?;
+        if attrs.ignore {
+            continue;
+        }
+        let field_ident = field.ident.as_ref().unwrap();
+        let field_name = field_ident.to_string();
+        let field_name = field_name.trim_start_matches("r#");
+        let (inner_type, is_option) = unwrap_option(&field.ty);
+        let length = attrs.length.unwrap_or(255);
+        let column_type = match attrs.column_type {
+            Some(column_type) => quote!(::bmbp_sql::RdbcColumnType::#column_type),
+            None => infer_column_type(inner_type, length),
+        };
+        columns.push(ColumnDef {
+            variant: Ident::new(&to_pascal_case(field_name), Span::call_site()),
+            name: attrs.column.unwrap_or_else(|| field_name.to_string()),
+            column_type,
+            nullable: !attrs.primary_key && attrs.nullable.unwrap_or(is_option),
+            default: attrs.default,
+            primary_key: attrs.primary_key,
+        });
+    }
+    if columns.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "RdbcTable needs at least one column",
+        ));
+    }
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let column_ident = format_ident!("{}Column", ident);
+    let table_name = table_attrs
+        .table
+        .unwrap_or_else(|| to_snake_case(&ident.to_string()));
+    let schema = table_attrs.schema;
+    let alias = table_attrs.alias;
+
+    let variants: Vec<&Ident> = columns.iter().map(|c| &c.variant).collect();
+    let names: Vec<&String> = columns.iter().map(|c| &c.name).collect();
+    let types: Vec<&TokenStream> = columns.iter().map(|c| &c.column_type).collect();
+    let nullables: Vec<bool> = columns.iter().map(|c| c.nullable).collect();
+    let defaults: Vec<TokenStream> = columns
+        .iter()
+        .map(|c| match &c.default {
+            Some(expression) => quote!(Some(::bmbp_sql::ColumnDefault::Expression(
+                #expression.to_string()
+            ))),
+            None => quote!(None),
+        })
+        .collect();
+    let primary_keys: Vec<&Ident> = columns
+        .iter()
+        .filter(|c| c.primary_key)
+        .map(|c| &c.variant)
+        .collect();
+    let primary_key_fn = match primary_keys.as_slice() {
+        [] => quote!(),
+        [primary_key] => quote! {
+            fn primary_key() -> impl ::bmbp_sql::RdbcColumnIdent {
+                #column_ident::#primary_key
+            }
+        },
+        _ => quote! {
+            fn union_primary_key() -> Vec<impl ::bmbp_sql::RdbcColumnIdent> {
+                vec![#(#column_ident::#primary_keys),*]
+            }
+        },
+    };
+    let column_doc = format!("Columns of [`{}`].", ident);
+
+    Ok(quote! {
+        #[doc = #column_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #column_ident {
+            #(#variants),*
+        }
+
+        impl ::bmbp_sql::RdbcColumnIdent for #column_ident {
+            fn name(&self) -> String {
+                match self {
+                    #(#column_ident::#variants => #names.to_string()),*
+                }
+            }
+            fn column_type(&self) -> ::bmbp_sql::RdbcColumnType {
+                match self {
+                    #(#column_ident::#variants => #types),*
+                }
+            }
+            fn nullable(&self) -> bool {
+                match self {
+                    #(#column_ident::#variants => #nullables),*
+                }
+            }
+            fn default_value(&self) -> Option<::bmbp_sql::ColumnDefault> {
+                match self {
+                    #(#column_ident::#variants => #defaults),*
+                }
+            }
+        }
+
+        impl ::bmbp_sql::RdbcTableIdent for #ident {
+            fn name() -> String {
+                #table_name.to_string()
+            }
+            fn alias() -> String {
+                #alias.to_string()
+            }
+            fn schema() -> String {
+                #schema.to_string()
+            }
+            fn columns() -> Vec<impl ::bmbp_sql::RdbcColumnIdent> {
+                vec![#(#column_ident::#variants),*]
+            }
+            #primary_key_fn
+        }
+    })
+}
+
+fn parse_table_attrs(input: &DeriveInput) -> syn::Result<TableAttrs> {
+    let mut attrs = TableAttrs {
+        table: None,
+        schema: "".to_string(),
+        alias: "".to_string(),
+    };
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("rdbc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                attrs.table = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("schema") {
+                attrs.schema = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("alias") {
+                attrs.alias = meta.value()?.parse::<LitStr>()?.value();
+            } else {
+                return Err(meta.error("unknown rdbc table attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+pub(crate) fn parse_field_attrs(field: &syn::Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs {
+        column: None,
+        primary_key: false,
+        ignore: false,
+        column_type: None,
+        length: None,
+        nullable: None,
+        default: None,
+    };
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("rdbc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("column") {
+                attrs.column = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("primary_key") {
+                attrs.primary_key = true;
+            } else if meta.path.is_ident("ignore") {
+                attrs.ignore = true;
+            } else if meta.path.is_ident("column_type") {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                attrs.column_type = Some(lit.parse::<TokenStream>()?);
+            } else if meta.path.is_ident("length") {
+                attrs.length = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("nullable") {
+                attrs.nullable = Some(true);
+            } else if meta.path.is_ident("not_null") {
+                attrs.nullable = Some(false);
+            } else if meta.path.is_ident("default") {
+                attrs.default = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("unknown rdbc field attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+/// `Option<T>` yields `(T, true)`, any other type `(ty, false)`.
+fn unwrap_option(ty: &Type) -> (&Type, bool) {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == "Option" {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                        return (inner, true);
+                    }
+                }
+            }
+        }
+    }
+    (ty, false)
+}
+
+fn infer_column_type(ty: &Type, length: u32) -> TokenStream {
+    let type_name = match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| {
+                if segment.ident == "Vec" {
+                    "Vec".to_string()
+                } else {
+                    segment.ident.to_string()
+                }
+            })
+            .unwrap_or_default(),
+        _ => "".to_string(),
+    };
+    match type_name.as_str() {
+        "i8" | "i16" | "u8" => quote!(::bmbp_sql::RdbcColumnType::SmallInt),
+        "i32" | "u16" => quote!(::bmbp_sql::RdbcColumnType::Int),
+        "i64" | "u32" | "u64" | "isize" | "usize" => quote!(::bmbp_sql::RdbcColumnType::BigInt),
+        "f32" => quote!(::bmbp_sql::RdbcColumnType::Float),
+        "f64" => quote!(::bmbp_sql::RdbcColumnType::Double),
+        "bool" => quote!(::bmbp_sql::RdbcColumnType::Boolean),
+        "char" => quote!(::bmbp_sql::RdbcColumnType::Char(1)),
+        "NaiveDate" => quote!(::bmbp_sql::RdbcColumnType::Date),
+        "NaiveDateTime" => quote!(::bmbp_sql::RdbcColumnType::DateTime),
+        "NaiveTime" => quote!(::bmbp_sql::RdbcColumnType::Time),
+        "DateTime" => quote!(::bmbp_sql::RdbcColumnType::Timestamp),
+        "Vec" => quote!(::bmbp_sql::RdbcColumnType::Bytes),
+        "Value" => quote!(::bmbp_sql::RdbcColumnType::Json),
+        "Uuid" => quote!(::bmbp_sql::RdbcColumnType::Uuid),
+        _ => quote!(::bmbp_sql::RdbcColumnType::Varchar(#length)),
+    }
+}
+
+fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if index > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
@@ -1,8 +1,30 @@
+use crate::{ColumnDDLWrapper, ColumnDefault, RdbcColumnType, TableDDLWrapper};
+
 pub trait RdbcColumnIdent {
     fn name(&self) -> String;
     fn alias() -> String {
         "".to_string()
     }
+    /// Column type, `VARCHAR(length)` unless overridden.
+    fn column_type(&self) -> RdbcColumnType {
+        RdbcColumnType::Varchar(self.length())
+    }
+    fn length(&self) -> u32 {
+        255
+    }
+    fn nullable(&self) -> bool {
+        true
+    }
+    fn default_value(&self) -> Option<ColumnDefault> {
+        None
+    }
+    /// Column definition built from the metadata above.
+    fn column_ddl(&self) -> ColumnDDLWrapper {
+        let mut column = ColumnDDLWrapper::new(self.name(), self.column_type());
+        column.nullable = self.nullable();
+        column.default_value = self.default_value();
+        column
+    }
 }
 
 impl<T> RdbcColumnIdent for T
@@ -17,6 +39,9 @@ where
 pub trait RdbcTableIdent {
     fn name() -> String;
     fn alias() -> String;
+    fn schema() -> String {
+        "".to_string()
+    }
     fn columns() -> Vec<impl RdbcColumnIdent>;
     fn primary_key() -> impl RdbcColumnIdent {
         "".to_string()
@@ -24,4 +49,26 @@ pub trait RdbcTableIdent {
     fn union_primary_key() -> Vec<impl RdbcColumnIdent> {
         vec!["".to_string()]
     }
+    /// CREATE TABLE definition of the table, keyed by `union_primary_key()`
+    /// or else `primary_key()`.
+    fn table_ddl() -> TableDDLWrapper {
+        let mut primary_keys: Vec<String> = Self::union_primary_key()
+            .iter()
+            .map(|column| column.name())
+            .filter(|name| !name.is_empty())
+            .collect();
+        if primary_keys.is_empty() {
+            primary_keys.push(Self::primary_key().name());
+        }
+        let mut table = TableDDLWrapper::new(Self::name());
+        table.schema(Self::schema());
+        for ident in Self::columns().iter() {
+            let mut column = ident.column_ddl();
+            if primary_keys.contains(&column.name) {
+                column.primary_key();
+            }
+            table.column(column);
+        }
+        table
+    }
 }
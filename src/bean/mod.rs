@@ -1,7 +1,23 @@
 mod ident;
+mod row;
 mod value;
+mod value_de;
+#[cfg(feature = "mysql")]
+mod value_mysql;
+#[cfg(feature = "postgres")]
 mod value_pg;
+mod value_ser;
+#[cfg(feature = "sqlite")]
+mod value_sqlite;
 
 pub use ident::*;
+pub use row::*;
 pub use value::*;
+pub use value_de::*;
+#[cfg(feature = "mysql")]
+pub use value_mysql::*;
+#[cfg(feature = "postgres")]
 pub use value_pg::*;
+pub use value_ser::*;
+#[cfg(feature = "sqlite")]
+pub(crate) use value_sqlite::sqlite_value;
@@ -0,0 +1,75 @@
+use crate::{from_rdbc_value, RdbcError, RdbcResult, RdbcValue};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// Maps a result row, keyed by column name, into a typed value. Derive it
+/// with `#[derive(FromRdbcRow)]`, which reads each field from the column of
+/// its `#[rdbc(column = "..")]` attribute or its name.
+pub trait FromRdbcRow: Sized {
+    fn from_rdbc_row(row: HashMap<String, RdbcValue>) -> RdbcResult<Self>;
+}
+
+impl FromRdbcRow for HashMap<String, RdbcValue> {
+    fn from_rdbc_row(row: HashMap<String, RdbcValue>) -> RdbcResult<Self> {
+        Ok(row)
+    }
+}
+
+impl FromRdbcRow for RdbcValue {
+    fn from_rdbc_row(row: HashMap<String, RdbcValue>) -> RdbcResult<Self> {
+        Ok(RdbcValue::Object(row))
+    }
+}
+
+/// Takes `column` out of `row`, matching its name case-insensitively, and
+/// converts it like [`from_rdbc_value`]. A missing column reads as NULL.
+/// Used by the `FromRdbcRow` derive.
+#[doc(hidden)]
+pub fn row_column<T>(
+    row: &mut HashMap<String, RdbcValue>,
+    column: &str,
+    type_name: &str,
+) -> RdbcResult<T>
+where
+    T: DeserializeOwned,
+{
+    let value = match row.remove(column) {
+        Some(value) => value,
+        None => row
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case(column))
+            .cloned()
+            .and_then(|key| row.remove(&key))
+            .unwrap_or(RdbcValue::Null),
+    };
+    let found = value_kind(&value);
+    from_rdbc_value(value).map_err(|err| {
+        RdbcError::RowMapping(format!(
+            "column {} expects {}, found {}: {}",
+            column, type_name, found, err
+        ))
+    })
+}
+
+fn value_kind(value: &RdbcValue) -> &'static str {
+    match value {
+        RdbcValue::Char(_) => "Char",
+        RdbcValue::Varchar(_) => "Varchar",
+        RdbcValue::Text(_) => "Text",
+        RdbcValue::LongText(_) => "LongText",
+        RdbcValue::SmallInt(_) => "SmallInt",
+        RdbcValue::Int(_) => "Int",
+        RdbcValue::BigInt(_) => "BigInt",
+        RdbcValue::Double(_) => "Double",
+        RdbcValue::BigDouble(_) => "BigDouble",
+        RdbcValue::Date(_) => "Date",
+        RdbcValue::DateTime(_) => "DateTime",
+        RdbcValue::Time(_) => "Time",
+        RdbcValue::TimeStamp(_) => "TimeStamp",
+        RdbcValue::Bytes(_) => "Bytes",
+        RdbcValue::Boolean(_) => "Boolean",
+        RdbcValue::Array(_) => "Array",
+        RdbcValue::Object(_) => "Object",
+        RdbcValue::Null => "Null",
+    }
+}
@@ -924,6 +924,28 @@ impl From<&Option<&bool>> for RdbcValue {
     }
 }
 
+impl From<serde_json::Value> for RdbcValue {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => RdbcValue::Null,
+            serde_json::Value::Bool(v) => RdbcValue::Boolean(v),
+            serde_json::Value::Number(v) => match v.as_i64() {
+                Some(v) => RdbcValue::BigInt(v),
+                None => RdbcValue::BigDouble(v.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(v) => RdbcValue::Varchar(v),
+            serde_json::Value::Array(v) => {
+                RdbcValue::Array(v.into_iter().map(RdbcValue::from).collect())
+            }
+            serde_json::Value::Object(v) => RdbcValue::Object(
+                v.into_iter()
+                    .map(|(key, value)| (key, RdbcValue::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
 impl RdbcValue {
     pub fn time_from_i32(value: i32) -> Self {
         RdbcValue::TimeStamp(value as u64)
@@ -0,0 +1,305 @@
+use crate::{RdbcError, RdbcResult, RdbcValue};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+use std::collections::hash_map;
+use std::fmt::Display;
+use std::vec;
+
+/// Converts an [`RdbcValue`], e.g. a row object, into any deserializable
+/// type. Dates and times are handed to the target as ISO 8601 text, which
+/// chrono parses, and numbers read as text are parsed into numeric fields.
+pub fn from_rdbc_value<T>(value: RdbcValue) -> RdbcResult<T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(value)
+}
+
+impl de::Error for RdbcError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        RdbcError::Serde(msg.to_string())
+    }
+}
+
+impl IntoDeserializer<'_, RdbcError> for RdbcValue {
+    type Deserializer = RdbcValue;
+
+    fn into_deserializer(self) -> RdbcValue {
+        self
+    }
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident => $visit:ident: $ty:ty,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> RdbcResult<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                match self.as_string() {
+                    Some(text) => {
+                        let value = text.trim().parse::<$ty>().map_err(|_| {
+                            RdbcError::Serde(format!(
+                                "invalid {} value {:?}",
+                                stringify!($ty),
+                                text
+                            ))
+                        })?;
+                        visitor.$visit(value)
+                    }
+                    None => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for RdbcValue {
+    type Error = RdbcError;
+
+    fn deserialize_any<V>(self, visitor: V) -> RdbcResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            RdbcValue::Char(v) => visitor.visit_char(v),
+            RdbcValue::Varchar(v) | RdbcValue::Text(v) | RdbcValue::LongText(v) => {
+                visitor.visit_string(v)
+            }
+            RdbcValue::SmallInt(v) => visitor.visit_i8(v),
+            RdbcValue::Int(v) => visitor.visit_i32(v),
+            RdbcValue::BigInt(v) => visitor.visit_i64(v),
+            RdbcValue::Double(v) => visitor.visit_f32(v),
+            RdbcValue::BigDouble(v) => visitor.visit_f64(v),
+            RdbcValue::Date(v) => visitor.visit_string(v.format("%Y-%m-%d").to_string()),
+            RdbcValue::DateTime(v) => {
+                visitor.visit_string(v.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+            }
+            RdbcValue::Time(v) => visitor.visit_string(v.format("%H:%M:%S%.f").to_string()),
+            RdbcValue::TimeStamp(v) => visitor.visit_u64(v),
+            RdbcValue::Bytes(v) => visitor.visit_byte_buf(v),
+            RdbcValue::Boolean(v) => visitor.visit_bool(v),
+            RdbcValue::Array(v) => visitor.visit_seq(ArrayAccess {
+                items: v.into_iter(),
+            }),
+            RdbcValue::Object(v) => visitor.visit_map(ObjectAccess {
+                entries: v.into_iter(),
+                value: None,
+            }),
+            RdbcValue::Null => visitor.visit_unit(),
+        }
+    }
+
+    deserialize_number! {
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> RdbcResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // Databases without a boolean type return 0 and 1.
+        match self {
+            RdbcValue::SmallInt(v) => visitor.visit_bool(v != 0),
+            RdbcValue::Int(v) => visitor.visit_bool(v != 0),
+            RdbcValue::BigInt(v) => visitor.visit_bool(v != 0),
+            v => v.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> RdbcResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            RdbcValue::Null => visitor.visit_none(),
+            v => visitor.visit_some(v),
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> RdbcResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // Bytes into `Vec<u8>` fields, which read a sequence.
+        match self {
+            RdbcValue::Bytes(v) => visitor.visit_seq(ArrayAccess {
+                items: v
+                    .into_iter()
+                    .map(|byte| RdbcValue::Int(byte as i32))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            }),
+            v => v.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> RdbcResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> RdbcResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            RdbcValue::Object(object) if object.len() == 1 => {
+                let (variant, value) = object.into_iter().next().unwrap();
+                visitor.visit_enum(VariantValue {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            v => match v.as_string() {
+                Some(variant) => visitor.visit_enum(VariantValue {
+                    variant,
+                    value: None,
+                }),
+                None => Err(RdbcError::Serde(format!("expected an enum, found {:?}", v))),
+            },
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct ArrayAccess {
+    items: vec::IntoIter<RdbcValue>,
+}
+
+impl<'de> SeqAccess<'de> for ArrayAccess {
+    type Error = RdbcError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> RdbcResult<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.items.next() {
+            Some(value) => seed.deserialize(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct ObjectAccess {
+    entries: hash_map::IntoIter<String, RdbcValue>,
+    value: Option<RdbcValue>,
+}
+
+impl<'de> MapAccess<'de> for ObjectAccess {
+    type Error = RdbcError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> RdbcResult<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(RdbcValue::Varchar(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> RdbcResult<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| RdbcError::Serde("object value without a key".to_string()))?;
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct VariantValue {
+    variant: String,
+    value: Option<RdbcValue>,
+}
+
+impl<'de> EnumAccess<'de> for VariantValue {
+    type Error = RdbcError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> RdbcResult<(V::Value, Self)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(RdbcValue::Varchar(self.variant.clone()))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantValue {
+    type Error = RdbcError;
+
+    fn unit_variant(self) -> RdbcResult<()> {
+        match self.value {
+            None | Some(RdbcValue::Null) => Ok(()),
+            Some(v) => Err(RdbcError::Serde(format!(
+                "unit variant {} has a value {:?}",
+                self.variant, v
+            ))),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> RdbcResult<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value.unwrap_or(RdbcValue::Null))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> RdbcResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.value
+            .unwrap_or(RdbcValue::Null)
+            .deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> RdbcResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.value
+            .unwrap_or(RdbcValue::Null)
+            .deserialize_any(visitor)
+    }
+}
@@ -0,0 +1,173 @@
+use crate::bean::value::RdbcValue;
+use crate::{RdbcError, RdbcResult};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use mysql_async::consts::ColumnType;
+use mysql_async::{Column, Value};
+
+/// Binary collation id, which marks BLOB and BINARY columns.
+const BINARY_CHARSET: u16 = 63;
+
+/// Binds values as MySQL protocol values: booleans as 0 and 1, arrays and
+/// objects as JSON text. `Value::from(&value)` comes with it through
+/// `ToValue`.
+impl From<RdbcValue> for Value {
+    fn from(value: RdbcValue) -> Self {
+        match value {
+            RdbcValue::Char(v) => Value::Bytes(v.to_string().into_bytes()),
+            RdbcValue::Varchar(v) | RdbcValue::Text(v) | RdbcValue::LongText(v) => {
+                Value::Bytes(v.into_bytes())
+            }
+            RdbcValue::SmallInt(v) => Value::Int(v as i64),
+            RdbcValue::Int(v) => Value::Int(v as i64),
+            RdbcValue::BigInt(v) => Value::Int(v),
+            RdbcValue::Double(v) => Value::Float(v),
+            RdbcValue::BigDouble(v) => Value::Double(v),
+            RdbcValue::Date(v) => {
+                Value::Date(v.year() as u16, v.month() as u8, v.day() as u8, 0, 0, 0, 0)
+            }
+            RdbcValue::DateTime(v) => Value::Date(
+                v.year() as u16,
+                v.month() as u8,
+                v.day() as u8,
+                v.hour() as u8,
+                v.minute() as u8,
+                v.second() as u8,
+                v.nanosecond() / 1000,
+            ),
+            RdbcValue::Time(v) => Value::Time(
+                false,
+                0,
+                v.hour() as u8,
+                v.minute() as u8,
+                v.second() as u8,
+                v.nanosecond() / 1000,
+            ),
+            RdbcValue::TimeStamp(v) => Value::UInt(v),
+            RdbcValue::Bytes(v) => Value::Bytes(v),
+            RdbcValue::Boolean(v) => Value::Int(v as i64),
+            RdbcValue::Array(_) | RdbcValue::Object(_) => {
+                Value::Bytes(serde_json::to_vec(&value).unwrap_or_default())
+            }
+            RdbcValue::Null => Value::NULL,
+        }
+    }
+}
+
+/// Converts a MySQL value by the type of its column, from the binary protocol
+/// of prepared statements or the text protocol. `TINYINT(1)` reads as a
+/// boolean, DECIMAL as its exact text, JSON as arrays and objects, and
+/// binary strings as bytes.
+pub fn mysql_column_value(value: Value, column: &Column) -> RdbcResult<RdbcValue> {
+    let column_type = column.column_type();
+    let value = match value {
+        Value::NULL => RdbcValue::Null,
+        Value::Int(v) => integer_value(v, column),
+        Value::UInt(v) => match i64::try_from(v) {
+            Ok(v) => integer_value(v, column),
+            Err(_) => RdbcValue::Varchar(v.to_string()),
+        },
+        Value::Float(v) => RdbcValue::Double(v),
+        Value::Double(v) => RdbcValue::BigDouble(v),
+        Value::Date(year, month, day, hour, minute, second, micros) => {
+            let date = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32);
+            let time =
+                NaiveTime::from_hms_micro_opt(hour as u32, minute as u32, second as u32, micros);
+            match (date, time) {
+                (Some(date), _) if column_type == ColumnType::MYSQL_TYPE_DATE => {
+                    RdbcValue::Date(date)
+                }
+                (Some(date), Some(time)) => RdbcValue::DateTime(date.and_time(time)),
+                // Zero dates such as 0000-00-00.
+                _ => RdbcValue::Null,
+            }
+        }
+        Value::Time(negative, days, hour, minute, second, micros) => {
+            match NaiveTime::from_hms_micro_opt(hour as u32, minute as u32, second as u32, micros) {
+                Some(time) if !negative && days == 0 => RdbcValue::Time(time),
+                _ => RdbcValue::Varchar(format!(
+                    "{}{}:{:02}:{:02}",
+                    if negative { "-" } else { "" },
+                    days * 24 + hour as u32,
+                    minute,
+                    second
+                )),
+            }
+        }
+        Value::Bytes(bytes) => bytes_value(bytes, column)?,
+    };
+    Ok(value)
+}
+
+fn integer_value(v: i64, column: &Column) -> RdbcValue {
+    match column.column_type() {
+        ColumnType::MYSQL_TYPE_TINY if column.column_length() == 1 => RdbcValue::Boolean(v != 0),
+        ColumnType::MYSQL_TYPE_TINY
+        | ColumnType::MYSQL_TYPE_SHORT
+        | ColumnType::MYSQL_TYPE_INT24
+        | ColumnType::MYSQL_TYPE_LONG
+        | ColumnType::MYSQL_TYPE_YEAR => match i32::try_from(v) {
+            Ok(v) => RdbcValue::Int(v),
+            Err(_) => RdbcValue::BigInt(v),
+        },
+        _ => RdbcValue::BigInt(v),
+    }
+}
+
+fn is_string_type(column_type: ColumnType) -> bool {
+    matches!(
+        column_type,
+        ColumnType::MYSQL_TYPE_STRING
+            | ColumnType::MYSQL_TYPE_VAR_STRING
+            | ColumnType::MYSQL_TYPE_VARCHAR
+            | ColumnType::MYSQL_TYPE_BLOB
+            | ColumnType::MYSQL_TYPE_TINY_BLOB
+            | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+            | ColumnType::MYSQL_TYPE_LONG_BLOB
+    )
+}
+
+/// Text protocol values and strings, parsed by the column type.
+fn bytes_value(bytes: Vec<u8>, column: &Column) -> RdbcResult<RdbcValue> {
+    let column_type = column.column_type();
+    let binary = column.character_set() == BINARY_CHARSET;
+    if column_type == ColumnType::MYSQL_TYPE_BIT
+        || column_type == ColumnType::MYSQL_TYPE_GEOMETRY
+        || (binary && is_string_type(column_type))
+    {
+        return Ok(RdbcValue::Bytes(bytes));
+    }
+    let text = String::from_utf8(bytes)
+        .map_err(|err| RdbcError::Database(format!("column {}: {}", column.name_str(), err)))?;
+    let parsed = match column_type {
+        ColumnType::MYSQL_TYPE_TINY
+        | ColumnType::MYSQL_TYPE_SHORT
+        | ColumnType::MYSQL_TYPE_INT24
+        | ColumnType::MYSQL_TYPE_LONG
+        | ColumnType::MYSQL_TYPE_LONGLONG
+        | ColumnType::MYSQL_TYPE_YEAR => text.parse().ok().map(|v| integer_value(v, column)),
+        ColumnType::MYSQL_TYPE_FLOAT => text.parse().ok().map(RdbcValue::Double),
+        ColumnType::MYSQL_TYPE_DOUBLE => text.parse().ok().map(RdbcValue::BigDouble),
+        ColumnType::MYSQL_TYPE_DATE => NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+            .ok()
+            .map(RdbcValue::Date),
+        ColumnType::MYSQL_TYPE_DATETIME
+        | ColumnType::MYSQL_TYPE_DATETIME2
+        | ColumnType::MYSQL_TYPE_TIMESTAMP
+        | ColumnType::MYSQL_TYPE_TIMESTAMP2 => {
+            NaiveDateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S%.f")
+                .ok()
+                .map(RdbcValue::DateTime)
+        }
+        ColumnType::MYSQL_TYPE_TIME | ColumnType::MYSQL_TYPE_TIME2 => {
+            NaiveTime::parse_from_str(&text, "%H:%M:%S%.f")
+                .ok()
+                .map(RdbcValue::Time)
+        }
+        ColumnType::MYSQL_TYPE_JSON => serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .map(RdbcValue::from),
+        _ => None,
+    };
+    // DECIMAL and anything that does not parse keeps its text.
+    Ok(parsed.unwrap_or(RdbcValue::Varchar(text)))
+}
@@ -1,47 +1,387 @@
 use crate::bean::value::RdbcValue;
+use std::error::Error;
 use tokio_postgres::types::private::BytesMut;
 use tokio_postgres::types::IsNull;
-use tokio_postgres::types::{to_sql_checked, ToSql};
+use tokio_postgres::types::{to_sql_checked, FromSql, Kind, ToSql, Type};
 
+type BoxError = Box<dyn Error + Sync + Send>;
+
+/// Writes values as the PostgreSQL parameter type requires: integers are
+/// widened or narrowed with a range check, strings are parsed into numbers,
+/// `uuid`, `json` and dates, `TimeStamp` epoch seconds become timestamps, and
+/// arrays are sent as arrays of the member type. Other pairs are rejected.
 impl ToSql for RdbcValue {
-    fn to_sql(
-        &self,
-        ty: &tokio_postgres::types::Type,
-        w: &mut BytesMut,
-    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>>
-    where
-        Self: Sized,
-    {
-        match self {
-            RdbcValue::Char(v) => v.to_string().to_sql(ty, w),
-            RdbcValue::Varchar(v) => v.to_string().to_sql(ty, w),
-            RdbcValue::Text(v) => v.to_string().to_sql(ty, w),
-            RdbcValue::LongText(v) => v.to_string().to_sql(ty, w),
-            RdbcValue::SmallInt(v) => (v.clone()).to_sql(ty, w),
-            RdbcValue::Int(v) => (v.clone()).to_sql(ty, w),
-            RdbcValue::BigInt(v) => v.clone().to_sql(ty, w),
-            RdbcValue::Double(v) => v.clone().to_sql(ty, w),
-            RdbcValue::BigDouble(v) => v.clone().to_sql(ty, w),
-            RdbcValue::Date(v) => v.clone().to_sql(ty, w),
-            RdbcValue::DateTime(v) => v.clone().to_sql(ty, w),
-            RdbcValue::Time(v) => v.clone().to_sql(ty, w),
-            RdbcValue::TimeStamp(v) => (v.clone() as i64).to_sql(ty, w),
-            RdbcValue::Bytes(v) => v.to_vec().to_sql(ty, w),
-            RdbcValue::Boolean(v) => v.clone().to_sql(ty, w),
-            RdbcValue::Array(v) => v.to_vec().to_sql(ty, w),
-            RdbcValue::Object(v) => match serde_json::to_string(v) {
-                Ok(v) => v.to_sql(ty, w),
-                Err(e) => e.to_string().to_sql(ty, w),
+    fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, BoxError> {
+        if self.is_null() {
+            return Ok(IsNull::Yes);
+        }
+        match *ty {
+            Type::BOOL => bool_value(self, ty)?.to_sql(ty, w),
+            Type::CHAR => narrow::<i8>(integer_value(self, ty)?, ty)?.to_sql(ty, w),
+            Type::INT2 => narrow::<i16>(integer_value(self, ty)?, ty)?.to_sql(ty, w),
+            Type::INT4 => narrow::<i32>(integer_value(self, ty)?, ty)?.to_sql(ty, w),
+            Type::INT8 => integer_value(self, ty)?.to_sql(ty, w),
+            Type::OID => narrow::<u32>(integer_value(self, ty)?, ty)?.to_sql(ty, w),
+            Type::FLOAT4 => {
+                let value = float_value(self, ty)?;
+                if value.is_finite() && value.abs() > f32::MAX as f64 {
+                    return Err(format!("{} is out of range for type {}", value, ty).into());
+                }
+                (value as f32).to_sql(ty, w)
+            }
+            Type::FLOAT8 => float_value(self, ty)?.to_sql(ty, w),
+            Type::NUMERIC => {
+                let text = match self {
+                    RdbcValue::Double(v) if v.is_infinite() => infinity_text(*v as f64),
+                    RdbcValue::BigDouble(v) if v.is_infinite() => infinity_text(*v),
+                    v if v.is_string() || v.is_number() => v.to_string(),
+                    _ => return Err(mismatch(self, ty)),
+                };
+                numeric_to_sql(text.trim(), w)?;
+                Ok(IsNull::No)
+            }
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+                let text = match self {
+                    RdbcValue::Bytes(v) => std::str::from_utf8(v)?.to_string(),
+                    v => v.to_string(),
+                };
+                text.to_sql(ty, w)
+            }
+            Type::UUID => {
+                let uuid = match self {
+                    RdbcValue::Bytes(v) => uuid::Uuid::from_slice(v)?,
+                    v if v.is_string() => uuid::Uuid::parse_str(v.to_string().trim())?,
+                    _ => return Err(mismatch(self, ty)),
+                };
+                uuid.to_sql(ty, w)
+            }
+            Type::JSON | Type::JSONB => {
+                let json = match self {
+                    v if v.is_string() => serde_json::from_str(&v.to_string())?,
+                    v => serde_json::to_value(v)?,
+                };
+                json.to_sql(ty, w)
+            }
+            Type::DATE => match self {
+                RdbcValue::Date(v) => v.to_sql(ty, w),
+                v if v.is_string() => {
+                    chrono::NaiveDate::parse_from_str(v.to_string().trim(), "%Y-%m-%d")?
+                        .to_sql(ty, w)
+                }
+                _ => Err(mismatch(self, ty)),
+            },
+            Type::TIMESTAMP => date_time_value(self, ty)?.to_sql(ty, w),
+            Type::TIMESTAMPTZ => date_time_value(self, ty)?.and_utc().to_sql(ty, w),
+            Type::TIME => match self {
+                RdbcValue::Time(v) => v.to_sql(ty, w),
+                v if v.is_string() => {
+                    chrono::NaiveTime::parse_from_str(v.to_string().trim(), "%H:%M:%S%.f")?
+                        .to_sql(ty, w)
+                }
+                _ => Err(mismatch(self, ty)),
+            },
+            Type::BYTEA => match self {
+                RdbcValue::Bytes(v) => v.to_sql(ty, w),
+                v if v.is_string() => v.to_string().into_bytes().to_sql(ty, w),
+                _ => Err(mismatch(self, ty)),
+            },
+            _ => match (ty.kind(), self) {
+                (Kind::Array(_), RdbcValue::Array(v)) => v.to_sql(ty, w),
+                (Kind::Enum(_), v) if v.is_string() => {
+                    w.extend_from_slice(v.to_string().as_bytes());
+                    Ok(IsNull::No)
+                }
+                (Kind::Domain(inner), v) => v.to_sql(inner, w),
+                _ => Err(mismatch(self, ty)),
             },
-            RdbcValue::Null => Ok(tokio_postgres::types::IsNull::Yes),
         }
     }
-    fn accepts(_ty: &tokio_postgres::types::Type) -> bool
-    where
-        Self: Sized,
-    {
-        true
+
+    fn accepts(ty: &Type) -> bool {
+        <Self as FromSql>::accepts(ty)
     }
 
     to_sql_checked!();
 }
+
+fn mismatch(value: &RdbcValue, ty: &Type) -> BoxError {
+    format!("cannot bind {:?} to PostgreSQL type {}", value, ty).into()
+}
+
+fn narrow<T>(value: i64, ty: &Type) -> Result<T, BoxError>
+where
+    T: TryFrom<i64>,
+{
+    T::try_from(value).map_err(|_| format!("{} is out of range for type {}", value, ty).into())
+}
+
+fn integer_value(value: &RdbcValue, ty: &Type) -> Result<i64, BoxError> {
+    match value {
+        RdbcValue::SmallInt(v) => Ok(*v as i64),
+        RdbcValue::Int(v) => Ok(*v as i64),
+        RdbcValue::BigInt(v) => Ok(*v),
+        RdbcValue::TimeStamp(v) => {
+            i64::try_from(*v).map_err(|_| format!("{} is out of range for type {}", v, ty).into())
+        }
+        v if v.is_string() => Ok(v.to_string().trim().parse::<i64>()?),
+        _ => Err(mismatch(value, ty)),
+    }
+}
+
+fn float_value(value: &RdbcValue, ty: &Type) -> Result<f64, BoxError> {
+    match value {
+        RdbcValue::SmallInt(v) => Ok(*v as f64),
+        RdbcValue::Int(v) => Ok(*v as f64),
+        RdbcValue::BigInt(v) => Ok(*v as f64),
+        RdbcValue::Double(v) => Ok(*v as f64),
+        RdbcValue::BigDouble(v) => Ok(*v),
+        v if v.is_string() => Ok(v.to_string().trim().parse::<f64>()?),
+        _ => Err(mismatch(value, ty)),
+    }
+}
+
+fn bool_value(value: &RdbcValue, ty: &Type) -> Result<bool, BoxError> {
+    match value {
+        RdbcValue::Boolean(v) => Ok(*v),
+        v if v.is_string() => match v.to_string().trim().to_lowercase().as_str() {
+            "true" | "t" | "yes" | "on" | "1" => Ok(true),
+            "false" | "f" | "no" | "off" | "0" => Ok(false),
+            _ => Err(mismatch(value, ty)),
+        },
+        _ => Err(mismatch(value, ty)),
+    }
+}
+
+fn date_time_value(value: &RdbcValue, ty: &Type) -> Result<chrono::NaiveDateTime, BoxError> {
+    match value {
+        RdbcValue::DateTime(v) => Ok(*v),
+        RdbcValue::Date(v) => Ok(v.and_time(chrono::NaiveTime::MIN)),
+        RdbcValue::TimeStamp(v) => i64::try_from(*v)
+            .ok()
+            .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+            .map(|v| v.naive_utc())
+            .ok_or_else(|| format!("{} is out of range for type {}", v, ty).into()),
+        v if v.is_string() => {
+            let text = v.to_string();
+            let text = text.trim();
+            Ok(
+                chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f").or_else(
+                    |_| chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f"),
+                )?,
+            )
+        }
+        _ => Err(mismatch(value, ty)),
+    }
+}
+
+/// `numeric` spelling of an infinite float.
+fn infinity_text(value: f64) -> String {
+    if value.is_sign_negative() {
+        "-Infinity".to_string()
+    } else {
+        "Infinity".to_string()
+    }
+}
+
+/// Binary `numeric` of decimal text: base 10000 digits with a weight and a
+/// display scale.
+fn numeric_to_sql(text: &str, w: &mut BytesMut) -> Result<(), BoxError> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (sign, groups, weight, dscale) = match digits {
+        "NaN" => (0xC000u16, vec![], 0, 0),
+        "Infinity" => (if negative { 0xF000 } else { 0xD000 }, vec![], 0, 0),
+        _ => {
+            let (groups, weight, dscale) =
+                numeric_groups(digits).ok_or_else(|| format!("invalid numeric value {}", text))?;
+            let sign = if negative && !groups.is_empty() {
+                0x4000
+            } else {
+                0x0000
+            };
+            (sign, groups, weight, dscale)
+        }
+    };
+    w.extend_from_slice(&(groups.len() as u16).to_be_bytes());
+    w.extend_from_slice(&weight.to_be_bytes());
+    w.extend_from_slice(&sign.to_be_bytes());
+    w.extend_from_slice(&dscale.to_be_bytes());
+    for group in groups {
+        w.extend_from_slice(&group.to_be_bytes());
+    }
+    Ok(())
+}
+
+/// Base 10000 digits, weight of the first one and display scale of unsigned
+/// decimal text.
+fn numeric_groups(digits: &str) -> Option<(Vec<u16>, i16, u16)> {
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if (integer.is_empty() && fraction.is_empty())
+        || !integer
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let integer = integer.trim_start_matches('0');
+    let integer_pad = (4 - integer.len() % 4) % 4;
+    let fraction_pad = (4 - fraction.len() % 4) % 4;
+    let padded = format!(
+        "{}{}{}{}",
+        "0".repeat(integer_pad),
+        integer,
+        fraction,
+        "0".repeat(fraction_pad)
+    );
+    let mut groups: Vec<u16> = padded
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0, |group, digit| group * 10 + (digit - b'0') as u16)
+        })
+        .collect();
+    let mut weight = ((integer.len() + integer_pad) / 4) as i16 - 1;
+    while groups.first() == Some(&0) {
+        groups.remove(0);
+        weight -= 1;
+    }
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    if groups.is_empty() {
+        weight = 0;
+    }
+    Some((groups, weight, fraction.len() as u16))
+}
+
+/// Reads PostgreSQL values. `numeric` becomes its exact decimal text, `uuid`
+/// its hyphenated text, `timestamptz` the UTC date time, and `json`/`jsonb`
+/// the matching objects, arrays and scalars.
+impl<'a> FromSql<'a> for RdbcValue {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(match *ty {
+            Type::BOOL => RdbcValue::Boolean(bool::from_sql(ty, raw)?),
+            Type::CHAR => RdbcValue::SmallInt(i8::from_sql(ty, raw)?),
+            Type::INT2 => RdbcValue::Int(i16::from_sql(ty, raw)? as i32),
+            Type::INT4 => RdbcValue::Int(i32::from_sql(ty, raw)?),
+            Type::INT8 => RdbcValue::BigInt(i64::from_sql(ty, raw)?),
+            Type::OID => RdbcValue::BigInt(u32::from_sql(ty, raw)? as i64),
+            Type::FLOAT4 => RdbcValue::Double(f32::from_sql(ty, raw)?),
+            Type::FLOAT8 => RdbcValue::BigDouble(f64::from_sql(ty, raw)?),
+            Type::NUMERIC => RdbcValue::Varchar(numeric_string(raw)?),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+                RdbcValue::Varchar(String::from_sql(ty, raw)?)
+            }
+            Type::DATE => RdbcValue::Date(chrono::NaiveDate::from_sql(ty, raw)?),
+            Type::TIMESTAMP => RdbcValue::DateTime(chrono::NaiveDateTime::from_sql(ty, raw)?),
+            Type::TIMESTAMPTZ => {
+                RdbcValue::DateTime(chrono::DateTime::<chrono::Utc>::from_sql(ty, raw)?.naive_utc())
+            }
+            Type::TIME => RdbcValue::Time(chrono::NaiveTime::from_sql(ty, raw)?),
+            Type::BYTEA => RdbcValue::Bytes(Vec::<u8>::from_sql(ty, raw)?),
+            Type::JSON | Type::JSONB => RdbcValue::from(serde_json::Value::from_sql(ty, raw)?),
+            Type::UUID => RdbcValue::Varchar(uuid::Uuid::from_sql(ty, raw)?.to_string()),
+            _ => match ty.kind() {
+                Kind::Array(_) => RdbcValue::Array(Vec::<RdbcValue>::from_sql(ty, raw)?),
+                Kind::Enum(_) => RdbcValue::Varchar(std::str::from_utf8(raw)?.to_string()),
+                Kind::Domain(inner) => RdbcValue::from_sql(inner, raw)?,
+                _ => return Err(format!("cannot read PostgreSQL type {} as RdbcValue", ty).into()),
+            },
+        })
+    }
+
+    fn from_sql_null(_ty: &Type) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(RdbcValue::Null)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::BOOL
+            | Type::CHAR
+            | Type::INT2
+            | Type::INT4
+            | Type::INT8
+            | Type::OID
+            | Type::FLOAT4
+            | Type::FLOAT8
+            | Type::NUMERIC
+            | Type::TEXT
+            | Type::VARCHAR
+            | Type::BPCHAR
+            | Type::NAME
+            | Type::UNKNOWN
+            | Type::DATE
+            | Type::TIMESTAMP
+            | Type::TIMESTAMPTZ
+            | Type::TIME
+            | Type::BYTEA
+            | Type::JSON
+            | Type::JSONB
+            | Type::UUID => true,
+            _ => match ty.kind() {
+                Kind::Array(member) | Kind::Domain(member) => <Self as FromSql>::accepts(member),
+                Kind::Enum(_) => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Decimal text of a binary `numeric`: base 10000 digits with a weight and
+/// a display scale.
+fn numeric_string(raw: &[u8]) -> Result<String, Box<dyn Error + Sync + Send>> {
+    let read = |at: usize| u16::from_be_bytes([raw[at], raw[at + 1]]);
+    if raw.len() < 8 {
+        return Err("invalid numeric value".into());
+    }
+    let ndigits = read(0) as usize;
+    let weight = read(2) as i16 as i32;
+    let sign = read(4);
+    let dscale = read(6) as usize;
+    if raw.len() < 8 + ndigits * 2 {
+        return Err("invalid numeric value".into());
+    }
+    match sign {
+        0xC000 => return Ok("NaN".to_string()),
+        0xD000 => return Ok("Infinity".to_string()),
+        0xF000 => return Ok("-Infinity".to_string()),
+        _ => {}
+    }
+    let digit = |index: i32| -> u16 {
+        if index < 0 || index as usize >= ndigits {
+            0
+        } else {
+            read(8 + index as usize * 2)
+        }
+    };
+
+    let mut text = String::new();
+    if sign == 0x4000 {
+        text.push('-');
+    }
+    if weight < 0 {
+        text.push('0');
+    } else {
+        text.push_str(&digit(0).to_string());
+        for index in 1..=weight {
+            text.push_str(&format!("{:04}", digit(index)));
+        }
+    }
+    if dscale > 0 {
+        let mut fraction = String::new();
+        let mut index = weight + 1;
+        while fraction.len() < dscale {
+            fraction.push_str(&format!("{:04}", digit(index)));
+            index += 1;
+        }
+        fraction.truncate(dscale);
+        text.push('.');
+        text.push_str(&fraction);
+    }
+    Ok(text)
+}
@@ -0,0 +1,510 @@
+use crate::{RdbcError, RdbcResult, RdbcValue};
+use serde::ser::{self, Impossible, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Converts any serializable value, e.g. an entity, into an [`RdbcValue`]
+/// without a JSON round trip. Structs and maps become objects, sequences
+/// arrays, and byte buffers (`serde_bytes`) bytes. Dates and times, which
+/// chrono writes as formatted text, come back as date, date time and time
+/// values.
+pub fn to_rdbc_value<T>(value: &T) -> RdbcResult<RdbcValue>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(RdbcValueSerializer)
+}
+
+/// Converts a struct or map into the params map of `#{field}` placeholders.
+pub fn to_rdbc_params<T>(value: &T) -> RdbcResult<HashMap<String, RdbcValue>>
+where
+    T: Serialize + ?Sized,
+{
+    match to_rdbc_value(value)? {
+        RdbcValue::Object(params) => Ok(params),
+        v => Err(RdbcError::Serde(format!(
+            "params must serialize to an object, found {:?}",
+            v
+        ))),
+    }
+}
+
+impl ser::Error for RdbcError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        RdbcError::Serde(msg.to_string())
+    }
+}
+
+/// Serializer producing [`RdbcValue`]s, see [`to_rdbc_value`].
+pub struct RdbcValueSerializer;
+
+impl ser::Serializer for RdbcValueSerializer {
+    type Ok = RdbcValue;
+    type Error = RdbcError;
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeVariant<SerializeObject>;
+
+    fn serialize_bool(self, v: bool) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Boolean(v))
+    }
+    fn serialize_i8(self, v: i8) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::SmallInt(v))
+    }
+    fn serialize_i16(self, v: i16) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Int(v as i32))
+    }
+    fn serialize_i32(self, v: i32) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Int(v))
+    }
+    fn serialize_i64(self, v: i64) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::BigInt(v))
+    }
+    fn serialize_u8(self, v: u8) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Int(v as i32))
+    }
+    fn serialize_u16(self, v: u16) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Int(v as i32))
+    }
+    fn serialize_u32(self, v: u32) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::BigInt(v as i64))
+    }
+    fn serialize_u64(self, v: u64) -> RdbcResult<RdbcValue> {
+        i64::try_from(v)
+            .map(RdbcValue::BigInt)
+            .map_err(|_| RdbcError::Serde(format!("{} does not fit a BIGINT", v)))
+    }
+    fn serialize_f32(self, v: f32) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Double(v))
+    }
+    fn serialize_f64(self, v: f64) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::BigDouble(v))
+    }
+    fn serialize_char(self, v: char) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Char(v))
+    }
+    fn serialize_str(self, v: &str) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Varchar(v.to_string()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Bytes(v.to_vec()))
+    }
+    fn serialize_none(self) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Null)
+    }
+    fn serialize_some<T>(self, value: &T) -> RdbcResult<RdbcValue>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Null)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Null)
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Varchar(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> RdbcResult<RdbcValue>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> RdbcResult<RdbcValue>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut object = HashMap::new();
+        object.insert(variant.to_string(), value.serialize(self)?);
+        Ok(RdbcValue::Object(object))
+    }
+    fn serialize_seq(self, len: Option<usize>) -> RdbcResult<SerializeArray> {
+        Ok(SerializeArray {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> RdbcResult<SerializeArray> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> RdbcResult<SerializeArray> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> RdbcResult<SerializeVariant<SerializeArray>> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+    fn serialize_map(self, _len: Option<usize>) -> RdbcResult<SerializeObject> {
+        Ok(SerializeObject {
+            object: HashMap::new(),
+            key: None,
+        })
+    }
+    fn serialize_struct(self, _name: &'static str, len: usize) -> RdbcResult<SerializeObject> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> RdbcResult<SerializeVariant<SerializeObject>> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+
+    /// Display formatted values, as chrono writes its dates and times. Text
+    /// in exactly those formats is read back as a date or time value.
+    fn collect_str<T>(self, value: &T) -> RdbcResult<RdbcValue>
+    where
+        T: Display + ?Sized,
+    {
+        let text = value.to_string();
+        Ok(parse_temporal(&text).unwrap_or(RdbcValue::Varchar(text)))
+    }
+}
+
+/// Date, date time or time in the ISO 8601 formats chrono serializes.
+fn parse_temporal(text: &str) -> Option<RdbcValue> {
+    let bytes = text.as_bytes();
+    if bytes.len() == 10 && bytes[4] == b'-' && bytes[7] == b'-' {
+        return chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .map(RdbcValue::Date);
+    }
+    if bytes.len() >= 19 && bytes[10] == b'T' {
+        if let Ok(v) = chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f") {
+            return Some(RdbcValue::DateTime(v));
+        }
+        return chrono::DateTime::parse_from_rfc3339(text)
+            .ok()
+            .map(|v| RdbcValue::DateTime(v.naive_utc()));
+    }
+    if bytes.len() >= 8 && bytes[2] == b':' && bytes[5] == b':' {
+        return chrono::NaiveTime::parse_from_str(text, "%H:%M:%S%.f")
+            .ok()
+            .map(RdbcValue::Time);
+    }
+    None
+}
+
+pub struct SerializeArray {
+    items: Vec<RdbcValue>,
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = RdbcValue;
+    type Error = RdbcError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> RdbcResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.items.push(value.serialize(RdbcValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Array(self.items))
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = RdbcValue;
+    type Error = RdbcError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> RdbcResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> RdbcResult<RdbcValue> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = RdbcValue;
+    type Error = RdbcError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> RdbcResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> RdbcResult<RdbcValue> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+pub struct SerializeObject {
+    object: HashMap<String, RdbcValue>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = RdbcValue;
+    type Error = RdbcError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> RdbcResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+    fn serialize_value<T>(&mut self, value: &T) -> RdbcResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| RdbcError::Serde("map value without a key".to_string()))?;
+        self.object
+            .insert(key, value.serialize(RdbcValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> RdbcResult<RdbcValue> {
+        Ok(RdbcValue::Object(self.object))
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = RdbcValue;
+    type Error = RdbcError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> RdbcResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.object
+            .insert(key.to_string(), value.serialize(RdbcValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> RdbcResult<RdbcValue> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// Enum variant with data, an object with the variant name as its only key.
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<S> SerializeVariant<S> {
+    fn wrap(variant: &'static str, value: RdbcValue) -> RdbcValue {
+        let mut object = HashMap::new();
+        object.insert(variant.to_string(), value);
+        RdbcValue::Object(object)
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = RdbcValue;
+    type Error = RdbcError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> RdbcResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+    fn end(self) -> RdbcResult<RdbcValue> {
+        let value = ser::SerializeSeq::end(self.inner)?;
+        Ok(Self::wrap(self.variant, value))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeObject> {
+    type Ok = RdbcValue;
+    type Error = RdbcError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> RdbcResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+    fn end(self) -> RdbcResult<RdbcValue> {
+        let value = ser::SerializeMap::end(self.inner)?;
+        Ok(Self::wrap(self.variant, value))
+    }
+}
+
+/// Object keys: strings, chars, integers and unit variants.
+struct MapKeySerializer;
+
+fn key_error() -> RdbcError {
+    RdbcError::Serde("object keys must be strings or integers".to_string())
+}
+
+impl ser::Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = RdbcError;
+    type SerializeSeq = Impossible<String, RdbcError>;
+    type SerializeTuple = Impossible<String, RdbcError>;
+    type SerializeTupleStruct = Impossible<String, RdbcError>;
+    type SerializeTupleVariant = Impossible<String, RdbcError>;
+    type SerializeMap = Impossible<String, RdbcError>;
+    type SerializeStruct = Impossible<String, RdbcError>;
+    type SerializeStructVariant = Impossible<String, RdbcError>;
+
+    fn serialize_bool(self, _v: bool) -> RdbcResult<String> {
+        Err(key_error())
+    }
+    fn serialize_i8(self, v: i8) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_i16(self, v: i16) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_i32(self, v: i32) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_i64(self, v: i64) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_u8(self, v: u8) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_u16(self, v: u16) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_u32(self, v: u32) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_u64(self, v: u64) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_f32(self, _v: f32) -> RdbcResult<String> {
+        Err(key_error())
+    }
+    fn serialize_f64(self, _v: f64) -> RdbcResult<String> {
+        Err(key_error())
+    }
+    fn serialize_char(self, v: char) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_str(self, v: &str) -> RdbcResult<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> RdbcResult<String> {
+        Err(key_error())
+    }
+    fn serialize_none(self) -> RdbcResult<String> {
+        Err(key_error())
+    }
+    fn serialize_some<T>(self, value: &T) -> RdbcResult<String>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> RdbcResult<String> {
+        Err(key_error())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> RdbcResult<String> {
+        Err(key_error())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> RdbcResult<String> {
+        Ok(variant.to_string())
+    }
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> RdbcResult<String>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> RdbcResult<String>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(key_error())
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> RdbcResult<Self::SerializeSeq> {
+        Err(key_error())
+    }
+    fn serialize_tuple(self, _len: usize) -> RdbcResult<Self::SerializeTuple> {
+        Err(key_error())
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> RdbcResult<Self::SerializeTupleStruct> {
+        Err(key_error())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> RdbcResult<Self::SerializeTupleVariant> {
+        Err(key_error())
+    }
+    fn serialize_map(self, _len: Option<usize>) -> RdbcResult<Self::SerializeMap> {
+        Err(key_error())
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> RdbcResult<Self::SerializeStruct> {
+        Err(key_error())
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> RdbcResult<Self::SerializeStructVariant> {
+        Err(key_error())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::bean::value::RdbcValue;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use rusqlite::types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef};
+
+/// Binds values in SQLite storage classes: dates and times as ISO 8601 text,
+/// booleans as 0 and 1, arrays and objects as JSON text.
+impl ToSql for RdbcValue {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value = match self {
+            RdbcValue::Char(v) => Value::Text(v.to_string()),
+            RdbcValue::Varchar(v) | RdbcValue::Text(v) | RdbcValue::LongText(v) => {
+                return Ok(ToSqlOutput::Borrowed(ValueRef::Text(v.as_bytes())))
+            }
+            RdbcValue::SmallInt(v) => Value::Integer(*v as i64),
+            RdbcValue::Int(v) => Value::Integer(*v as i64),
+            RdbcValue::BigInt(v) => Value::Integer(*v),
+            RdbcValue::Double(v) => Value::Real(*v as f64),
+            RdbcValue::BigDouble(v) => Value::Real(*v),
+            RdbcValue::Date(v) => Value::Text(v.format("%Y-%m-%d").to_string()),
+            RdbcValue::DateTime(v) => Value::Text(v.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+            RdbcValue::Time(v) => Value::Text(v.format("%H:%M:%S%.f").to_string()),
+            RdbcValue::TimeStamp(v) => Value::Integer(i64::try_from(*v).map_err(|_| {
+                rusqlite::Error::ToSqlConversionFailure(
+                    format!("{} is out of range for type INTEGER", v).into(),
+                )
+            })?),
+            RdbcValue::Bytes(v) => return Ok(ToSqlOutput::Borrowed(ValueRef::Blob(v))),
+            RdbcValue::Boolean(v) => Value::Integer(*v as i64),
+            RdbcValue::Array(_) | RdbcValue::Object(_) => Value::Text(
+                serde_json::to_string(self)
+                    .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?,
+            ),
+            RdbcValue::Null => Value::Null,
+        };
+        Ok(ToSqlOutput::Owned(value))
+    }
+}
+
+impl FromSql for RdbcValue {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Ok(sqlite_value(value, None))
+    }
+}
+
+/// Converts a SQLite value, refined by the declared type of its column:
+/// `BOOLEAN` integers as booleans and `DATE`, `DATETIME`, `TIMESTAMP` and
+/// `TIME` text as dates and times when it parses.
+pub(crate) fn sqlite_value(value: ValueRef<'_>, decl_type: Option<&str>) -> RdbcValue {
+    let decl_type = decl_type.unwrap_or_default().to_uppercase();
+    match value {
+        ValueRef::Null => RdbcValue::Null,
+        ValueRef::Integer(v) if decl_type.contains("BOOL") => RdbcValue::Boolean(v != 0),
+        ValueRef::Integer(v) => RdbcValue::BigInt(v),
+        ValueRef::Real(v) => RdbcValue::BigDouble(v),
+        ValueRef::Text(v) => {
+            let text = String::from_utf8_lossy(v).into_owned();
+            temporal_value(&text, &decl_type).unwrap_or(RdbcValue::Varchar(text))
+        }
+        ValueRef::Blob(v) => RdbcValue::Bytes(v.to_vec()),
+    }
+}
+
+fn temporal_value(text: &str, decl_type: &str) -> Option<RdbcValue> {
+    if decl_type.contains("DATETIME") || decl_type.contains("TIMESTAMP") {
+        ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+            .map(RdbcValue::DateTime)
+    } else if decl_type.contains("DATE") {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .map(RdbcValue::Date)
+    } else if decl_type.contains("TIME") {
+        NaiveTime::parse_from_str(text, "%H:%M:%S%.f")
+            .ok()
+            .map(RdbcValue::Time)
+    } else {
+        None
+    }
+}
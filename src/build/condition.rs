@@ -1,6 +1,6 @@
 use crate::{
-    RdbcColumn, RdbcColumnIdent, RdbcCondition, RdbcDeleteWrapper, RdbcTable, RdbcTableIdent,
-    RdbcValue,
+    RdbcColumn, RdbcColumnIdent, RdbcCondition, RdbcDeleteWrapper, RdbcRawSql, RdbcTable,
+    RdbcTableIdent, RdbcValue,
 };
 
 pub trait RdbcWhereCondition {
@@ -22,4 +22,27 @@ pub trait RdbcWhereCondition {
         self.get_or_create_where_condition().eq(column, value);
         self
     }
+
+    fn raw(&mut self, raw: RdbcRawSql) -> &mut Self {
+        self.get_or_create_where_condition().raw(raw);
+        self
+    }
+
+    fn eq_script<C, V>(&mut self, column: C, path: V) -> &mut Self
+    where
+        RdbcColumn: From<C>,
+        V: ToString,
+    {
+        self.get_or_create_where_condition().eq_script(column, path);
+        self
+    }
+
+    fn in_script<C, V>(&mut self, column: C, path: V) -> &mut Self
+    where
+        RdbcColumn: From<C>,
+        V: ToString,
+    {
+        self.get_or_create_where_condition().in_script(column, path);
+        self
+    }
 }
@@ -0,0 +1,836 @@
+use crate::{
+    AlterColumn, AlterSequenceDDLWrapper, AlterTableAction, AlterTableDDLWrapper, ColumnDDLWrapper,
+    ColumnDefault, ColumnIdentity, CommentDDLWrapper, CopyTableDDLWrapper, DropIndexDDLWrapper,
+    DropSequenceDDLWrapper, DropTableDDLWrapper, DropViewDDLWrapper, ForeignKeyDDLWrapper,
+    IndexColumn, IndexDDLWrapper, RdbcColumnType, RdbcDdlWrapper, RdbcQueryWrapper, RdbcValue,
+    ReferentialAction, RefreshViewDDLWrapper, SequenceDDLWrapper, TableConstraint, TableDDLWrapper,
+    ViewDDLWrapper,
+};
+
+impl ColumnDDLWrapper {
+    pub fn new<N>(name: N, column_type: RdbcColumnType) -> Self
+    where
+        N: ToString,
+    {
+        ColumnDDLWrapper {
+            name: name.to_string(),
+            column_type,
+            nullable: true,
+            default_value: None,
+            identity: None,
+            primary_key: false,
+            unique: false,
+            check: None,
+            comment: None,
+        }
+    }
+    pub fn not_null(&mut self) -> &mut Self {
+        self.nullable = false;
+        self
+    }
+    pub fn nullable(&mut self) -> &mut Self {
+        self.nullable = true;
+        self
+    }
+    /// Sets a literal default value.
+    pub fn default_value<V>(&mut self, value: V) -> &mut Self
+    where
+        RdbcValue: From<V>,
+    {
+        self.default_value = Some(ColumnDefault::Value(RdbcValue::from(value)));
+        self
+    }
+    /// Sets a default expression such as `CURRENT_TIMESTAMP`, rendered verbatim.
+    pub fn default_expr<E>(&mut self, expression: E) -> &mut Self
+    where
+        E: ToString,
+    {
+        self.default_value = Some(ColumnDefault::Expression(expression.to_string()));
+        self
+    }
+    /// Numbers the column automatically: an identity column on PostgreSQL and
+    /// Oracle, `AUTO_INCREMENT` on MySQL and `INTEGER PRIMARY KEY AUTOINCREMENT`
+    /// on SQLite. Also makes it NOT NULL.
+    pub fn identity(&mut self) -> &mut Self {
+        self.identity.get_or_insert_with(ColumnIdentity::default);
+        self.nullable = false;
+        self
+    }
+    /// Identity numbering starting at `start` and stepping by `increment`.
+    pub fn identity_start(&mut self, start: i64, increment: i64) -> &mut Self {
+        self.identity();
+        let identity = self.identity.get_or_insert_with(ColumnIdentity::default);
+        identity.start = Some(start);
+        identity.increment = Some(increment);
+        self
+    }
+    /// Identity values can not be supplied by inserts (`GENERATED ALWAYS`).
+    pub fn identity_always(&mut self) -> &mut Self {
+        self.identity();
+        self.identity
+            .get_or_insert_with(ColumnIdentity::default)
+            .always = true;
+        self
+    }
+    /// Marks the column as (part of) the primary key, which also makes it NOT NULL.
+    pub fn primary_key(&mut self) -> &mut Self {
+        self.primary_key = true;
+        self.nullable = false;
+        self
+    }
+    pub fn unique(&mut self) -> &mut Self {
+        self.unique = true;
+        self
+    }
+    pub fn check<E>(&mut self, expression: E) -> &mut Self
+    where
+        E: ToString,
+    {
+        self.check = Some(expression.to_string());
+        self
+    }
+    pub fn comment<C>(&mut self, comment: C) -> &mut Self
+    where
+        C: ToString,
+    {
+        self.comment = Some(comment.to_string());
+        self
+    }
+}
+
+impl TableDDLWrapper {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: ToString,
+    {
+        TableDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            columns: vec![],
+            constraints: vec![],
+            if_not_exists: false,
+            comment: None,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn if_not_exists(&mut self) -> &mut Self {
+        self.if_not_exists = true;
+        self
+    }
+    pub fn comment<C>(&mut self, comment: C) -> &mut Self
+    where
+        C: ToString,
+    {
+        self.comment = Some(comment.to_string());
+        self
+    }
+    /// Appends a column and returns it for further configuration.
+    pub fn add_column<N>(&mut self, name: N, column_type: RdbcColumnType) -> &mut ColumnDDLWrapper
+    where
+        N: ToString,
+    {
+        self.columns.push(ColumnDDLWrapper::new(name, column_type));
+        self.columns.last_mut().unwrap()
+    }
+    pub fn column(&mut self, column: ColumnDDLWrapper) -> &mut Self {
+        self.columns.push(column);
+        self
+    }
+    /// Declares a (composite) primary key over `columns`.
+    pub fn primary_key<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: ToString,
+    {
+        self.constraints.push(TableConstraint::PrimaryKey {
+            name: None,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+        });
+        self
+    }
+    pub fn unique<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: ToString,
+    {
+        self.constraints.push(TableConstraint::Unique {
+            name: None,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+        });
+        self
+    }
+    pub fn check<E>(&mut self, expression: E) -> &mut Self
+    where
+        E: ToString,
+    {
+        self.constraints.push(TableConstraint::Check {
+            name: None,
+            expression: expression.to_string(),
+        });
+        self
+    }
+    /// Adds a foreign key and returns it for further configuration.
+    pub fn foreign_key<C, T, R>(
+        &mut self,
+        columns: &[C],
+        ref_table: T,
+        ref_columns: &[R],
+    ) -> &mut ForeignKeyDDLWrapper
+    where
+        C: ToString,
+        T: ToString,
+        R: ToString,
+    {
+        self.constraints
+            .push(TableConstraint::ForeignKey(ForeignKeyDDLWrapper::new(
+                columns,
+                ref_table,
+                ref_columns,
+            )));
+        match self.constraints.last_mut() {
+            Some(TableConstraint::ForeignKey(foreign_key)) => foreign_key,
+            _ => unreachable!(),
+        }
+    }
+    pub fn constraint(&mut self, constraint: TableConstraint) -> &mut Self {
+        self.constraints.push(constraint);
+        self
+    }
+}
+
+impl ForeignKeyDDLWrapper {
+    pub fn new<C, T, R>(columns: &[C], ref_table: T, ref_columns: &[R]) -> Self
+    where
+        C: ToString,
+        T: ToString,
+        R: ToString,
+    {
+        ForeignKeyDDLWrapper {
+            name: None,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            ref_table: ref_table.to_string(),
+            ref_columns: ref_columns.iter().map(|c| c.to_string()).collect(),
+            on_delete: None,
+            on_update: None,
+        }
+    }
+    pub fn name<N>(&mut self, name: N) -> &mut Self
+    where
+        N: ToString,
+    {
+        self.name = Some(name.to_string());
+        self
+    }
+    pub fn on_delete(&mut self, action: ReferentialAction) -> &mut Self {
+        self.on_delete = Some(action);
+        self
+    }
+    pub fn on_update(&mut self, action: ReferentialAction) -> &mut Self {
+        self.on_update = Some(action);
+        self
+    }
+}
+
+impl From<ForeignKeyDDLWrapper> for TableConstraint {
+    fn from(value: ForeignKeyDDLWrapper) -> Self {
+        TableConstraint::ForeignKey(value)
+    }
+}
+
+impl IndexDDLWrapper {
+    pub fn new<T, N>(table: T, name: N) -> Self
+    where
+        T: ToString,
+        N: ToString,
+    {
+        IndexDDLWrapper {
+            schema: "".to_string(),
+            table: table.to_string(),
+            name: name.to_string(),
+            columns: vec![],
+            unique: false,
+            where_condition: None,
+            concurrently: false,
+            if_not_exists: false,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn column<C>(&mut self, column: C) -> &mut Self
+    where
+        C: ToString,
+    {
+        self.columns.push(IndexColumn::Column(column.to_string()));
+        self
+    }
+    pub fn columns<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: ToString,
+    {
+        for column in columns {
+            self.column(column.to_string());
+        }
+        self
+    }
+    /// Indexes an expression such as `lower(email)`.
+    pub fn expression<E>(&mut self, expression: E) -> &mut Self
+    where
+        E: ToString,
+    {
+        self.columns
+            .push(IndexColumn::Expression(expression.to_string()));
+        self
+    }
+    pub fn unique(&mut self) -> &mut Self {
+        self.unique = true;
+        self
+    }
+    /// Makes the index partial, covering only rows matching `condition`.
+    pub fn where_condition<W>(&mut self, condition: W) -> &mut Self
+    where
+        W: ToString,
+    {
+        self.where_condition = Some(condition.to_string());
+        self
+    }
+    pub fn concurrently(&mut self) -> &mut Self {
+        self.concurrently = true;
+        self
+    }
+    pub fn if_not_exists(&mut self) -> &mut Self {
+        self.if_not_exists = true;
+        self
+    }
+}
+
+impl DropIndexDDLWrapper {
+    pub fn new<T, N>(table: T, name: N) -> Self
+    where
+        T: ToString,
+        N: ToString,
+    {
+        DropIndexDDLWrapper {
+            schema: "".to_string(),
+            table: table.to_string(),
+            name: name.to_string(),
+            concurrently: false,
+            if_exists: false,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn concurrently(&mut self) -> &mut Self {
+        self.concurrently = true;
+        self
+    }
+    pub fn if_exists(&mut self) -> &mut Self {
+        self.if_exists = true;
+        self
+    }
+}
+
+impl CommentDDLWrapper {
+    /// Comment on `table`.
+    pub fn on_table<T, C>(table: T, comment: C) -> Self
+    where
+        T: ToString,
+        C: ToString,
+    {
+        CommentDDLWrapper {
+            schema: "".to_string(),
+            table: table.to_string(),
+            column: None,
+            comment: comment.to_string(),
+            column_definition: None,
+        }
+    }
+    /// Comment on `table.column`.
+    pub fn on_column<T, N, C>(table: T, column: N, comment: C) -> Self
+    where
+        T: ToString,
+        N: ToString,
+        C: ToString,
+    {
+        CommentDDLWrapper {
+            schema: "".to_string(),
+            table: table.to_string(),
+            column: Some(column.to_string()),
+            comment: comment.to_string(),
+            column_definition: None,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn column_definition(&mut self, column: ColumnDDLWrapper) -> &mut Self {
+        self.column_definition = Some(column);
+        self
+    }
+}
+
+impl ViewDDLWrapper {
+    pub fn new<N>(name: N, query: RdbcQueryWrapper) -> Self
+    where
+        N: ToString,
+    {
+        ViewDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            columns: vec![],
+            query,
+            or_replace: false,
+            materialized: false,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    /// Names the view columns instead of taking them from the query.
+    pub fn columns<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: ToString,
+    {
+        self.columns = columns.iter().map(|c| c.to_string()).collect();
+        self
+    }
+    pub fn or_replace(&mut self) -> &mut Self {
+        self.or_replace = true;
+        self
+    }
+    pub fn materialized(&mut self) -> &mut Self {
+        self.materialized = true;
+        self
+    }
+}
+
+impl DropViewDDLWrapper {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: ToString,
+    {
+        DropViewDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            materialized: false,
+            if_exists: false,
+            cascade: false,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn materialized(&mut self) -> &mut Self {
+        self.materialized = true;
+        self
+    }
+    pub fn if_exists(&mut self) -> &mut Self {
+        self.if_exists = true;
+        self
+    }
+    pub fn cascade(&mut self) -> &mut Self {
+        self.cascade = true;
+        self
+    }
+}
+
+impl RefreshViewDDLWrapper {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: ToString,
+    {
+        RefreshViewDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            concurrently: false,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn concurrently(&mut self) -> &mut Self {
+        self.concurrently = true;
+        self
+    }
+}
+
+impl AlterColumn {
+    pub fn new(column: ColumnDDLWrapper) -> Self {
+        AlterColumn {
+            column,
+            change_type: false,
+            change_nullable: false,
+            change_default: false,
+            change_unique: false,
+        }
+    }
+    pub fn change_type(&mut self) -> &mut Self {
+        self.change_type = true;
+        self
+    }
+    pub fn change_nullable(&mut self) -> &mut Self {
+        self.change_nullable = true;
+        self
+    }
+    pub fn change_default(&mut self) -> &mut Self {
+        self.change_default = true;
+        self
+    }
+    pub fn change_unique(&mut self) -> &mut Self {
+        self.change_unique = true;
+        self
+    }
+}
+
+impl AlterTableDDLWrapper {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: ToString,
+    {
+        AlterTableDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            actions: vec![],
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn add_column(&mut self, column: ColumnDDLWrapper) -> &mut Self {
+        self.actions.push(AlterTableAction::AddColumn(column));
+        self
+    }
+    pub fn drop_column<N>(&mut self, name: N) -> &mut Self
+    where
+        N: ToString,
+    {
+        self.actions
+            .push(AlterTableAction::DropColumn(name.to_string()));
+        self
+    }
+    /// Alters an existing column towards `column`; flag the changed aspects on the result.
+    pub fn alter_column(&mut self, column: ColumnDDLWrapper) -> &mut AlterColumn {
+        self.actions
+            .push(AlterTableAction::AlterColumn(AlterColumn::new(column)));
+        match self.actions.last_mut() {
+            Some(AlterTableAction::AlterColumn(alter)) => alter,
+            _ => unreachable!(),
+        }
+    }
+    pub fn rename_column<F, T>(&mut self, from: F, to: T) -> &mut Self
+    where
+        F: ToString,
+        T: ToString,
+    {
+        self.actions.push(AlterTableAction::RenameColumn {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+        self
+    }
+    pub fn rename_to<N>(&mut self, name: N) -> &mut Self
+    where
+        N: ToString,
+    {
+        self.actions
+            .push(AlterTableAction::RenameTable(name.to_string()));
+        self
+    }
+    pub fn add_constraint(&mut self, constraint: TableConstraint) -> &mut Self {
+        self.actions
+            .push(AlterTableAction::AddConstraint(constraint));
+        self
+    }
+    pub fn drop_constraint<N>(&mut self, name: N) -> &mut Self
+    where
+        N: ToString,
+    {
+        self.actions
+            .push(AlterTableAction::DropConstraint(name.to_string()));
+        self
+    }
+}
+
+impl DropTableDDLWrapper {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: ToString,
+    {
+        DropTableDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            if_exists: false,
+            cascade: false,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn if_exists(&mut self) -> &mut Self {
+        self.if_exists = true;
+        self
+    }
+    pub fn cascade(&mut self) -> &mut Self {
+        self.cascade = true;
+        self
+    }
+}
+
+impl SequenceDDLWrapper {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: ToString,
+    {
+        SequenceDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            start: None,
+            increment: None,
+            min_value: None,
+            max_value: None,
+            cache: None,
+            cycle: false,
+            if_not_exists: false,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn start(&mut self, start: i64) -> &mut Self {
+        self.start = Some(start);
+        self
+    }
+    pub fn increment(&mut self, increment: i64) -> &mut Self {
+        self.increment = Some(increment);
+        self
+    }
+    pub fn min_value(&mut self, min_value: i64) -> &mut Self {
+        self.min_value = Some(min_value);
+        self
+    }
+    pub fn max_value(&mut self, max_value: i64) -> &mut Self {
+        self.max_value = Some(max_value);
+        self
+    }
+    pub fn cache(&mut self, cache: i64) -> &mut Self {
+        self.cache = Some(cache);
+        self
+    }
+    pub fn cycle(&mut self) -> &mut Self {
+        self.cycle = true;
+        self
+    }
+    pub fn if_not_exists(&mut self) -> &mut Self {
+        self.if_not_exists = true;
+        self
+    }
+}
+
+impl AlterSequenceDDLWrapper {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: ToString,
+    {
+        AlterSequenceDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            restart: None,
+            increment: None,
+            min_value: None,
+            max_value: None,
+            cache: None,
+            cycle: None,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    /// Restarts the sequence so that the next value is `value`.
+    pub fn restart(&mut self, value: i64) -> &mut Self {
+        self.restart = Some(value);
+        self
+    }
+    pub fn increment(&mut self, increment: i64) -> &mut Self {
+        self.increment = Some(increment);
+        self
+    }
+    pub fn min_value(&mut self, min_value: i64) -> &mut Self {
+        self.min_value = Some(min_value);
+        self
+    }
+    pub fn max_value(&mut self, max_value: i64) -> &mut Self {
+        self.max_value = Some(max_value);
+        self
+    }
+    pub fn cache(&mut self, cache: i64) -> &mut Self {
+        self.cache = Some(cache);
+        self
+    }
+    pub fn cycle(&mut self, cycle: bool) -> &mut Self {
+        self.cycle = Some(cycle);
+        self
+    }
+}
+
+impl DropSequenceDDLWrapper {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: ToString,
+    {
+        DropSequenceDDLWrapper {
+            schema: "".to_string(),
+            name: name.to_string(),
+            if_exists: false,
+            cascade: false,
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn if_exists(&mut self) -> &mut Self {
+        self.if_exists = true;
+        self
+    }
+    pub fn cascade(&mut self) -> &mut Self {
+        self.cascade = true;
+        self
+    }
+}
+
+impl CopyTableDDLWrapper {
+    pub fn new<F, T, C>(from: F, to: T, columns: &[C]) -> Self
+    where
+        F: ToString,
+        T: ToString,
+        C: ToString,
+    {
+        CopyTableDDLWrapper {
+            schema: "".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+        }
+    }
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+}
+
+impl From<TableDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: TableDDLWrapper) -> Self {
+        RdbcDdlWrapper::CreateTable(value)
+    }
+}
+impl From<AlterTableDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: AlterTableDDLWrapper) -> Self {
+        RdbcDdlWrapper::AlterTable(value)
+    }
+}
+impl From<DropTableDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: DropTableDDLWrapper) -> Self {
+        RdbcDdlWrapper::DropTable(value)
+    }
+}
+impl From<CommentDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: CommentDDLWrapper) -> Self {
+        RdbcDdlWrapper::Comment(value)
+    }
+}
+impl From<ViewDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: ViewDDLWrapper) -> Self {
+        RdbcDdlWrapper::CreateView(value)
+    }
+}
+impl From<DropViewDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: DropViewDDLWrapper) -> Self {
+        RdbcDdlWrapper::DropView(value)
+    }
+}
+impl From<RefreshViewDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: RefreshViewDDLWrapper) -> Self {
+        RdbcDdlWrapper::RefreshView(value)
+    }
+}
+impl From<IndexDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: IndexDDLWrapper) -> Self {
+        RdbcDdlWrapper::CreateIndex(value)
+    }
+}
+impl From<DropIndexDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: DropIndexDDLWrapper) -> Self {
+        RdbcDdlWrapper::DropIndex(value)
+    }
+}
+impl From<CopyTableDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: CopyTableDDLWrapper) -> Self {
+        RdbcDdlWrapper::CopyTable(value)
+    }
+}
+impl From<SequenceDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: SequenceDDLWrapper) -> Self {
+        RdbcDdlWrapper::CreateSequence(value)
+    }
+}
+impl From<AlterSequenceDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: AlterSequenceDDLWrapper) -> Self {
+        RdbcDdlWrapper::AlterSequence(value)
+    }
+}
+impl From<DropSequenceDDLWrapper> for RdbcDdlWrapper {
+    fn from(value: DropSequenceDDLWrapper) -> Self {
+        RdbcDdlWrapper::DropSequence(value)
+    }
+}
@@ -3,8 +3,41 @@ use crate::{
     DmlColumn, JoinTable, RdbcColumn, RdbcColumnValue, RdbcCondition, RdbcDeleteWrapper,
     RdbcInsertWrapper, RdbcQueryWrapper, RdbcTable, RdbcTableIdent, RdbcUpdateWrapper, RdbcValue,
 };
+use std::collections::HashMap;
 
-impl RdbcInsertWrapper {}
+impl RdbcInsertWrapper {
+    pub fn new<T>(table: T) -> Self
+    where
+        RdbcTable: From<T>,
+    {
+        RdbcInsertWrapper {
+            table: RdbcTable::from(table),
+            column_dml: vec![],
+            columns: vec![],
+            column_value: vec![],
+            column_query: None,
+            params: HashMap::new(),
+        }
+    }
+    /// Inserts `value` into `column`.
+    pub fn value<C, V>(&mut self, column: C, value: V) -> &mut Self
+    where
+        RdbcColumn: From<C>,
+        RdbcColumnValue: From<V>,
+    {
+        self.column_dml.push(DmlColumn::new(column, value));
+        self
+    }
+    /// Adds a param referenced by `#{key}` placeholders in the insert.
+    pub fn param<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: ToString,
+        RdbcValue: From<V>,
+    {
+        self.params.insert(key.to_string(), RdbcValue::from(value));
+        self
+    }
+}
 
 impl RdbcUpdateWrapper {
     pub fn with_table<T>() -> RdbcUpdateWrapper
@@ -23,6 +56,20 @@ impl RdbcUpdateWrapper {
         self.column_dml.push(DmlColumn::new(column, value));
         self
     }
+    /// Permits rendering the update without a WHERE condition.
+    pub fn allow_full_table(&mut self) -> &mut Self {
+        self.allow_full_table = true;
+        self
+    }
+    /// Adds a param referenced by `#{key}` placeholders in the update.
+    pub fn param<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: ToString,
+        RdbcValue: From<V>,
+    {
+        self.params.insert(key.to_string(), RdbcValue::from(value));
+        self
+    }
     fn get_or_create_where_condition(&mut self) -> &mut RdbcCondition {
         self.where_condition.get_or_insert_with(RdbcCondition::new)
     }
@@ -45,6 +92,20 @@ impl RdbcDeleteWrapper {
         wrapper.from(T::name());
         wrapper
     }
+    /// Permits rendering the delete without a WHERE condition.
+    pub fn allow_full_table(&mut self) -> &mut Self {
+        self.allow_full_table = true;
+        self
+    }
+    /// Adds a param referenced by `#{key}` placeholders in the delete.
+    pub fn param<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: ToString,
+        RdbcValue: From<V>,
+    {
+        self.params.insert(key.to_string(), RdbcValue::from(value));
+        self
+    }
 }
 impl RdbcWhereCondition for RdbcDeleteWrapper {
     fn get_or_create_where_condition(&mut self) -> &mut RdbcCondition {
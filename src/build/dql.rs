@@ -1,8 +1,8 @@
 use crate::wrapper::{RdbcColumn, RdbcQueryWrapper, RdbcTable};
 use crate::{
     CompareColumn, CompareKind, CompareLikeKind, ConditionColumn, ConditionKind, JoinTable,
-    RdbcColumnValue, RdbcCondition, RdbcDeleteWrapper, RdbcTableIdent, RdbcUpdateWrapper,
-    RdbcValue,
+    RdbcColumnValue, RdbcCondition, RdbcDeleteWrapper, RdbcRawSql, RdbcTableIdent,
+    RdbcUpdateWrapper, RdbcValue,
 };
 
 use crate::build::condition::RdbcWhereCondition;
@@ -122,6 +122,23 @@ impl RdbcQueryWrapper {
         }
         self
     }
+    /// Adds a param referenced by `#{key}` placeholders in the query.
+    pub fn param<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: ToString,
+        RdbcValue: From<V>,
+    {
+        self.params.insert(key.to_string(), RdbcValue::from(value));
+        self
+    }
+    pub fn limit(&mut self, limit: u64) -> &mut Self {
+        self.limit_count = Some(limit);
+        self
+    }
+    pub fn offset(&mut self, offset: u64) -> &mut Self {
+        self.offset_count = Some(offset);
+        self
+    }
     /// Internal helper to get or initialize the `where_condition`.
     fn get_or_create_where_condition(&mut self) -> &mut RdbcCondition {
         self.where_condition.get_or_insert_with(RdbcCondition::new)
@@ -162,6 +179,27 @@ impl RdbcCondition {
     {
         self.add_condition(CompareKind::NotLike(CompareLikeKind::Both), column, value)
     }
+    /// Adds a raw SQL predicate with its own bound params.
+    pub fn raw(&mut self, raw: RdbcRawSql) -> &mut Self {
+        self.column.push(ConditionColumn::Raw(Box::new(raw)));
+        self
+    }
+    /// Compares the column with the `#{path}` param resolved at render time.
+    pub fn eq_script<T, V>(&mut self, column: T, path: V) -> &mut Self
+    where
+        RdbcColumn: From<T>,
+        V: ToString,
+    {
+        self.add_script_condition(CompareKind::Equal, column, path)
+    }
+    /// Renders `column IN (#{path})`, expanding an array param into a list.
+    pub fn in_script<T, V>(&mut self, column: T, path: V) -> &mut Self
+    where
+        RdbcColumn: From<T>,
+        V: ToString,
+    {
+        self.add_script_condition(CompareKind::In, column, path)
+    }
     pub fn eq_column<T, V>(&mut self, column: T, value: V) -> &mut Self
     where
         RdbcColumn: From<T>,
@@ -204,10 +242,7 @@ impl RdbcCondition {
         self.column.push(ConditionColumn::Compare(CompareColumn {
             column: RdbcColumn::from(column),
             kind,
-            value: RdbcColumnValue::StaticValue(RdbcValue::from(format!(
-                "#{{{}}}",
-                value.to_string()
-            ))),
+            value: RdbcColumnValue::ScriptValue(format!("#{{{}}}", value.to_string())),
             ignore_null: false,
         }));
         self
@@ -1,6 +1,36 @@
+use crate::RdbcError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Target database dialect. Configs spell it as the lower case name, parsed
+/// like [`DataBase::from_str`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", try_from = "String")]
 pub enum DataBase {
     MySql,
     Sqlite,
     Postgres,
     Oracle,
 }
+
+impl FromStr for DataBase {
+    type Err = RdbcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "mysql" | "mariadb" => Ok(DataBase::MySql),
+            "sqlite" | "sqlite3" => Ok(DataBase::Sqlite),
+            "postgres" | "postgresql" | "pg" => Ok(DataBase::Postgres),
+            "oracle" => Ok(DataBase::Oracle),
+            _ => Err(RdbcError::UnknownDataBase(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for DataBase {
+    type Error = RdbcError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
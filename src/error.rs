@@ -0,0 +1,64 @@
+use std::fmt::{Display, Formatter};
+
+/// Errors raised while building or rendering SQL.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RdbcError {
+    /// UPDATE or DELETE without a WHERE clause that was not explicitly allowed.
+    FullTableStatement(String),
+    /// A `#{path}` placeholder that is not present in the params.
+    UnknownParam(String),
+    /// A param whose value cannot be bound where it is referenced.
+    InvalidParam(String),
+    /// A dynamic SQL template that cannot be parsed or rendered.
+    Template(String),
+    /// A database name that does not match any [`crate::DataBase`] variant.
+    UnknownDataBase(String),
+    /// Named SQL that cannot be loaded or found.
+    Registry(String),
+    /// A statement or option the target database cannot express.
+    Unsupported(String),
+    /// A schema change that may lose data, refused by a safe schema diff.
+    Destructive(String),
+    /// A migration that cannot be loaded, validated or applied.
+    Migration(String),
+    /// A datasource config that cannot be loaded, or an unknown datasource.
+    DataSource(String),
+    /// An error reported by the database driver or server.
+    Database(String),
+    /// A transaction aborted by a serialization failure or deadlock, which
+    /// may succeed when retried.
+    Serialization(String),
+    /// A value that cannot be converted from or into a serde data type.
+    Serde(String),
+    /// A result column that cannot be mapped into the field reading it.
+    RowMapping(String),
+}
+
+impl Display for RdbcError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RdbcError::FullTableStatement(statement) => write!(
+                f,
+                "{} without WHERE condition is not allowed, call allow_full_table() to permit it",
+                statement
+            ),
+            RdbcError::UnknownParam(key) => write!(f, "unknown param #{{{}}}", key),
+            RdbcError::InvalidParam(msg) => write!(f, "invalid param: {}", msg),
+            RdbcError::Template(msg) => write!(f, "invalid template: {}", msg),
+            RdbcError::UnknownDataBase(name) => write!(f, "unknown database {}", name),
+            RdbcError::Registry(msg) => write!(f, "sql registry: {}", msg),
+            RdbcError::Unsupported(msg) => write!(f, "unsupported: {}", msg),
+            RdbcError::Destructive(msg) => write!(f, "destructive change refused: {}", msg),
+            RdbcError::Migration(msg) => write!(f, "migration: {}", msg),
+            RdbcError::DataSource(msg) => write!(f, "datasource: {}", msg),
+            RdbcError::Database(msg) => write!(f, "database: {}", msg),
+            RdbcError::Serialization(msg) => write!(f, "serialization failure: {}", msg),
+            RdbcError::Serde(msg) => write!(f, "serde: {}", msg),
+            RdbcError::RowMapping(msg) => write!(f, "row mapping: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RdbcError {}
+
+pub type RdbcResult<T> = Result<T, RdbcError>;
@@ -0,0 +1,295 @@
+use crate::{
+    DataBase, FromRdbcRow, RdbcDeleteWrapper, RdbcError, RdbcInsertWrapper, RdbcPage,
+    RdbcQueryWrapper, RdbcResult, RdbcTransactionOptions, RdbcUpdateWrapper, RdbcValue,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Future returned by [`RdbcExecutor`] methods.
+pub type RdbcFuture<'a, T> = Pin<Box<dyn Future<Output = RdbcResult<T>> + Send + 'a>>;
+
+/// Runs wrappers on whichever database is behind it, so that repository code
+/// can take `&dyn RdbcExecutor` or a generic executor. Implemented by the
+/// executors and transactions of every driver and by pooled connections.
+pub trait RdbcExecutor: Send + Sync {
+    /// Dialect the wrappers are rendered for.
+    fn database(&self) -> DataBase;
+
+    /// Rows of `query`, each an [`RdbcValue::Object`].
+    fn query_list<'a>(&'a self, query: &'a RdbcQueryWrapper) -> RdbcFuture<'a, Vec<RdbcValue>>;
+
+    /// First row of `query`, `None` when nothing matches.
+    fn query_one<'a>(&'a self, query: &'a RdbcQueryWrapper) -> RdbcFuture<'a, Option<RdbcValue>> {
+        Box::pin(async move { Ok(self.query_list(query).await?.into_iter().next()) })
+    }
+
+    /// Rows of `query` keyed by column name whatever the key case, as
+    /// [`FromRdbcRow`] reads them.
+    fn query_rows<'a>(
+        &'a self,
+        query: &'a RdbcQueryWrapper,
+    ) -> RdbcFuture<'a, Vec<HashMap<String, RdbcValue>>>;
+
+    /// Page `page_no` (from 1) of `query` with `page_size` rows, together with
+    /// the total row count.
+    fn query_page<'a>(
+        &'a self,
+        query: &'a RdbcQueryWrapper,
+        page_no: u64,
+        page_size: u64,
+    ) -> RdbcFuture<'a, RdbcPage<RdbcValue>>;
+
+    /// Runs `insert`, returning the number of inserted rows.
+    fn insert<'a>(&'a self, insert: &'a RdbcInsertWrapper) -> RdbcFuture<'a, u64>;
+
+    /// Runs `update`, returning the number of updated rows.
+    fn update<'a>(&'a self, update: &'a RdbcUpdateWrapper) -> RdbcFuture<'a, u64>;
+
+    /// Runs `delete`, returning the number of deleted rows.
+    fn delete<'a>(&'a self, delete: &'a RdbcDeleteWrapper) -> RdbcFuture<'a, u64>;
+
+    /// Runs SQL with params in the placeholder style of
+    /// [`database`](Self::database), returning each row as an object.
+    fn query_sql<'a>(
+        &'a self,
+        sql: &'a str,
+        params: &'a [RdbcValue],
+    ) -> RdbcFuture<'a, Vec<RdbcValue>>;
+
+    /// Runs SQL with params in the placeholder style of
+    /// [`database`](Self::database), returning the number of affected rows.
+    fn execute_sql<'a>(&'a self, sql: &'a str, params: &'a [RdbcValue]) -> RdbcFuture<'a, u64>;
+
+    /// Starts a transaction. On a transaction it starts a nested one as a
+    /// savepoint, which takes no isolation level or access mode.
+    fn begin_with<'a>(
+        &'a mut self,
+        options: &RdbcTransactionOptions,
+    ) -> RdbcFuture<'a, Box<dyn RdbcTransaction<'a> + 'a>>;
+
+    /// Starts a transaction with the session defaults.
+    fn begin(&mut self) -> RdbcFuture<'_, Box<dyn RdbcTransaction<'_> + '_>> {
+        self.begin_with(&RdbcTransactionOptions::default())
+    }
+}
+
+/// A transaction started by [`RdbcExecutor::begin`]. Dropping it without
+/// [`commit`](Self::commit) rolls it back.
+pub trait RdbcTransaction<'a>: RdbcExecutor + 'a {
+    fn commit(self: Box<Self>) -> RdbcFuture<'a, ()>;
+
+    fn rollback(self: Box<Self>) -> RdbcFuture<'a, ()>;
+}
+
+/// Typed queries and closure transactions on any [`RdbcExecutor`], including
+/// `dyn RdbcExecutor`.
+pub trait RdbcExecutorExt: RdbcExecutor {
+    /// Rows of `query` mapped into `T` by column name.
+    fn query_as<'a, T>(
+        &'a self,
+        query: &'a RdbcQueryWrapper,
+    ) -> impl Future<Output = RdbcResult<Vec<T>>> + Send + 'a
+    where
+        T: FromRdbcRow,
+    {
+        async move {
+            self.query_rows(query)
+                .await?
+                .into_iter()
+                .map(T::from_rdbc_row)
+                .collect()
+        }
+    }
+
+    /// First row of `query` mapped into `T`, `None` when nothing matches.
+    fn query_one_as<'a, T>(
+        &'a self,
+        query: &'a RdbcQueryWrapper,
+    ) -> impl Future<Output = RdbcResult<Option<T>>> + Send + 'a
+    where
+        T: FromRdbcRow,
+    {
+        async move {
+            match self.query_rows(query).await?.into_iter().next() {
+                Some(row) => T::from_rdbc_row(row).map(Some),
+                None => Ok(None),
+            }
+        }
+    }
+
+    /// Runs `f` in a transaction and commits it, or rolls it back when `f`
+    /// fails. A transaction failing with [`RdbcError::Serialization`], in `f`
+    /// or on commit, is rerun up to `options.retries` times.
+    fn with_transaction<'a, T, F>(
+        &'a mut self,
+        options: &'a RdbcTransactionOptions,
+        mut f: F,
+    ) -> impl Future<Output = RdbcResult<T>> + 'a
+    where
+        F: AsyncFnMut(&mut dyn RdbcExecutor) -> RdbcResult<T> + 'a,
+    {
+        async move {
+            let mut attempt = 0;
+            loop {
+                let mut transaction = self.begin_with(options).await?;
+                let result = match f(&mut *transaction).await {
+                    Ok(value) => transaction.commit().await.map(|_| value),
+                    Err(err) => {
+                        let _ = transaction.rollback().await;
+                        Err(err)
+                    }
+                };
+                match result {
+                    Err(RdbcError::Serialization(_)) if attempt < options.retries => attempt += 1,
+                    result => return result,
+                }
+            }
+        }
+    }
+}
+
+impl<E: RdbcExecutor + ?Sized> RdbcExecutorExt for E {}
+
+/// Fails for options a nested transaction cannot take.
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+pub(crate) fn check_nested(options: &RdbcTransactionOptions) -> RdbcResult<()> {
+    if options.isolation.is_some() || options.read_only {
+        return Err(RdbcError::Unsupported(
+            "a nested transaction takes no isolation level or access mode".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Name of the savepoint of a nested transaction started through
+/// [`RdbcExecutor::begin_with`], unique within the process.
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+pub(crate) fn savepoint_name() -> String {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static SAVEPOINT: AtomicU64 = AtomicU64::new(0);
+    format!("rdbc_sp_{}", SAVEPOINT.fetch_add(1, Ordering::Relaxed) + 1)
+}
+
+/// Implements [`RdbcExecutor`] for a driver executor, or with `transaction`
+/// for one of its transactions, through their inherent methods of the same
+/// names.
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+macro_rules! impl_rdbc_executor {
+    ($executor:ident, $database:expr) => {
+        impl $crate::RdbcExecutor for $executor {
+            $crate::executor::common::impl_rdbc_executor!(@methods $executor, $database);
+
+            fn begin_with<'a>(
+                &'a mut self,
+                options: &$crate::RdbcTransactionOptions,
+            ) -> $crate::RdbcFuture<'a, Box<dyn $crate::RdbcTransaction<'a> + 'a>> {
+                let options = options.clone();
+                Box::pin(async move {
+                    let transaction = $executor::begin_with(self, &options).await?;
+                    Ok(Box::new(transaction) as Box<dyn $crate::RdbcTransaction<'a> + 'a>)
+                })
+            }
+        }
+    };
+    (transaction $transaction:ident, $database:expr) => {
+        impl $crate::RdbcExecutor for $transaction<'_> {
+            $crate::executor::common::impl_rdbc_executor!(@methods $transaction, $database);
+
+            fn begin_with<'a>(
+                &'a mut self,
+                options: &$crate::RdbcTransactionOptions,
+            ) -> $crate::RdbcFuture<'a, Box<dyn $crate::RdbcTransaction<'a> + 'a>> {
+                let checked = $crate::executor::common::check_nested(options);
+                Box::pin(async move {
+                    checked?;
+                    let name = $crate::executor::common::savepoint_name();
+                    let transaction = $transaction::savepoint(self, &name).await?;
+                    Ok(Box::new(transaction) as Box<dyn $crate::RdbcTransaction<'a> + 'a>)
+                })
+            }
+        }
+
+        impl<'a> $crate::RdbcTransaction<'a> for $transaction<'a> {
+            fn commit(self: Box<Self>) -> $crate::RdbcFuture<'a, ()> {
+                Box::pin($transaction::commit(*self))
+            }
+
+            fn rollback(self: Box<Self>) -> $crate::RdbcFuture<'a, ()> {
+                Box::pin($transaction::rollback(*self))
+            }
+        }
+    };
+    (@methods $executor:ident, $database:expr) => {
+        fn database(&self) -> $crate::DataBase {
+            $database
+        }
+
+        fn query_list<'a>(
+            &'a self,
+            query: &'a $crate::RdbcQueryWrapper,
+        ) -> $crate::RdbcFuture<'a, Vec<$crate::RdbcValue>> {
+            Box::pin($executor::query_list(self, query))
+        }
+
+        fn query_rows<'a>(
+            &'a self,
+            query: &'a $crate::RdbcQueryWrapper,
+        ) -> $crate::RdbcFuture<
+            'a,
+            Vec<std::collections::HashMap<String, $crate::RdbcValue>>,
+        > {
+            Box::pin($executor::query_as(self, query))
+        }
+
+        fn query_page<'a>(
+            &'a self,
+            query: &'a $crate::RdbcQueryWrapper,
+            page_no: u64,
+            page_size: u64,
+        ) -> $crate::RdbcFuture<'a, $crate::RdbcPage<$crate::RdbcValue>> {
+            Box::pin($executor::query_page(self, query, page_no, page_size))
+        }
+
+        fn insert<'a>(
+            &'a self,
+            insert: &'a $crate::RdbcInsertWrapper,
+        ) -> $crate::RdbcFuture<'a, u64> {
+            Box::pin($executor::execute(self, insert))
+        }
+
+        fn update<'a>(
+            &'a self,
+            update: &'a $crate::RdbcUpdateWrapper,
+        ) -> $crate::RdbcFuture<'a, u64> {
+            Box::pin($executor::execute(self, update))
+        }
+
+        fn delete<'a>(
+            &'a self,
+            delete: &'a $crate::RdbcDeleteWrapper,
+        ) -> $crate::RdbcFuture<'a, u64> {
+            Box::pin($executor::execute(self, delete))
+        }
+
+        fn query_sql<'a>(
+            &'a self,
+            sql: &'a str,
+            params: &'a [$crate::RdbcValue],
+        ) -> $crate::RdbcFuture<'a, Vec<$crate::RdbcValue>> {
+            Box::pin($executor::query_sql(self, sql, params))
+        }
+
+        fn execute_sql<'a>(
+            &'a self,
+            sql: &'a str,
+            params: &'a [$crate::RdbcValue],
+        ) -> $crate::RdbcFuture<'a, u64> {
+            Box::pin($executor::execute_sql(self, sql, params))
+        }
+    };
+}
+
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+pub(crate) use impl_rdbc_executor;
@@ -0,0 +1,190 @@
+use crate::{DataBase, RdbcError, RdbcResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Connection settings and pool sizes of one database. The dialect picks
+/// both the driver and the renderer of the executors handed out for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RdbcDataSource {
+    pub dialect: DataBase,
+    pub host: String,
+    /// Port, the driver default when `None`.
+    pub port: Option<u16>,
+    /// Database name, or the file path for SQLite.
+    pub database: String,
+    pub user: String,
+    pub password: String,
+    /// Connections opened when the pool is created. SQLite opens a
+    /// connection per checkout and keeps none.
+    pub pool_min: usize,
+    pub pool_max: usize,
+    /// Seconds to wait for a new connection, unlimited when `None`. MySQL
+    /// checkouts are bounded by this plus `acquire_timeout`; SQLite ignores it.
+    pub connect_timeout: Option<u64>,
+    /// Seconds to wait for a free pooled connection, unlimited when `None`.
+    /// SQLite ignores it.
+    pub acquire_timeout: Option<u64>,
+}
+
+impl Default for RdbcDataSource {
+    fn default() -> Self {
+        RdbcDataSource {
+            dialect: DataBase::Postgres,
+            host: "localhost".to_string(),
+            port: None,
+            database: String::new(),
+            user: String::new(),
+            password: String::new(),
+            pool_min: 0,
+            pool_max: 10,
+            connect_timeout: Some(30),
+            acquire_timeout: Some(30),
+        }
+    }
+}
+
+impl RdbcDataSource {
+    pub fn new(dialect: DataBase) -> Self {
+        RdbcDataSource {
+            dialect,
+            ..Default::default()
+        }
+    }
+
+    pub fn host(&mut self, host: impl Into<String>) -> &mut Self {
+        self.host = host.into();
+        self
+    }
+    pub fn port(&mut self, port: u16) -> &mut Self {
+        self.port = Some(port);
+        self
+    }
+    pub fn database(&mut self, database: impl Into<String>) -> &mut Self {
+        self.database = database.into();
+        self
+    }
+    pub fn user(&mut self, user: impl Into<String>) -> &mut Self {
+        self.user = user.into();
+        self
+    }
+    pub fn password(&mut self, password: impl Into<String>) -> &mut Self {
+        self.password = password.into();
+        self
+    }
+    pub fn pool_size(&mut self, min: usize, max: usize) -> &mut Self {
+        self.pool_min = min;
+        self.pool_max = max;
+        self
+    }
+    pub fn connect_timeout(&mut self, secs: u64) -> &mut Self {
+        self.connect_timeout = Some(secs);
+        self
+    }
+    pub fn acquire_timeout(&mut self, secs: u64) -> &mut Self {
+        self.acquire_timeout = Some(secs);
+        self
+    }
+
+    #[cfg(feature = "config")]
+    pub fn from_toml(content: &str) -> RdbcResult<Self> {
+        toml::from_str(content).map_err(|err| RdbcError::DataSource(err.to_string()))
+    }
+
+    pub fn from_json(content: &str) -> RdbcResult<Self> {
+        serde_json::from_str(content).map_err(|err| RdbcError::DataSource(err.to_string()))
+    }
+
+    /// Reads `{prefix}_DIALECT`, `_HOST`, `_PORT`, `_DATABASE`, `_USER`,
+    /// `_PASSWORD`, `_POOL_MIN`, `_POOL_MAX`, `_CONNECT_TIMEOUT` and
+    /// `_ACQUIRE_TIMEOUT`. Unset variables keep their defaults.
+    pub fn from_env(prefix: &str) -> RdbcResult<Self> {
+        let var = |key: &str| std::env::var(format!("{}_{}", prefix, key)).ok();
+        let mut data_source = RdbcDataSource::default();
+        if let Some(dialect) = var("DIALECT") {
+            data_source.dialect = dialect.parse()?;
+        }
+        if let Some(host) = var("HOST") {
+            data_source.host = host;
+        }
+        if let Some(port) = var("PORT") {
+            data_source.port = Some(parse_var(prefix, "PORT", &port)?);
+        }
+        if let Some(database) = var("DATABASE") {
+            data_source.database = database;
+        }
+        if let Some(user) = var("USER") {
+            data_source.user = user;
+        }
+        if let Some(password) = var("PASSWORD") {
+            data_source.password = password;
+        }
+        if let Some(pool_min) = var("POOL_MIN") {
+            data_source.pool_min = parse_var(prefix, "POOL_MIN", &pool_min)?;
+        }
+        if let Some(pool_max) = var("POOL_MAX") {
+            data_source.pool_max = parse_var(prefix, "POOL_MAX", &pool_max)?;
+        }
+        if let Some(timeout) = var("CONNECT_TIMEOUT") {
+            data_source.connect_timeout = Some(parse_var(prefix, "CONNECT_TIMEOUT", &timeout)?);
+        }
+        if let Some(timeout) = var("ACQUIRE_TIMEOUT") {
+            data_source.acquire_timeout = Some(parse_var(prefix, "ACQUIRE_TIMEOUT", &timeout)?);
+        }
+        Ok(data_source)
+    }
+
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    pub(crate) fn connect_timeout_duration(&self) -> Option<std::time::Duration> {
+        self.connect_timeout.map(std::time::Duration::from_secs)
+    }
+
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    pub(crate) fn acquire_timeout_duration(&self) -> Option<std::time::Duration> {
+        self.acquire_timeout.map(std::time::Duration::from_secs)
+    }
+}
+
+fn parse_var<T>(prefix: &str, key: &str, value: &str) -> RdbcResult<T>
+where
+    T: std::str::FromStr,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|_| RdbcError::DataSource(format!("invalid {}_{} value {:?}", prefix, key, value)))
+}
+
+/// Named datasources, e.g. a `[datasources.main]` table per source in TOML.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RdbcDataSourceConfig {
+    #[serde(default)]
+    pub datasources: HashMap<String, RdbcDataSource>,
+}
+
+impl RdbcDataSourceConfig {
+    #[cfg(feature = "config")]
+    pub fn from_toml(content: &str) -> RdbcResult<Self> {
+        toml::from_str(content).map_err(|err| RdbcError::DataSource(err.to_string()))
+    }
+
+    pub fn from_json(content: &str) -> RdbcResult<Self> {
+        serde_json::from_str(content).map_err(|err| RdbcError::DataSource(err.to_string()))
+    }
+
+    /// Reads one datasource per name from `{PREFIX}_{NAME}_*` variables, see
+    /// [`RdbcDataSource::from_env`].
+    pub fn from_env<I, N>(prefix: &str, names: I) -> RdbcResult<Self>
+    where
+        I: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        let mut datasources = HashMap::new();
+        for name in names {
+            let name = name.into();
+            let env_prefix = format!("{}_{}", prefix, name.to_uppercase());
+            datasources.insert(name, RdbcDataSource::from_env(&env_prefix)?);
+        }
+        Ok(RdbcDataSourceConfig { datasources })
+    }
+}
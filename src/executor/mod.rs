@@ -0,0 +1,127 @@
+pub(crate) mod common;
+mod datasource;
+#[cfg(feature = "mysql")]
+mod mysql;
+#[cfg(feature = "postgres")]
+mod pg;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+mod pool;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use common::{RdbcExecutor, RdbcExecutorExt, RdbcFuture, RdbcTransaction};
+pub use datasource::*;
+#[cfg(feature = "mysql")]
+pub use mysql::*;
+#[cfg(feature = "postgres")]
+pub use pg::*;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+pub use pool::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite::*;
+
+/// One page of query results. Pages are numbered from 1.
+#[derive(Debug, Clone)]
+pub struct RdbcPage<T> {
+    pub page_no: u64,
+    pub page_size: u64,
+    /// Rows matching the query over all pages.
+    pub total: u64,
+    pub data: Vec<T>,
+}
+
+impl<T> RdbcPage<T> {
+    /// Number of pages holding `total` rows.
+    pub fn page_count(&self) -> u64 {
+        if self.page_size == 0 {
+            return 0;
+        }
+        self.total.div_ceil(self.page_size)
+    }
+}
+
+/// Isolation level of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RdbcIsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+/// Options of a transaction started by an executor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdbcTransactionOptions {
+    /// Isolation level, the database default when `None`.
+    pub isolation: Option<RdbcIsolationLevel>,
+    pub read_only: bool,
+    /// How often `with_transaction` reruns a transaction that failed with a
+    /// serialization failure.
+    pub retries: u32,
+}
+
+impl Default for RdbcTransactionOptions {
+    fn default() -> Self {
+        RdbcTransactionOptions {
+            isolation: None,
+            read_only: false,
+            retries: 3,
+        }
+    }
+}
+
+impl RdbcTransactionOptions {
+    pub fn isolation(&mut self, isolation: RdbcIsolationLevel) -> &mut Self {
+        self.isolation = Some(isolation);
+        self
+    }
+    pub fn read_only(&mut self, read_only: bool) -> &mut Self {
+        self.read_only = read_only;
+        self
+    }
+    pub fn retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+}
+
+/// How result column names become keys of row objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RdbcKeyCase {
+    /// The column name as the database reports it.
+    #[default]
+    Column,
+    /// snake_case column names as camelCase, e.g. `create_time` as `createTime`.
+    /// Upper case names, as Oracle reports them, are lowered first.
+    CamelCase,
+}
+
+impl RdbcKeyCase {
+    pub fn key(&self, column: &str) -> String {
+        match self {
+            RdbcKeyCase::Column => column.to_string(),
+            RdbcKeyCase::CamelCase => camel_case(column),
+        }
+    }
+}
+
+fn camel_case(column: &str) -> String {
+    let column = if column.chars().any(|c| c.is_lowercase()) {
+        column.to_string()
+    } else {
+        column.to_lowercase()
+    };
+    let mut key = String::with_capacity(column.len());
+    let mut upper_next = false;
+    for c in column.chars() {
+        if c == '_' {
+            upper_next = !key.is_empty();
+        } else if upper_next {
+            key.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            key.push(c);
+        }
+    }
+    key
+}
@@ -0,0 +1,434 @@
+use crate::executor::common::impl_rdbc_executor;
+use crate::{
+    DataBase, FromRdbcRow, RdbcError, RdbcIsolationLevel, RdbcKeyCase, RdbcPage, RdbcQueryWrapper,
+    RdbcResult, RdbcStatement, RdbcTransactionOptions, RdbcValue,
+};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use tokio_postgres::error::SqlState;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Client, GenericClient, IsolationLevel, NoTls, Row, Transaction};
+
+/// Runs wrappers on a PostgreSQL connection, rendered by the PostgreSQL
+/// renderer. Rows are returned as [`RdbcValue::Object`] keyed by column name.
+pub struct RdbcPgExecutor {
+    client: PgClient,
+    key_case: RdbcKeyCase,
+}
+
+/// A connection of its own or one checked out of a pool, which takes it back
+/// on drop.
+enum PgClient {
+    Owned(Client),
+    Pooled(deadpool_postgres::Object),
+}
+
+impl Deref for PgClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        match self {
+            PgClient::Owned(client) => client,
+            PgClient::Pooled(object) => object,
+        }
+    }
+}
+
+impl DerefMut for PgClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        match self {
+            PgClient::Owned(client) => client,
+            PgClient::Pooled(object) => object,
+        }
+    }
+}
+
+impl RdbcPgExecutor {
+    pub fn new(client: Client) -> Self {
+        RdbcPgExecutor {
+            client: PgClient::Owned(client),
+            key_case: RdbcKeyCase::Column,
+        }
+    }
+
+    pub(crate) fn pooled(object: deadpool_postgres::Object) -> Self {
+        RdbcPgExecutor {
+            client: PgClient::Pooled(object),
+            key_case: RdbcKeyCase::Column,
+        }
+    }
+
+    /// Connects without TLS, e.g. `host=localhost user=postgres dbname=app`
+    /// or a `postgresql://` URL. The connection is driven by a task spawned on
+    /// the current tokio runtime.
+    pub async fn connect(config: &str) -> RdbcResult<Self> {
+        let (client, connection) = tokio_postgres::connect(config, NoTls)
+            .await
+            .map_err(database_error)?;
+        tokio::spawn(async move {
+            // The client reports the failure on its next call.
+            let _ = connection.await;
+        });
+        Ok(RdbcPgExecutor::new(client))
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Spelling of the keys of returned row objects.
+    pub fn key_case(&mut self, key_case: RdbcKeyCase) -> &mut Self {
+        self.key_case = key_case;
+        self
+    }
+
+    /// Rows of `query`, each an [`RdbcValue::Object`].
+    pub async fn query_list<S>(&self, query: &S) -> RdbcResult<Vec<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        query_list(&*self.client, query, self.key_case).await
+    }
+
+    /// First row of `query`, `None` when nothing matches.
+    pub async fn query_one<S>(&self, query: &S) -> RdbcResult<Option<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        Ok(self.query_list(query).await?.into_iter().next())
+    }
+
+    /// Rows of `query` mapped into `T` by column name.
+    pub async fn query_as<T, S>(&self, query: &S) -> RdbcResult<Vec<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        query_as(&*self.client, query).await
+    }
+
+    /// First row of `query` mapped into `T`, `None` when nothing matches.
+    pub async fn query_one_as<T, S>(&self, query: &S) -> RdbcResult<Option<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        Ok(self.query_as(query).await?.into_iter().next())
+    }
+
+    /// Page `page_no` (from 1) of `query` with `page_size` rows, together with
+    /// the total row count. Limit and offset of `query` are replaced.
+    pub async fn query_page(
+        &self,
+        query: &RdbcQueryWrapper,
+        page_no: u64,
+        page_size: u64,
+    ) -> RdbcResult<RdbcPage<RdbcValue>> {
+        query_page(&*self.client, query, page_no, page_size, self.key_case).await
+    }
+
+    /// Runs an insert, update, delete or raw statement, returning the number
+    /// of affected rows.
+    pub async fn execute<S>(&self, statement: &S) -> RdbcResult<u64>
+    where
+        S: RdbcStatement,
+    {
+        let (sql, params) = statement.render(DataBase::Postgres)?;
+        self.execute_sql(&sql, &params).await
+    }
+
+    /// Runs SQL with `$n` params, returning each row as an object.
+    pub async fn query_sql(&self, sql: &str, params: &[RdbcValue]) -> RdbcResult<Vec<RdbcValue>> {
+        query_rows(&*self.client, sql, params, self.key_case).await
+    }
+
+    /// Runs SQL with `$n` params, returning the number of affected rows.
+    pub async fn execute_sql(&self, sql: &str, params: &[RdbcValue]) -> RdbcResult<u64> {
+        execute_sql(&*self.client, sql, params).await
+    }
+
+    /// Starts a transaction with the database defaults.
+    pub async fn begin(&mut self) -> RdbcResult<RdbcPgTransaction<'_>> {
+        self.begin_with(&RdbcTransactionOptions::default()).await
+    }
+
+    /// Starts a transaction with the isolation level and access mode of
+    /// `options`.
+    pub async fn begin_with(
+        &mut self,
+        options: &RdbcTransactionOptions,
+    ) -> RdbcResult<RdbcPgTransaction<'_>> {
+        let mut builder = self.client.build_transaction();
+        if let Some(isolation) = options.isolation {
+            builder = builder.isolation_level(isolation_level(isolation));
+        }
+        if options.read_only {
+            builder = builder.read_only(true);
+        }
+        let transaction = builder.start().await.map_err(database_error)?;
+        Ok(RdbcPgTransaction {
+            transaction,
+            key_case: self.key_case,
+        })
+    }
+}
+
+impl_rdbc_executor!(RdbcPgExecutor, DataBase::Postgres);
+
+/// A PostgreSQL transaction or savepoint, taking the same wrappers as
+/// [`RdbcPgExecutor`]. Dropping it without [`commit`](Self::commit) rolls it
+/// back.
+pub struct RdbcPgTransaction<'a> {
+    transaction: Transaction<'a>,
+    key_case: RdbcKeyCase,
+}
+
+impl RdbcPgTransaction<'_> {
+    pub fn transaction(&self) -> &Transaction<'_> {
+        &self.transaction
+    }
+
+    /// Rows of `query`, each an [`RdbcValue::Object`].
+    pub async fn query_list<S>(&self, query: &S) -> RdbcResult<Vec<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        query_list(&self.transaction, query, self.key_case).await
+    }
+
+    /// First row of `query`, `None` when nothing matches.
+    pub async fn query_one<S>(&self, query: &S) -> RdbcResult<Option<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        Ok(self.query_list(query).await?.into_iter().next())
+    }
+
+    /// Rows of `query` mapped into `T` by column name.
+    pub async fn query_as<T, S>(&self, query: &S) -> RdbcResult<Vec<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        query_as(&self.transaction, query).await
+    }
+
+    /// First row of `query` mapped into `T`, `None` when nothing matches.
+    pub async fn query_one_as<T, S>(&self, query: &S) -> RdbcResult<Option<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        Ok(self.query_as(query).await?.into_iter().next())
+    }
+
+    /// Page `page_no` (from 1) of `query`, see [`RdbcPgExecutor::query_page`].
+    pub async fn query_page(
+        &self,
+        query: &RdbcQueryWrapper,
+        page_no: u64,
+        page_size: u64,
+    ) -> RdbcResult<RdbcPage<RdbcValue>> {
+        query_page(&self.transaction, query, page_no, page_size, self.key_case).await
+    }
+
+    /// Runs an insert, update, delete or raw statement, returning the number
+    /// of affected rows.
+    pub async fn execute<S>(&self, statement: &S) -> RdbcResult<u64>
+    where
+        S: RdbcStatement,
+    {
+        let (sql, params) = statement.render(DataBase::Postgres)?;
+        self.execute_sql(&sql, &params).await
+    }
+
+    /// Runs SQL with `$n` params, returning each row as an object.
+    pub async fn query_sql(&self, sql: &str, params: &[RdbcValue]) -> RdbcResult<Vec<RdbcValue>> {
+        query_rows(&self.transaction, sql, params, self.key_case).await
+    }
+
+    /// Runs SQL with `$n` params, returning the number of affected rows.
+    pub async fn execute_sql(&self, sql: &str, params: &[RdbcValue]) -> RdbcResult<u64> {
+        execute_sql(&self.transaction, sql, params).await
+    }
+
+    /// Starts a nested transaction as the savepoint `name`. Committing it
+    /// releases the savepoint, rolling it back returns to it.
+    pub async fn savepoint(&mut self, name: &str) -> RdbcResult<RdbcPgTransaction<'_>> {
+        let transaction = self
+            .transaction
+            .savepoint(name)
+            .await
+            .map_err(database_error)?;
+        Ok(RdbcPgTransaction {
+            transaction,
+            key_case: self.key_case,
+        })
+    }
+
+    pub async fn commit(self) -> RdbcResult<()> {
+        self.transaction.commit().await.map_err(database_error)
+    }
+
+    pub async fn rollback(self) -> RdbcResult<()> {
+        self.transaction.rollback().await.map_err(database_error)
+    }
+}
+
+impl_rdbc_executor!(transaction RdbcPgTransaction, DataBase::Postgres);
+
+async fn query_list<C, S>(
+    client: &C,
+    query: &S,
+    key_case: RdbcKeyCase,
+) -> RdbcResult<Vec<RdbcValue>>
+where
+    C: GenericClient,
+    S: RdbcStatement,
+{
+    let (sql, params) = query.render(DataBase::Postgres)?;
+    query_rows(client, &sql, &params, key_case).await
+}
+
+async fn query_as<C, T, S>(client: &C, query: &S) -> RdbcResult<Vec<T>>
+where
+    C: GenericClient,
+    T: FromRdbcRow,
+    S: RdbcStatement,
+{
+    let (sql, params) = query.render(DataBase::Postgres)?;
+    query_objects(client, &sql, &params)
+        .await?
+        .into_iter()
+        .map(T::from_rdbc_row)
+        .collect()
+}
+
+async fn query_page<C>(
+    client: &C,
+    query: &RdbcQueryWrapper,
+    page_no: u64,
+    page_size: u64,
+    key_case: RdbcKeyCase,
+) -> RdbcResult<RdbcPage<RdbcValue>>
+where
+    C: GenericClient,
+{
+    let page_no = page_no.max(1);
+    let mut page_query = query.clone();
+    page_query.limit_count = None;
+    page_query.offset_count = None;
+    let (sql, params) = page_query.render(DataBase::Postgres)?;
+    let count_sql = format!("SELECT COUNT(*) AS total FROM ({}) page_total", sql);
+    let row = client
+        .query_one(count_sql.as_str(), &sql_params(&params))
+        .await
+        .map_err(database_error)?;
+    let total: i64 = row.try_get(0).map_err(database_error)?;
+
+    page_query
+        .limit(page_size)
+        .offset((page_no - 1) * page_size);
+    let data = if total > 0 {
+        query_list(client, &page_query, key_case).await?
+    } else {
+        vec![]
+    };
+    Ok(RdbcPage {
+        page_no,
+        page_size,
+        total: total as u64,
+        data,
+    })
+}
+
+async fn query_rows<C>(
+    client: &C,
+    sql: &str,
+    params: &[RdbcValue],
+    key_case: RdbcKeyCase,
+) -> RdbcResult<Vec<RdbcValue>>
+where
+    C: GenericClient,
+{
+    let rows = client
+        .query(sql, &sql_params(params))
+        .await
+        .map_err(database_error)?;
+    rows.iter()
+        .map(|row| pg_row_to_object(row, key_case))
+        .collect()
+}
+
+/// Rows keyed by column name, as the migrator and row mapping look them up.
+async fn query_objects<C>(
+    client: &C,
+    sql: &str,
+    params: &[RdbcValue],
+) -> RdbcResult<Vec<HashMap<String, RdbcValue>>>
+where
+    C: GenericClient,
+{
+    let rows = query_rows(client, sql, params, RdbcKeyCase::Column).await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match row {
+            RdbcValue::Object(row) => Some(row),
+            _ => None,
+        })
+        .collect())
+}
+
+async fn execute_sql<C>(client: &C, sql: &str, params: &[RdbcValue]) -> RdbcResult<u64>
+where
+    C: GenericClient,
+{
+    client
+        .execute(sql, &sql_params(params))
+        .await
+        .map_err(database_error)
+}
+
+fn sql_params(params: &[RdbcValue]) -> Vec<&(dyn ToSql + Sync)> {
+    params
+        .iter()
+        .map(|value| value as &(dyn ToSql + Sync))
+        .collect()
+}
+
+fn isolation_level(isolation: RdbcIsolationLevel) -> IsolationLevel {
+    match isolation {
+        RdbcIsolationLevel::ReadUncommitted => IsolationLevel::ReadUncommitted,
+        RdbcIsolationLevel::ReadCommitted => IsolationLevel::ReadCommitted,
+        RdbcIsolationLevel::RepeatableRead => IsolationLevel::RepeatableRead,
+        RdbcIsolationLevel::Serializable => IsolationLevel::Serializable,
+    }
+}
+
+pub(crate) fn database_error(err: tokio_postgres::Error) -> RdbcError {
+    match err.as_db_error() {
+        Some(db_error) => {
+            let msg = format!("{} ({})", db_error.message(), db_error.code().code());
+            match *db_error.code() {
+                SqlState::T_R_SERIALIZATION_FAILURE | SqlState::T_R_DEADLOCK_DETECTED => {
+                    RdbcError::Serialization(msg)
+                }
+                _ => RdbcError::Database(msg),
+            }
+        }
+        None => RdbcError::Database(err.to_string()),
+    }
+}
+
+/// Builds an [`RdbcValue::Object`] from a PostgreSQL row, keyed by column
+/// name as `key_case` spells it.
+pub fn pg_row_to_object(row: &Row, key_case: RdbcKeyCase) -> RdbcResult<RdbcValue> {
+    let mut object = HashMap::new();
+    for (index, column) in row.columns().iter().enumerate() {
+        let value: RdbcValue = row
+            .try_get(index)
+            .map_err(|err| RdbcError::Database(format!("column {}: {}", column.name(), err)))?;
+        object.insert(key_case.key(column.name()), value);
+    }
+    Ok(RdbcValue::Object(object))
+}
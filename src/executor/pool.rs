@@ -0,0 +1,415 @@
+use crate::executor::common::RdbcTransaction;
+#[cfg(feature = "postgres")]
+use crate::executor::pg::database_error;
+#[cfg(feature = "postgres")]
+use crate::RdbcPgExecutor;
+use crate::{
+    DataBase, FromRdbcRow, RdbcDataSource, RdbcDataSourceConfig, RdbcDeleteWrapper, RdbcError,
+    RdbcExecutor, RdbcFuture, RdbcInsertWrapper, RdbcPage, RdbcQueryWrapper, RdbcResult,
+    RdbcStatement, RdbcTransactionOptions, RdbcUpdateWrapper, RdbcValue,
+};
+#[cfg(feature = "postgres")]
+use deadpool_postgres::{Manager, ManagerConfig, PoolError, RecyclingMethod, Runtime};
+use std::collections::HashMap;
+#[cfg(feature = "mysql")]
+use std::time::Duration;
+#[cfg(feature = "postgres")]
+use tokio_postgres::NoTls;
+
+/// Connection pool of one datasource, backed by the driver of its dialect.
+pub enum RdbcPool {
+    #[cfg(feature = "postgres")]
+    Postgres(deadpool_postgres::Pool),
+    /// The driver pool and the longest a checkout may take.
+    #[cfg(feature = "mysql")]
+    Mysql(mysql_async::Pool, Option<Duration>),
+    /// The database file. SQLite connections are cheap, so every checkout
+    /// opens its own; with `:memory:` each one is a separate database.
+    #[cfg(feature = "sqlite")]
+    Sqlite(String),
+}
+
+impl RdbcPool {
+    /// Creates the pool and opens `pool_min` connections up front.
+    pub async fn connect(data_source: &RdbcDataSource) -> RdbcResult<Self> {
+        let pool = match data_source.dialect {
+            #[cfg(feature = "postgres")]
+            DataBase::Postgres => RdbcPool::Postgres(pg_pool(data_source)?),
+            #[cfg(feature = "mysql")]
+            DataBase::MySql => RdbcPool::Mysql(
+                mysql_pool(data_source)?,
+                mysql_checkout_timeout(data_source),
+            ),
+            #[cfg(feature = "sqlite")]
+            DataBase::Sqlite => RdbcPool::Sqlite(data_source.database.clone()),
+            dialect => {
+                return Err(RdbcError::Unsupported(format!(
+                    "no driver for {:?} datasources",
+                    dialect
+                )))
+            }
+        };
+        let mut warm = Vec::with_capacity(data_source.pool_min);
+        for _ in 0..data_source.pool_min {
+            warm.push(pool.executor().await?);
+        }
+        Ok(pool)
+    }
+
+    pub fn database(&self) -> DataBase {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcPool::Postgres(_) => DataBase::Postgres,
+            #[cfg(feature = "mysql")]
+            RdbcPool::Mysql(..) => DataBase::MySql,
+            #[cfg(feature = "sqlite")]
+            RdbcPool::Sqlite(_) => DataBase::Sqlite,
+        }
+    }
+
+    /// Checks a connection out of the pool. It goes back when the returned
+    /// executor is dropped.
+    pub async fn executor(&self) -> RdbcResult<RdbcConnection> {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcPool::Postgres(pool) => {
+                let object = pool.get().await.map_err(pool_error)?;
+                Ok(RdbcConnection::Postgres(RdbcPgExecutor::pooled(object)))
+            }
+            #[cfg(feature = "mysql")]
+            RdbcPool::Mysql(pool, timeout) => {
+                let conn = match timeout {
+                    Some(timeout) => tokio::time::timeout(*timeout, pool.get_conn())
+                        .await
+                        .map_err(|_| {
+                            RdbcError::DataSource("timed out checking out a connection".to_string())
+                        })?,
+                    None => pool.get_conn().await,
+                }
+                .map_err(crate::executor::mysql::database_error)?;
+                Ok(RdbcConnection::Mysql(crate::RdbcMysqlExecutor::new(conn)))
+            }
+            #[cfg(feature = "sqlite")]
+            RdbcPool::Sqlite(path) => Ok(RdbcConnection::Sqlite(crate::RdbcSqliteExecutor::open(
+                path,
+            )?)),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn pg_pool(data_source: &RdbcDataSource) -> RdbcResult<deadpool_postgres::Pool> {
+    let mut config = tokio_postgres::Config::new();
+    config
+        .host(&data_source.host)
+        .dbname(&data_source.database)
+        .user(&data_source.user);
+    if let Some(port) = data_source.port {
+        config.port(port);
+    }
+    if !data_source.password.is_empty() {
+        config.password(&data_source.password);
+    }
+    if let Some(timeout) = data_source.connect_timeout_duration() {
+        config.connect_timeout(timeout);
+    }
+    let manager = Manager::from_config(
+        config,
+        NoTls,
+        ManagerConfig {
+            recycling_method: RecyclingMethod::Fast,
+        },
+    );
+    deadpool_postgres::Pool::builder(manager)
+        .max_size(data_source.pool_max.max(1))
+        .wait_timeout(data_source.acquire_timeout_duration())
+        .create_timeout(data_source.connect_timeout_duration())
+        .runtime(Runtime::Tokio1)
+        .build()
+        .map_err(|err| RdbcError::DataSource(err.to_string()))
+}
+
+/// A MySQL pool of `pool_min` to `pool_max` connections.
+#[cfg(feature = "mysql")]
+fn mysql_pool(data_source: &RdbcDataSource) -> RdbcResult<mysql_async::Pool> {
+    let pool_max = data_source.pool_max.max(1);
+    let constraints =
+        mysql_async::PoolConstraints::new(data_source.pool_min.min(pool_max), pool_max)
+            .ok_or_else(|| RdbcError::DataSource("invalid pool size".to_string()))?;
+    let opts = mysql_async::OptsBuilder::default()
+        .ip_or_hostname(data_source.host.as_str())
+        .tcp_port(data_source.port.unwrap_or(3306))
+        .db_name(Some(data_source.database.as_str()))
+        .user(Some(data_source.user.as_str()))
+        .pass((!data_source.password.is_empty()).then_some(data_source.password.as_str()))
+        .pool_opts(mysql_async::PoolOpts::default().with_constraints(constraints));
+    Ok(mysql_async::Pool::new(opts))
+}
+
+/// The driver has no connect or acquire timeout, and a checkout may both wait
+/// for a free connection and open a new one, so it is bounded by the sum of
+/// the two. Unlimited when either is.
+#[cfg(feature = "mysql")]
+fn mysql_checkout_timeout(data_source: &RdbcDataSource) -> Option<Duration> {
+    Some(data_source.connect_timeout_duration()? + data_source.acquire_timeout_duration()?)
+}
+
+#[cfg(feature = "postgres")]
+fn pool_error(err: PoolError) -> RdbcError {
+    match err {
+        PoolError::Backend(err) => database_error(err),
+        err => RdbcError::DataSource(err.to_string()),
+    }
+}
+
+/// An executor on a pooled connection, for the driver and renderer picked by
+/// the dialect of its datasource.
+pub enum RdbcConnection {
+    #[cfg(feature = "postgres")]
+    Postgres(RdbcPgExecutor),
+    #[cfg(feature = "mysql")]
+    Mysql(crate::RdbcMysqlExecutor),
+    #[cfg(feature = "sqlite")]
+    Sqlite(crate::RdbcSqliteExecutor),
+}
+
+impl RdbcConnection {
+    pub fn database(&self) -> DataBase {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(_) => DataBase::Postgres,
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(_) => DataBase::MySql,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(_) => DataBase::Sqlite,
+        }
+    }
+
+    /// Rows of `query`, each an [`RdbcValue::Object`].
+    pub async fn query_list<S>(&self, query: &S) -> RdbcResult<Vec<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(executor) => executor.query_list(query).await,
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(executor) => executor.query_list(query).await,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(executor) => executor.query_list(query).await,
+        }
+    }
+
+    /// First row of `query`, `None` when nothing matches.
+    pub async fn query_one<S>(&self, query: &S) -> RdbcResult<Option<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(executor) => executor.query_one(query).await,
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(executor) => executor.query_one(query).await,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(executor) => executor.query_one(query).await,
+        }
+    }
+
+    /// Rows of `query` mapped into `T` by column name.
+    pub async fn query_as<T, S>(&self, query: &S) -> RdbcResult<Vec<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(executor) => executor.query_as(query).await,
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(executor) => executor.query_as(query).await,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(executor) => executor.query_as(query).await,
+        }
+    }
+
+    /// First row of `query` mapped into `T`, `None` when nothing matches.
+    pub async fn query_one_as<T, S>(&self, query: &S) -> RdbcResult<Option<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(executor) => executor.query_one_as(query).await,
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(executor) => executor.query_one_as(query).await,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(executor) => executor.query_one_as(query).await,
+        }
+    }
+
+    /// Page `page_no` (from 1) of `query` with `page_size` rows.
+    pub async fn query_page(
+        &self,
+        query: &RdbcQueryWrapper,
+        page_no: u64,
+        page_size: u64,
+    ) -> RdbcResult<RdbcPage<RdbcValue>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(executor) => {
+                executor.query_page(query, page_no, page_size).await
+            }
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(executor) => executor.query_page(query, page_no, page_size).await,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(executor) => {
+                executor.query_page(query, page_no, page_size).await
+            }
+        }
+    }
+
+    /// Runs an insert, update, delete or raw statement, returning the number
+    /// of affected rows.
+    pub async fn execute<S>(&self, statement: &S) -> RdbcResult<u64>
+    where
+        S: RdbcStatement,
+    {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(executor) => executor.execute(statement).await,
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(executor) => executor.execute(statement).await,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(executor) => executor.execute(statement).await,
+        }
+    }
+}
+
+impl RdbcConnection {
+    fn inner(&self) -> &dyn RdbcExecutor {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(executor) => executor,
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(executor) => executor,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(executor) => executor,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut dyn RdbcExecutor {
+        match self {
+            #[cfg(feature = "postgres")]
+            RdbcConnection::Postgres(executor) => executor,
+            #[cfg(feature = "mysql")]
+            RdbcConnection::Mysql(executor) => executor,
+            #[cfg(feature = "sqlite")]
+            RdbcConnection::Sqlite(executor) => executor,
+        }
+    }
+}
+
+impl RdbcExecutor for RdbcConnection {
+    fn database(&self) -> DataBase {
+        self.inner().database()
+    }
+
+    fn query_list<'a>(&'a self, query: &'a RdbcQueryWrapper) -> RdbcFuture<'a, Vec<RdbcValue>> {
+        self.inner().query_list(query)
+    }
+
+    fn query_rows<'a>(
+        &'a self,
+        query: &'a RdbcQueryWrapper,
+    ) -> RdbcFuture<'a, Vec<HashMap<String, RdbcValue>>> {
+        self.inner().query_rows(query)
+    }
+
+    fn query_page<'a>(
+        &'a self,
+        query: &'a RdbcQueryWrapper,
+        page_no: u64,
+        page_size: u64,
+    ) -> RdbcFuture<'a, RdbcPage<RdbcValue>> {
+        self.inner().query_page(query, page_no, page_size)
+    }
+
+    fn insert<'a>(&'a self, insert: &'a RdbcInsertWrapper) -> RdbcFuture<'a, u64> {
+        self.inner().insert(insert)
+    }
+
+    fn update<'a>(&'a self, update: &'a RdbcUpdateWrapper) -> RdbcFuture<'a, u64> {
+        self.inner().update(update)
+    }
+
+    fn delete<'a>(&'a self, delete: &'a RdbcDeleteWrapper) -> RdbcFuture<'a, u64> {
+        self.inner().delete(delete)
+    }
+
+    fn query_sql<'a>(
+        &'a self,
+        sql: &'a str,
+        params: &'a [RdbcValue],
+    ) -> RdbcFuture<'a, Vec<RdbcValue>> {
+        self.inner().query_sql(sql, params)
+    }
+
+    fn execute_sql<'a>(&'a self, sql: &'a str, params: &'a [RdbcValue]) -> RdbcFuture<'a, u64> {
+        self.inner().execute_sql(sql, params)
+    }
+
+    fn begin_with<'a>(
+        &'a mut self,
+        options: &RdbcTransactionOptions,
+    ) -> RdbcFuture<'a, Box<dyn RdbcTransaction<'a> + 'a>> {
+        self.inner_mut().begin_with(options)
+    }
+}
+
+/// Pools of named datasources.
+#[derive(Default)]
+pub struct RdbcDataSourceRegistry {
+    pools: HashMap<String, RdbcPool>,
+}
+
+impl RdbcDataSourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connects a pool for every datasource of `config`.
+    pub async fn connect(config: &RdbcDataSourceConfig) -> RdbcResult<Self> {
+        let mut registry = RdbcDataSourceRegistry::new();
+        for (name, data_source) in config.datasources.iter() {
+            registry.register(name, data_source).await?;
+        }
+        Ok(registry)
+    }
+
+    /// Connects a pool for `data_source` as `name`, replacing any pool
+    /// registered under that name.
+    pub async fn register(
+        &mut self,
+        name: impl Into<String>,
+        data_source: &RdbcDataSource,
+    ) -> RdbcResult<()> {
+        let pool = RdbcPool::connect(data_source).await?;
+        self.pools.insert(name.into(), pool);
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.pools.keys().map(|name| name.as_str()).collect()
+    }
+
+    pub fn pool(&self, name: &str) -> RdbcResult<&RdbcPool> {
+        self.pools
+            .get(name)
+            .ok_or_else(|| RdbcError::DataSource(format!("unknown datasource {}", name)))
+    }
+
+    /// Checks a connection out of the pool of `name`.
+    pub async fn executor(&self, name: &str) -> RdbcResult<RdbcConnection> {
+        self.pool(name)?.executor().await
+    }
+}
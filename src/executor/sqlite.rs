@@ -0,0 +1,412 @@
+use crate::bean::sqlite_value;
+use crate::executor::common::impl_rdbc_executor;
+use crate::{
+    DataBase, FromRdbcRow, RdbcError, RdbcKeyCase, RdbcPage, RdbcQueryWrapper, RdbcResult,
+    RdbcStatement, RdbcTransactionOptions, RdbcValue,
+};
+use rusqlite::{params_from_iter, Connection};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Runs wrappers on a SQLite database, rendered by the SQLite renderer. Rows
+/// are returned as [`RdbcValue::Object`] keyed by column name.
+pub struct RdbcSqliteExecutor {
+    conn: Mutex<Connection>,
+    key_case: RdbcKeyCase,
+}
+
+impl RdbcSqliteExecutor {
+    pub fn new(conn: Connection) -> Self {
+        RdbcSqliteExecutor {
+            conn: Mutex::new(conn),
+            key_case: RdbcKeyCase::Column,
+        }
+    }
+
+    /// Opens the database file at `path`, creating it when missing.
+    pub fn open(path: &str) -> RdbcResult<Self> {
+        Connection::open(path)
+            .map(RdbcSqliteExecutor::new)
+            .map_err(database_error)
+    }
+
+    /// Opens a private database that lives as long as the executor.
+    pub fn open_in_memory() -> RdbcResult<Self> {
+        Connection::open_in_memory()
+            .map(RdbcSqliteExecutor::new)
+            .map_err(database_error)
+    }
+
+    pub fn connection(&self) -> MutexGuard<'_, Connection> {
+        lock(&self.conn)
+    }
+
+    /// Spelling of the keys of returned row objects.
+    pub fn key_case(&mut self, key_case: RdbcKeyCase) -> &mut Self {
+        self.key_case = key_case;
+        self
+    }
+
+    /// Rows of `query`, each an [`RdbcValue::Object`].
+    pub async fn query_list<S>(&self, query: &S) -> RdbcResult<Vec<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        query_list(&lock(&self.conn), query, self.key_case)
+    }
+
+    /// First row of `query`, `None` when nothing matches.
+    pub async fn query_one<S>(&self, query: &S) -> RdbcResult<Option<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        Ok(self.query_list(query).await?.into_iter().next())
+    }
+
+    /// Rows of `query` mapped into `T` by column name.
+    pub async fn query_as<T, S>(&self, query: &S) -> RdbcResult<Vec<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        query_as(&lock(&self.conn), query)
+    }
+
+    /// First row of `query` mapped into `T`, `None` when nothing matches.
+    pub async fn query_one_as<T, S>(&self, query: &S) -> RdbcResult<Option<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        Ok(self.query_as(query).await?.into_iter().next())
+    }
+
+    /// Page `page_no` (from 1) of `query` with `page_size` rows, together with
+    /// the total row count. Limit and offset of `query` are replaced.
+    pub async fn query_page(
+        &self,
+        query: &RdbcQueryWrapper,
+        page_no: u64,
+        page_size: u64,
+    ) -> RdbcResult<RdbcPage<RdbcValue>> {
+        query_page(&lock(&self.conn), query, page_no, page_size, self.key_case)
+    }
+
+    /// Runs an insert, update, delete or raw statement, returning the number
+    /// of affected rows.
+    pub async fn execute<S>(&self, statement: &S) -> RdbcResult<u64>
+    where
+        S: RdbcStatement,
+    {
+        let (sql, params) = statement.render(DataBase::Sqlite)?;
+        execute_sql(&lock(&self.conn), &sql, &params)
+    }
+
+    /// Runs SQL with `?` params, returning each row as an object.
+    pub async fn query_sql(&self, sql: &str, params: &[RdbcValue]) -> RdbcResult<Vec<RdbcValue>> {
+        query_rows(&lock(&self.conn), sql, params, self.key_case)
+    }
+
+    /// Runs SQL with `?` params, returning the number of affected rows.
+    pub async fn execute_sql(&self, sql: &str, params: &[RdbcValue]) -> RdbcResult<u64> {
+        execute_sql(&lock(&self.conn), sql, params)
+    }
+
+    /// Starts a deferred transaction.
+    pub async fn begin(&mut self) -> RdbcResult<RdbcSqliteTransaction<'_>> {
+        self.begin_with(&RdbcTransactionOptions::default()).await
+    }
+
+    /// Starts a transaction. SQLite transactions are always serializable, so
+    /// the isolation level of `options` is not used; a read-only transaction
+    /// is enforced with `PRAGMA query_only` until it ends.
+    pub async fn begin_with(
+        &mut self,
+        options: &RdbcTransactionOptions,
+    ) -> RdbcResult<RdbcSqliteTransaction<'_>> {
+        let conn = self.conn.get_mut().unwrap_or_else(PoisonError::into_inner);
+        conn.execute_batch("BEGIN DEFERRED")
+            .map_err(database_error)?;
+        if options.read_only {
+            if let Err(err) = conn.execute_batch("PRAGMA query_only = ON") {
+                let _ = conn.execute_batch("ROLLBACK");
+                return Err(database_error(err));
+            }
+        }
+        Ok(RdbcSqliteTransaction {
+            conn: &self.conn,
+            savepoint: None,
+            read_only: options.read_only,
+            done: false,
+            key_case: self.key_case,
+        })
+    }
+}
+
+impl_rdbc_executor!(RdbcSqliteExecutor, DataBase::Sqlite);
+
+/// A SQLite transaction or savepoint, taking the same wrappers as
+/// [`RdbcSqliteExecutor`]. Dropping it without [`commit`](Self::commit) rolls
+/// it back.
+pub struct RdbcSqliteTransaction<'a> {
+    conn: &'a Mutex<Connection>,
+    /// Name of the savepoint of a nested transaction.
+    savepoint: Option<String>,
+    read_only: bool,
+    done: bool,
+    key_case: RdbcKeyCase,
+}
+
+impl RdbcSqliteTransaction<'_> {
+    pub fn connection(&self) -> MutexGuard<'_, Connection> {
+        lock(self.conn)
+    }
+
+    /// Rows of `query`, each an [`RdbcValue::Object`].
+    pub async fn query_list<S>(&self, query: &S) -> RdbcResult<Vec<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        query_list(&lock(self.conn), query, self.key_case)
+    }
+
+    /// First row of `query`, `None` when nothing matches.
+    pub async fn query_one<S>(&self, query: &S) -> RdbcResult<Option<RdbcValue>>
+    where
+        S: RdbcStatement,
+    {
+        Ok(self.query_list(query).await?.into_iter().next())
+    }
+
+    /// Rows of `query` mapped into `T` by column name.
+    pub async fn query_as<T, S>(&self, query: &S) -> RdbcResult<Vec<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        query_as(&lock(self.conn), query)
+    }
+
+    /// First row of `query` mapped into `T`, `None` when nothing matches.
+    pub async fn query_one_as<T, S>(&self, query: &S) -> RdbcResult<Option<T>>
+    where
+        T: FromRdbcRow,
+        S: RdbcStatement,
+    {
+        Ok(self.query_as(query).await?.into_iter().next())
+    }
+
+    /// Page `page_no` (from 1) of `query`, see
+    /// [`RdbcSqliteExecutor::query_page`].
+    pub async fn query_page(
+        &self,
+        query: &RdbcQueryWrapper,
+        page_no: u64,
+        page_size: u64,
+    ) -> RdbcResult<RdbcPage<RdbcValue>> {
+        query_page(&lock(self.conn), query, page_no, page_size, self.key_case)
+    }
+
+    /// Runs an insert, update, delete or raw statement, returning the number
+    /// of affected rows.
+    pub async fn execute<S>(&self, statement: &S) -> RdbcResult<u64>
+    where
+        S: RdbcStatement,
+    {
+        let (sql, params) = statement.render(DataBase::Sqlite)?;
+        execute_sql(&lock(self.conn), &sql, &params)
+    }
+
+    /// Runs SQL with `?` params, returning each row as an object.
+    pub async fn query_sql(&self, sql: &str, params: &[RdbcValue]) -> RdbcResult<Vec<RdbcValue>> {
+        query_rows(&lock(self.conn), sql, params, self.key_case)
+    }
+
+    /// Runs SQL with `?` params, returning the number of affected rows.
+    pub async fn execute_sql(&self, sql: &str, params: &[RdbcValue]) -> RdbcResult<u64> {
+        execute_sql(&lock(self.conn), sql, params)
+    }
+
+    /// Starts a nested transaction as the savepoint `name`. Committing it
+    /// releases the savepoint, rolling it back returns to it.
+    pub async fn savepoint(&mut self, name: &str) -> RdbcResult<RdbcSqliteTransaction<'_>> {
+        let name = quote_identifier(name);
+        lock(self.conn)
+            .execute_batch(&format!("SAVEPOINT {}", name))
+            .map_err(database_error)?;
+        Ok(RdbcSqliteTransaction {
+            conn: self.conn,
+            savepoint: Some(name),
+            read_only: false,
+            done: false,
+            key_case: self.key_case,
+        })
+    }
+
+    pub async fn commit(mut self) -> RdbcResult<()> {
+        self.finish(true)
+    }
+
+    pub async fn rollback(mut self) -> RdbcResult<()> {
+        self.finish(false)
+    }
+
+    fn finish(&mut self, commit: bool) -> RdbcResult<()> {
+        self.done = true;
+        let sql = match (&self.savepoint, commit) {
+            (Some(name), true) => format!("RELEASE {}", name),
+            // Rolling back to a savepoint keeps it open, release it as well.
+            (Some(name), false) => format!("ROLLBACK TO {0}; RELEASE {0}", name),
+            (None, true) => "COMMIT".to_string(),
+            (None, false) => "ROLLBACK".to_string(),
+        };
+        let conn = lock(self.conn);
+        let result = conn.execute_batch(&sql).map_err(database_error);
+        if result.is_err() && self.savepoint.is_none() && !conn.is_autocommit() {
+            // A failed COMMIT leaves the transaction open.
+            let _ = conn.execute_batch("ROLLBACK");
+        }
+        if self.read_only {
+            let _ = conn.execute_batch("PRAGMA query_only = OFF");
+        }
+        result
+    }
+}
+
+impl Drop for RdbcSqliteTransaction<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            let _ = self.finish(false);
+        }
+    }
+}
+
+impl_rdbc_executor!(transaction RdbcSqliteTransaction, DataBase::Sqlite);
+
+fn query_list<S>(conn: &Connection, query: &S, key_case: RdbcKeyCase) -> RdbcResult<Vec<RdbcValue>>
+where
+    S: RdbcStatement,
+{
+    let (sql, params) = query.render(DataBase::Sqlite)?;
+    query_rows(conn, &sql, &params, key_case)
+}
+
+fn query_as<T, S>(conn: &Connection, query: &S) -> RdbcResult<Vec<T>>
+where
+    T: FromRdbcRow,
+    S: RdbcStatement,
+{
+    let (sql, params) = query.render(DataBase::Sqlite)?;
+    query_objects(conn, &sql, &params)?
+        .into_iter()
+        .map(T::from_rdbc_row)
+        .collect()
+}
+
+fn query_page(
+    conn: &Connection,
+    query: &RdbcQueryWrapper,
+    page_no: u64,
+    page_size: u64,
+    key_case: RdbcKeyCase,
+) -> RdbcResult<RdbcPage<RdbcValue>> {
+    let page_no = page_no.max(1);
+    let mut page_query = query.clone();
+    page_query.limit_count = None;
+    page_query.offset_count = None;
+    let (sql, params) = page_query.render(DataBase::Sqlite)?;
+    let count_sql = format!("SELECT COUNT(*) AS total FROM ({}) page_total", sql);
+    let total: i64 = conn
+        .query_row(&count_sql, params_from_iter(params.iter()), |row| {
+            row.get(0)
+        })
+        .map_err(database_error)?;
+
+    page_query
+        .limit(page_size)
+        .offset((page_no - 1) * page_size);
+    let data = if total > 0 {
+        query_list(conn, &page_query, key_case)?
+    } else {
+        vec![]
+    };
+    Ok(RdbcPage {
+        page_no,
+        page_size,
+        total: total as u64,
+        data,
+    })
+}
+
+fn query_rows(
+    conn: &Connection,
+    sql: &str,
+    params: &[RdbcValue],
+    key_case: RdbcKeyCase,
+) -> RdbcResult<Vec<RdbcValue>> {
+    let mut statement = conn.prepare(sql).map_err(database_error)?;
+    let columns: Vec<(String, Option<String>)> = statement
+        .columns()
+        .iter()
+        .map(|column| {
+            (
+                key_case.key(column.name()),
+                column.decl_type().map(|decl_type| decl_type.to_string()),
+            )
+        })
+        .collect();
+    let mut rows = statement
+        .query(params_from_iter(params.iter()))
+        .map_err(database_error)?;
+    let mut objects = vec![];
+    while let Some(row) = rows.next().map_err(database_error)? {
+        let mut object = HashMap::new();
+        for (index, (key, decl_type)) in columns.iter().enumerate() {
+            let value = row.get_ref(index).map_err(database_error)?;
+            object.insert(key.clone(), sqlite_value(value, decl_type.as_deref()));
+        }
+        objects.push(RdbcValue::Object(object));
+    }
+    Ok(objects)
+}
+
+/// Rows keyed by column name, as the migrator and row mapping look them up.
+fn query_objects(
+    conn: &Connection,
+    sql: &str,
+    params: &[RdbcValue],
+) -> RdbcResult<Vec<HashMap<String, RdbcValue>>> {
+    let rows = query_rows(conn, sql, params, RdbcKeyCase::Column)?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match row {
+            RdbcValue::Object(row) => Some(row),
+            _ => None,
+        })
+        .collect())
+}
+
+fn execute_sql(conn: &Connection, sql: &str, params: &[RdbcValue]) -> RdbcResult<u64> {
+    conn.execute(sql, params_from_iter(params.iter()))
+        .map(|count| count as u64)
+        .map_err(database_error)
+}
+
+/// Locks the connection. A panic while it was held leaves no state behind
+/// that a later statement could trip over, so a poisoned lock is taken over.
+fn lock(conn: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    conn.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub(crate) fn database_error(err: rusqlite::Error) -> RdbcError {
+    match err.sqlite_error_code() {
+        Some(rusqlite::ErrorCode::DatabaseBusy) | Some(rusqlite::ErrorCode::DatabaseLocked) => {
+            RdbcError::Serialization(err.to_string())
+        }
+        _ => RdbcError::Database(err.to_string()),
+    }
+}
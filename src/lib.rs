@@ -1,12 +1,25 @@
 mod bean;
 mod build;
 mod db;
+mod error;
+mod executor;
+mod migrate;
 mod model;
 mod render;
+mod schema;
+mod template;
 mod wrapper;
 
 pub use bean::*;
 pub use build::*;
 pub use db::*;
+pub use error::*;
+pub use executor::*;
+pub use migrate::*;
 pub use render::*;
+pub use schema::*;
+pub use template::*;
 pub use wrapper::*;
+
+#[cfg(feature = "derive")]
+pub use bmbp_sql_macros::{FromRdbcRow, RdbcTable};
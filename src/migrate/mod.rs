@@ -0,0 +1,582 @@
+mod script;
+
+use crate::migrate::script::{checksum, split_sql_statements};
+use crate::render::qualified_name;
+use crate::schema::{row_i64, row_string};
+use crate::{
+    render_ddl, render_script, DataBase, RdbcColumnType, RdbcDdlWrapper, RdbcError, RdbcExecutor,
+    RdbcIntrospector, RdbcResult, RdbcValue, TableDDLWrapper,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Statements of one migration direction.
+#[derive(Debug, Clone)]
+pub enum RdbcMigrationScript {
+    /// SQL text with `;` separated statements, run as written on every dialect.
+    Sql(String),
+    /// DDL rendered for the target dialect.
+    Ddl(Vec<RdbcDdlWrapper>),
+}
+
+impl RdbcMigrationScript {
+    pub fn render(&self, db_type: DataBase) -> RdbcResult<Vec<String>> {
+        match self {
+            RdbcMigrationScript::Sql(sql) => Ok(split_sql_statements(sql)),
+            RdbcMigrationScript::Ddl(ddl) => {
+                let mut statements = vec![];
+                for item in ddl.iter() {
+                    statements.extend(render_ddl(item, db_type)?);
+                }
+                Ok(statements)
+            }
+        }
+    }
+}
+
+/// Versioned schema change with an optional undo script.
+#[derive(Debug, Clone)]
+pub struct RdbcMigration {
+    pub version: u64,
+    pub description: String,
+    pub up: RdbcMigrationScript,
+    pub down: Option<RdbcMigrationScript>,
+}
+
+impl RdbcMigration {
+    pub fn sql<D, S>(version: u64, description: D, sql: S) -> Self
+    where
+        D: ToString,
+        S: ToString,
+    {
+        RdbcMigration {
+            version,
+            description: description.to_string(),
+            up: RdbcMigrationScript::Sql(sql.to_string()),
+            down: None,
+        }
+    }
+    pub fn ddl<D>(version: u64, description: D, ddl: Vec<RdbcDdlWrapper>) -> Self
+    where
+        D: ToString,
+    {
+        RdbcMigration {
+            version,
+            description: description.to_string(),
+            up: RdbcMigrationScript::Ddl(ddl),
+            down: None,
+        }
+    }
+    pub fn down_sql<S>(&mut self, sql: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.down = Some(RdbcMigrationScript::Sql(sql.to_string()));
+        self
+    }
+    pub fn down_ddl(&mut self, ddl: Vec<RdbcDdlWrapper>) -> &mut Self {
+        self.down = Some(RdbcMigrationScript::Ddl(ddl));
+        self
+    }
+
+    /// Checksum of the up statements as rendered for `db_type`.
+    pub fn checksum(&self, db_type: DataBase) -> RdbcResult<String> {
+        Ok(checksum(&self.up.render(db_type)?))
+    }
+}
+
+/// Row of the history table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RdbcAppliedMigration {
+    pub version: u64,
+    pub description: String,
+    pub checksum: String,
+}
+
+/// Applies and rolls back migrations, recording them in a history table.
+///
+/// Migrations run in version order, each in a transaction together with its
+/// history row where the dialect has transactional DDL (PostgreSQL, SQLite).
+/// A row in the lock table keeps a second migrator out until the run ends;
+/// [`RdbcMigrator::unlock`] clears a lock left behind by a crashed run.
+///
+/// `load_dir` reads `V<version>__<description>.sql` files and their undo
+/// scripts `U<version>__<description>.sql`; as in [`crate::RdbcSqlRegistry`],
+/// `V1__init.postgres.sql` applies to PostgreSQL only and wins over `V1__init.sql`.
+#[derive(Debug, Clone)]
+pub struct RdbcMigrator {
+    db_type: DataBase,
+    schema: String,
+    history_table: String,
+    lock_table: String,
+    migrations: Vec<RdbcMigration>,
+}
+
+impl RdbcMigrator {
+    pub fn new(db_type: DataBase) -> Self {
+        RdbcMigrator {
+            db_type,
+            schema: "".to_string(),
+            history_table: "rdbc_migration_history".to_string(),
+            lock_table: "rdbc_migration_lock".to_string(),
+            migrations: vec![],
+        }
+    }
+    /// Schema of the history and lock tables.
+    pub fn schema<S>(&mut self, schema: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.schema = schema.to_string();
+        self
+    }
+    pub fn history_table<T>(&mut self, table: T) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.history_table = table.to_string();
+        self
+    }
+    pub fn lock_table<T>(&mut self, table: T) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.lock_table = table.to_string();
+        self
+    }
+    pub fn migration(&mut self, migration: RdbcMigration) -> &mut Self {
+        self.migrations.push(migration);
+        self.migrations.sort_by_key(|migration| migration.version);
+        self
+    }
+    pub fn migrations(&self) -> &[RdbcMigration] {
+        &self.migrations
+    }
+
+    /// Loads the migration files of `dir`.
+    pub fn load_dir<P: AsRef<Path>>(&mut self, dir: P) -> RdbcResult<&mut Self> {
+        let dir = dir.as_ref();
+        let entries = fs::read_dir(dir)
+            .map_err(|e| RdbcError::Migration(format!("read dir {}: {}", dir.display(), e)))?;
+        let mut paths = vec![];
+        for entry in entries {
+            let entry = entry.map_err(|e| RdbcError::Migration(e.to_string()))?;
+            paths.push(entry.path());
+        }
+        paths.sort();
+        let mut files: Vec<MigrationFile> = vec![];
+        for path in paths {
+            if path.extension().map(|e| e != "sql").unwrap_or(true) {
+                continue;
+            }
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let (name, dialect) = match stem.rsplit_once('.') {
+                Some((name, ext)) => match DataBase::from_str(ext) {
+                    Ok(dialect) => (name, Some(dialect)),
+                    Err(_) => (stem.as_ref(), None),
+                },
+                None => (stem.as_ref(), None),
+            };
+            if dialect.map(|d| d != self.db_type).unwrap_or(false) {
+                continue;
+            }
+            let (undo, rest) = match (name.strip_prefix('V'), name.strip_prefix('U')) {
+                (Some(rest), _) => (false, rest),
+                (_, Some(rest)) => (true, rest),
+                _ => continue,
+            };
+            let (version, description) = match rest.split_once("__") {
+                Some((version, description)) => match version.parse::<u64>() {
+                    Ok(version) => (version, description.replace('_', " ")),
+                    Err(_) => continue,
+                },
+                None => continue,
+            };
+            let text = fs::read_to_string(&path).map_err(|e| {
+                RdbcError::Migration(format!("read file {}: {}", path.display(), e))
+            })?;
+            files.push(MigrationFile {
+                version,
+                description,
+                undo,
+                dialect: dialect.is_some(),
+                text,
+            });
+        }
+        // a dialect file replaces the generic file of the same version
+        files.sort_by_key(|file| (file.version, file.undo, !file.dialect));
+        files.dedup_by_key(|file| (file.version, file.undo));
+
+        for file in files.iter().filter(|file| !file.undo) {
+            let mut migration = RdbcMigration::sql(file.version, &file.description, &file.text);
+            if let Some(undo) = files
+                .iter()
+                .find(|undo| undo.undo && undo.version == file.version)
+            {
+                migration.down_sql(&undo.text);
+            }
+            self.migration(migration);
+        }
+        if let Some(undo) = files.iter().find(|undo| {
+            undo.undo
+                && !self
+                    .migrations
+                    .iter()
+                    .any(|migration| migration.version == undo.version)
+        }) {
+            return Err(RdbcError::Migration(format!(
+                "undo script of version {} has no migration",
+                undo.version
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Applies the pending migrations, returning their versions.
+    pub async fn migrate<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+    ) -> RdbcResult<Vec<u64>> {
+        self.prepare(executor).await?;
+        self.lock(executor).await?;
+        let result = self.apply_pending(executor).await;
+        let unlocked = self.unlock(executor).await;
+        let applied = result?;
+        unlocked?;
+        Ok(applied)
+    }
+
+    /// Undoes the applied migrations above `target`, newest first, returning
+    /// their versions.
+    pub async fn rollback<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+        target: u64,
+    ) -> RdbcResult<Vec<u64>> {
+        self.prepare(executor).await?;
+        self.lock(executor).await?;
+        let result = self.undo_applied(executor, target).await;
+        let unlocked = self.unlock(executor).await;
+        let undone = result?;
+        unlocked?;
+        Ok(undone)
+    }
+
+    /// SQL `migrate` would run, each migration headed by a `--` comment.
+    /// Nothing is written, not even the history table.
+    pub async fn dry_run<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+    ) -> RdbcResult<Vec<String>> {
+        let applied = if self.table_exists(executor, &self.history_table).await? {
+            self.applied(executor).await?
+        } else {
+            vec![]
+        };
+        let mut statements = vec![];
+        for migration in self.pending(&applied)? {
+            statements.push(format!(
+                "-- {} {}",
+                migration.version, migration.description
+            ));
+            statements.extend(migration.up.render(self.db_type)?);
+        }
+        Ok(statements)
+    }
+
+    /// History rows in version order.
+    pub async fn applied<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+    ) -> RdbcResult<Vec<RdbcAppliedMigration>> {
+        let sql = format!(
+            "SELECT version, description, checksum FROM {} ORDER BY version",
+            qualified_name(&self.schema, &self.history_table)
+        );
+        let rows = query_rows(executor, &sql, &[]).await?;
+        Ok(rows
+            .iter()
+            .map(|row| RdbcAppliedMigration {
+                version: row_i64(row, "version").unwrap_or(0) as u64,
+                description: row_string(row, "description").unwrap_or_default(),
+                checksum: row_string(row, "checksum").unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Releases the migration lock, whoever holds it.
+    pub async fn unlock<E: RdbcExecutor + ?Sized>(&self, executor: &mut E) -> RdbcResult<()> {
+        let sql = format!(
+            "DELETE FROM {}",
+            qualified_name(&self.schema, &self.lock_table)
+        );
+        executor.execute_sql(&sql, &[]).await?;
+        Ok(())
+    }
+
+    async fn apply_pending<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+    ) -> RdbcResult<Vec<u64>> {
+        let applied = self.applied(executor).await?;
+        let mut versions = vec![];
+        for migration in self.pending(&applied)? {
+            let statements = migration.up.render(self.db_type)?;
+            let (sql, params) = self.script(
+                &format!(
+                    "INSERT INTO {} (version, description, checksum, applied_at) \
+                     VALUES (#{{version}}, #{{description}}, #{{checksum}}, #{{applied_at}})",
+                    qualified_name(&self.schema, &self.history_table)
+                ),
+                vec![
+                    ("version", RdbcValue::BigInt(migration.version as i64)),
+                    (
+                        "description",
+                        RdbcValue::from(migration.description.as_str()),
+                    ),
+                    ("checksum", RdbcValue::from(checksum(&statements))),
+                    (
+                        "applied_at",
+                        RdbcValue::DateTime(chrono::Local::now().naive_local()),
+                    ),
+                ],
+            )?;
+            self.run_atomic(executor, migration.version, &statements, (&sql, &params))
+                .await?;
+            versions.push(migration.version);
+        }
+        Ok(versions)
+    }
+
+    async fn undo_applied<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+        target: u64,
+    ) -> RdbcResult<Vec<u64>> {
+        let applied = self.applied(executor).await?;
+        let mut undo = vec![];
+        for row in applied.iter().rev().filter(|row| row.version > target) {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|migration| migration.version == row.version)
+                .ok_or_else(|| {
+                    RdbcError::Migration(format!("applied version {} is unknown", row.version))
+                })?;
+            let down = migration.down.as_ref().ok_or_else(|| {
+                RdbcError::Migration(format!("version {} has no undo script", row.version))
+            })?;
+            undo.push((migration.version, down.render(self.db_type)?));
+        }
+        let mut versions = vec![];
+        for (version, statements) in undo {
+            let (sql, params) = self.script(
+                &format!(
+                    "DELETE FROM {} WHERE version = #{{version}}",
+                    qualified_name(&self.schema, &self.history_table)
+                ),
+                vec![("version", RdbcValue::BigInt(version as i64))],
+            )?;
+            self.run_atomic(executor, version, &statements, (&sql, &params))
+                .await?;
+            versions.push(version);
+        }
+        Ok(versions)
+    }
+
+    /// Runs `statements` and the history change in one transaction where the
+    /// dialect supports it.
+    async fn run_atomic<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+        version: u64,
+        statements: &[String],
+        (history_sql, history_params): (&str, &[RdbcValue]),
+    ) -> RdbcResult<()> {
+        let transactional = matches!(self.db_type, DataBase::Postgres | DataBase::Sqlite);
+        if transactional {
+            executor.execute_sql("BEGIN", &[]).await?;
+        }
+        let mut result = Ok(());
+        for statement in statements.iter() {
+            if let Err(err) = executor.execute_sql(statement, &[]).await {
+                result = Err(RdbcError::Migration(format!(
+                    "version {} failed at `{}`: {}",
+                    version, statement, err
+                )));
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = executor
+                .execute_sql(history_sql, history_params)
+                .await
+                .map(|_| ());
+        }
+        if transactional {
+            match result {
+                Ok(_) => executor.execute_sql("COMMIT", &[]).await.map(|_| ()),
+                Err(err) => {
+                    // A failed rollback must not hide why the migration failed.
+                    let _ = executor.execute_sql("ROLLBACK", &[]).await;
+                    Err(err)
+                }
+            }
+        } else {
+            result
+        }
+    }
+
+    /// Migrations not applied yet, after checking that applied ones are unchanged.
+    fn pending(&self, applied: &[RdbcAppliedMigration]) -> RdbcResult<Vec<&RdbcMigration>> {
+        for pair in self.migrations.windows(2) {
+            if pair[0].version == pair[1].version {
+                return Err(RdbcError::Migration(format!(
+                    "duplicate migration version {}",
+                    pair[0].version
+                )));
+            }
+        }
+        let mut pending = vec![];
+        for migration in self.migrations.iter() {
+            match applied.iter().find(|row| row.version == migration.version) {
+                Some(row) => {
+                    if row.checksum != migration.checksum(self.db_type)? {
+                        return Err(RdbcError::Migration(format!(
+                            "checksum of applied version {} changed",
+                            migration.version
+                        )));
+                    }
+                }
+                None => pending.push(migration),
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Creates the history and lock tables when missing. IF NOT EXISTS lets a
+    /// migrator that lost the race to create them go on to the lock.
+    async fn prepare<E: RdbcExecutor + ?Sized>(&self, executor: &mut E) -> RdbcResult<()> {
+        if !self.table_exists(executor, &self.history_table).await? {
+            let mut table = TableDDLWrapper::new(&self.history_table);
+            table.schema(&self.schema).if_not_exists();
+            table
+                .add_column("version", RdbcColumnType::BigInt)
+                .primary_key();
+            table
+                .add_column("description", RdbcColumnType::Varchar(255))
+                .not_null();
+            table
+                .add_column("checksum", RdbcColumnType::Varchar(64))
+                .not_null();
+            table
+                .add_column("applied_at", RdbcColumnType::DateTime)
+                .not_null();
+            self.execute_ddl(executor, table.into()).await?;
+        }
+        if !self.table_exists(executor, &self.lock_table).await? {
+            let mut table = TableDDLWrapper::new(&self.lock_table);
+            table.schema(&self.schema).if_not_exists();
+            table
+                .add_column("lock_id", RdbcColumnType::Int)
+                .primary_key();
+            table
+                .add_column("locked_by", RdbcColumnType::Varchar(64))
+                .not_null();
+            table
+                .add_column("locked_at", RdbcColumnType::DateTime)
+                .not_null();
+            self.execute_ddl(executor, table.into()).await?;
+        }
+        Ok(())
+    }
+
+    /// Takes the lock by inserting its single row; the primary key makes a
+    /// second insert fail while the lock is held.
+    async fn lock<E: RdbcExecutor + ?Sized>(&self, executor: &mut E) -> RdbcResult<()> {
+        let (sql, params) = self.script(
+            &format!(
+                "INSERT INTO {} (lock_id, locked_by, locked_at) VALUES (1, #{{locked_by}}, #{{locked_at}})",
+                qualified_name(&self.schema, &self.lock_table)
+            ),
+            vec![
+                ("locked_by", RdbcValue::from(uuid::Uuid::new_v4().to_string())),
+                ("locked_at", RdbcValue::DateTime(chrono::Local::now().naive_local())),
+            ],
+        )?;
+        if let Err(err) = executor.execute_sql(&sql, &params).await {
+            let sql = format!(
+                "SELECT locked_by AS holder, locked_at AS since FROM {}",
+                qualified_name(&self.schema, &self.lock_table)
+            );
+            return match query_rows(executor, &sql, &[]).await?.first() {
+                Some(row) => Err(RdbcError::Migration(format!(
+                    "migration lock is held by {} since {}",
+                    row_string(row, "holder").unwrap_or_default(),
+                    row_string(row, "since").unwrap_or_default()
+                ))),
+                None => Err(err),
+            };
+        }
+        Ok(())
+    }
+
+    async fn table_exists<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+        table: &str,
+    ) -> RdbcResult<bool> {
+        let (sql, params) = RdbcIntrospector::new(self.db_type).table_query(&self.schema, table);
+        Ok(!query_rows(executor, &sql, &params).await?.is_empty())
+    }
+
+    async fn execute_ddl<E: RdbcExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+        ddl: RdbcDdlWrapper,
+    ) -> RdbcResult<()> {
+        for sql in render_ddl(&ddl, self.db_type)? {
+            executor.execute_sql(&sql, &[]).await?;
+        }
+        Ok(())
+    }
+
+    fn script(
+        &self,
+        sql: &str,
+        params: Vec<(&str, RdbcValue)>,
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        let params: HashMap<String, RdbcValue> = params
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect();
+        render_script(sql, &params, self.db_type)
+    }
+}
+
+/// Rows of a migrator query as maps. Keys follow the key case of `executor`,
+/// so the migrator only reads columns named by a single word.
+async fn query_rows<E: RdbcExecutor + ?Sized>(
+    executor: &E,
+    sql: &str,
+    params: &[RdbcValue],
+) -> RdbcResult<Vec<HashMap<String, RdbcValue>>> {
+    let rows = executor.query_sql(sql, params).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| match row {
+            RdbcValue::Object(row) => row,
+            _ => HashMap::new(),
+        })
+        .collect())
+}
+
+struct MigrationFile {
+    version: u64,
+    description: String,
+    undo: bool,
+    dialect: bool,
+    text: String,
+}
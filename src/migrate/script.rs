@@ -0,0 +1,123 @@
+/// Splits SQL text into statements at `;` outside of quotes, comments and
+/// PostgreSQL dollar-quoted bodies. Comments ahead of a statement are
+/// dropped.
+pub(crate) fn split_sql_statements(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut statements = vec![];
+    let mut current = String::new();
+    let mut has_code = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\'' | '"' | '`' => {
+                let end = find_char(&chars, i + 1, c);
+                current.extend(&chars[i..end]);
+                has_code = true;
+                i = end;
+            }
+            '-' if next == Some('-') => {
+                let end = find_char(&chars, i, '\n');
+                if has_code {
+                    current.extend(&chars[i..end]);
+                }
+                i = end;
+            }
+            '/' if next == Some('*') => {
+                let end = find_seq(&chars, i + 2, &['*', '/']);
+                if has_code {
+                    current.extend(&chars[i..end]);
+                }
+                i = end;
+            }
+            '$' => match dollar_tag(&chars, i) {
+                Some(tag) => {
+                    let end = find_seq(&chars, i + tag.len(), &tag);
+                    current.extend(&chars[i..end]);
+                    has_code = true;
+                    i = end;
+                }
+                None => {
+                    current.push(c);
+                    has_code = true;
+                    i += 1;
+                }
+            },
+            ';' => {
+                if has_code {
+                    statements.push(current.trim().to_string());
+                }
+                current.clear();
+                has_code = false;
+                i += 1;
+            }
+            _ => {
+                current.push(c);
+                has_code |= !c.is_whitespace();
+                i += 1;
+            }
+        }
+    }
+    if has_code {
+        statements.push(current.trim().to_string());
+    }
+    statements
+}
+
+/// Index after the closing `quote`, doubled quotes staying inside.
+fn find_char(chars: &[char], from: usize, quote: char) -> usize {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == quote {
+            if quote != '\n' && chars.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+/// Index after the first occurrence of `seq` at or after `from`.
+fn find_seq(chars: &[char], from: usize, seq: &[char]) -> usize {
+    let mut i = from;
+    while i + seq.len() <= chars.len() {
+        if chars[i..i + seq.len()] == *seq {
+            return i + seq.len();
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+/// `$$` or `$tag$` starting at `at`; `$1` params are not tags.
+fn dollar_tag(chars: &[char], at: usize) -> Option<Vec<char>> {
+    let mut i = at + 1;
+    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+        if i == at + 1 && chars[i].is_ascii_digit() {
+            return None;
+        }
+        i += 1;
+    }
+    if chars.get(i) == Some(&'$') {
+        Some(chars[at..=i].to_vec())
+    } else {
+        None
+    }
+}
+
+/// FNV-1a hash of the statements, as 16 hex digits. Detects edits of applied
+/// migrations; it is not meant to resist tampering.
+pub(crate) fn checksum(statements: &[String]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for statement in statements.iter() {
+        for byte in statement.bytes().chain(std::iter::once(b'\n')) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
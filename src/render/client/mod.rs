@@ -1,8 +1,12 @@
 mod mysql;
+mod mysql_ddl;
 mod oracle;
+mod oracle_ddl;
 mod pg;
+mod pg_ddl;
 mod sqlite;
-mod util;
+mod sqlite_ddl;
+pub(crate) mod util;
 
 pub use mysql::*;
 pub use oracle::*;
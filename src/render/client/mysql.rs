@@ -1,100 +1,23 @@
-use crate::render::render::RdbcSQLRender;
-use crate::{RdbcDeleteWrapper, RdbcInsertWrapper, RdbcQueryWrapper, RdbcUpdateWrapper, RdbcValue};
-use std::collections::HashMap;
+use crate::render::client::ScriptSQLRender;
+use crate::render::render::RdbcDmlDialect;
 
-pub struct MysqlSQLRender {}
-impl RdbcSQLRender for MysqlSQLRender {
-    fn render_query(query: &RdbcQueryWrapper) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
-    }
-
-    fn render_update(query: &RdbcUpdateWrapper) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
-    }
-
-    fn render_insert(query: &RdbcInsertWrapper) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
-    }
-
-    fn render_delete(query: &RdbcDeleteWrapper) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
-    }
-
-    fn render_query_with_params(
-        sql_wrapper: &RdbcQueryWrapper,
-        params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
-    }
-
-    fn render_update_with_prams(
-        sql_wrapper: &RdbcUpdateWrapper,
-        params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
-    }
-
-    fn render_insert_with_params(
-        sql_wrapper: &RdbcInsertWrapper,
-        params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
-    }
+pub struct MysqlDialect;
 
-    fn render_delete_with_params(
-        sql_wrapper: &RdbcDeleteWrapper,
-        params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+impl RdbcDmlDialect for MysqlDialect {
+    fn placeholder(_index: usize) -> String {
+        "?".to_string()
     }
 
-    fn render_query_script(sql_wrapper: &RdbcQueryWrapper) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
+    fn text_param(param: &str) -> String {
+        format!("CAST({} AS CHAR)", param)
     }
 
-    fn render_update_script(
-        sql_wrapper: &RdbcUpdateWrapper,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
+    fn next_value(_sequence: &str) -> String {
+        // No sequences; NULL makes an AUTO_INCREMENT column take its next value.
+        "NULL".to_string()
     }
 
-    fn render_insert_script(
-        sql_wrapper: &RdbcInsertWrapper,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
-    }
-
-    fn render_delete_script(
-        sql_wrapper: &RdbcDeleteWrapper,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
-    }
-
-    fn render_query_script_with_params(
-        sql_wrapper: &RdbcQueryWrapper,
-        params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
-    }
-
-    fn render_update_script_with_params(
-        sql_wrapper: &RdbcUpdateWrapper,
-        params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
-    }
-
-    fn render_insert_script_with_params(
-        sql_wrapper: &RdbcInsertWrapper,
-        params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
-    }
-
-    fn render_delete_script_with_params(
-        sql_wrapper: &RdbcDeleteWrapper,
-        params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
-    }
+    const SEQUENCES: bool = false;
 }
+
+pub type MysqlSQLRender = ScriptSQLRender<MysqlDialect>;
@@ -0,0 +1,246 @@
+use crate::render::client::MysqlSQLRender;
+use crate::render::render::{ansi_literal, qualified_name, RdbcDDLRender};
+use crate::{
+    AlterColumn, AlterSequenceDDLWrapper, ColumnDDLWrapper, ColumnIdentity, CommentDDLWrapper,
+    DropIndexDDLWrapper, DropSequenceDDLWrapper, IndexDDLWrapper, RdbcColumnType, RdbcError,
+    RdbcResult, RdbcValue, ReferentialAction, SequenceDDLWrapper, TableDDLWrapper,
+};
+
+impl RdbcDDLRender for MysqlSQLRender {
+    fn render_alter_column(table_name: &str, alter: &AlterColumn) -> RdbcResult<Vec<String>> {
+        let column = &alter.column;
+        let mut statements = vec![];
+        if alter.change_type || alter.change_nullable {
+            // MODIFY restates the whole column, default included.
+            statements.push(format!(
+                "ALTER TABLE {} MODIFY COLUMN {}",
+                table_name,
+                Self::render_column_spec(column)?
+            ));
+        } else if alter.change_default {
+            statements.push(match column.default_value.as_ref() {
+                Some(default_value) => format!(
+                    "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {}",
+                    table_name,
+                    column.name,
+                    Self::render_default(default_value)
+                ),
+                None => format!(
+                    "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT",
+                    table_name, column.name
+                ),
+            });
+        }
+        if alter.change_unique {
+            // An inline UNIQUE creates an index named after the column.
+            statements.push(if column.unique {
+                format!("ALTER TABLE {} ADD UNIQUE ({})", table_name, column.name)
+            } else {
+                format!("ALTER TABLE {} DROP INDEX {}", table_name, column.name)
+            });
+        }
+        Ok(statements)
+    }
+
+    fn render_column_type(column_type: &RdbcColumnType) -> String {
+        match column_type {
+            RdbcColumnType::Char(len) => format!("CHAR({})", len),
+            RdbcColumnType::Varchar(len) => format!("VARCHAR({})", len),
+            RdbcColumnType::Text => "TEXT".to_string(),
+            RdbcColumnType::LongText => "LONGTEXT".to_string(),
+            RdbcColumnType::SmallInt => "SMALLINT".to_string(),
+            RdbcColumnType::Int => "INT".to_string(),
+            RdbcColumnType::BigInt => "BIGINT".to_string(),
+            RdbcColumnType::Float => "FLOAT".to_string(),
+            RdbcColumnType::Double => "DOUBLE".to_string(),
+            RdbcColumnType::Decimal(precision, scale) => {
+                format!("DECIMAL({},{})", precision, scale)
+            }
+            RdbcColumnType::Boolean => "BOOLEAN".to_string(),
+            RdbcColumnType::Date => "DATE".to_string(),
+            RdbcColumnType::DateTime => "DATETIME".to_string(),
+            RdbcColumnType::Time => "TIME".to_string(),
+            RdbcColumnType::Timestamp => "TIMESTAMP".to_string(),
+            RdbcColumnType::Bytes => "LONGBLOB".to_string(),
+            RdbcColumnType::Json => "JSON".to_string(),
+            RdbcColumnType::Uuid => "CHAR(36)".to_string(),
+            RdbcColumnType::Custom(name) => name.clone(),
+        }
+    }
+
+    fn render_literal(value: &RdbcValue) -> String {
+        match value {
+            // Backslash is an escape character in MySQL string literals.
+            RdbcValue::Char(_)
+            | RdbcValue::Varchar(_)
+            | RdbcValue::Text(_)
+            | RdbcValue::LongText(_)
+            | RdbcValue::Array(_)
+            | RdbcValue::Object(_) => format!(
+                "'{}'",
+                value.to_string().replace('\\', "\\\\").replace('\'', "''")
+            ),
+            _ => ansi_literal(value),
+        }
+    }
+
+    fn render_referential_action(event: &str, action: ReferentialAction) -> RdbcResult<String> {
+        Ok(match action {
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::SetDefault => {
+                return Err(RdbcError::Unsupported(format!(
+                    "MySQL rejects ON {} SET DEFAULT",
+                    event
+                )))
+            }
+        }
+        .to_string())
+    }
+
+    fn render_create_index(index: &IndexDDLWrapper) -> RdbcResult<Vec<String>> {
+        if index.where_condition.is_some() {
+            return Err(RdbcError::Unsupported(format!(
+                "MySQL has no partial index {}",
+                index.name
+            )));
+        }
+        if index.if_not_exists {
+            return Err(RdbcError::Unsupported(
+                "MySQL has no CREATE INDEX IF NOT EXISTS".to_string(),
+            ));
+        }
+        Ok(vec![format!(
+            "CREATE {}INDEX {} ON {} ({})",
+            if index.unique { "UNIQUE " } else { "" },
+            index.name,
+            qualified_name(&index.schema, &index.table),
+            Self::render_index_columns(index)
+        )])
+    }
+
+    fn render_drop_index(drop: &DropIndexDDLWrapper) -> RdbcResult<Vec<String>> {
+        if drop.if_exists {
+            return Err(RdbcError::Unsupported(
+                "MySQL has no DROP INDEX IF EXISTS".to_string(),
+            ));
+        }
+        Ok(vec![format!(
+            "DROP INDEX {} ON {}",
+            drop.name,
+            qualified_name(&drop.schema, &drop.table)
+        )])
+    }
+
+    fn render_comments(
+        _schema: &str,
+        _table: &str,
+        _table_comment: Option<&String>,
+        _columns: &[ColumnDDLWrapper],
+    ) -> RdbcResult<Vec<String>> {
+        // Comments are part of the column and table definitions.
+        Ok(vec![])
+    }
+
+    fn render_comment(comment: &CommentDDLWrapper) -> RdbcResult<Vec<String>> {
+        let table_name = qualified_name(&comment.schema, &comment.table);
+        if comment.column.is_none() {
+            return Ok(vec![format!(
+                "ALTER TABLE {} COMMENT = {}",
+                table_name,
+                Self::render_literal(&RdbcValue::from(comment.comment.as_str()))
+            )]);
+        }
+        match comment.column_definition.as_ref() {
+            Some(definition) => {
+                let mut definition = definition.clone();
+                definition.comment = Some(comment.comment.clone());
+                Ok(vec![format!(
+                    "ALTER TABLE {} MODIFY COLUMN {}",
+                    table_name,
+                    Self::render_column_spec(&definition)?
+                )])
+            }
+            None => Err(RdbcError::Unsupported(format!(
+                "MySQL needs the column definition to comment on {}.{}",
+                table_name,
+                comment.column.as_ref().unwrap()
+            ))),
+        }
+    }
+
+    fn render_table_options(table: &TableDDLWrapper) -> String {
+        let mut options = String::new();
+        let start = table
+            .columns
+            .iter()
+            .find_map(|column| column.identity.as_ref().and_then(|identity| identity.start));
+        if let Some(start) = start {
+            options.push_str(&format!(" AUTO_INCREMENT = {}", start));
+        }
+        if let Some(comment) = table.comment.as_ref() {
+            options.push_str(&format!(
+                " COMMENT = {}",
+                Self::render_literal(&RdbcValue::from(comment.as_str()))
+            ));
+        }
+        options
+    }
+
+    fn render_identity(identity: &ColumnIdentity) -> RdbcResult<String> {
+        if identity.increment.is_some() || identity.always {
+            return Err(RdbcError::Unsupported(
+                "MySQL AUTO_INCREMENT has neither a per column increment nor GENERATED ALWAYS"
+                    .to_string(),
+            ));
+        }
+        // The start value is a table option.
+        Ok(" AUTO_INCREMENT".to_string())
+    }
+
+    fn render_create_sequence(sequence: &SequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "MySQL has no sequence {}, use an AUTO_INCREMENT column",
+            sequence.name
+        )))
+    }
+
+    fn render_alter_sequence(alter: &AlterSequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "MySQL has no sequence {}",
+            alter.name
+        )))
+    }
+
+    fn render_drop_sequence(drop: &DropSequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "MySQL has no sequence {}",
+            drop.name
+        )))
+    }
+
+    fn render_column_spec(column: &ColumnDDLWrapper) -> RdbcResult<String> {
+        let mut spec = format!(
+            "{} {}",
+            column.name,
+            Self::render_column_type(&column.column_type)
+        );
+        if let Some(identity) = column.identity.as_ref() {
+            spec.push_str(&Self::render_identity(identity)?);
+        }
+        if let Some(default_value) = column.default_value.as_ref() {
+            spec.push_str(" DEFAULT ");
+            spec.push_str(&Self::render_default(default_value));
+        }
+        if !column.nullable {
+            spec.push_str(" NOT NULL");
+        }
+        if let Some(comment) = column.comment.as_ref() {
+            spec.push_str(" COMMENT ");
+            spec.push_str(&Self::render_literal(&RdbcValue::from(comment.as_str())));
+        }
+        Ok(spec)
+    }
+}
@@ -1,51 +1,83 @@
-use crate::render::render::RdbcSQLRender;
-use crate::{RdbcDeleteWrapper, RdbcInsertWrapper, RdbcQueryWrapper, RdbcUpdateWrapper, RdbcValue};
+use crate::render::client::ScriptSQLRender;
+use crate::render::render::{RdbcDmlDialect, RdbcSQLRender};
+use crate::{
+    RdbcDeleteWrapper, RdbcError, RdbcInsertWrapper, RdbcQueryWrapper, RdbcResult,
+    RdbcUpdateWrapper, RdbcValue,
+};
 use std::collections::HashMap;
 
+pub struct OracleDialect;
+
+impl RdbcDmlDialect for OracleDialect {
+    fn placeholder(index: usize) -> String {
+        format!(":{}", index)
+    }
+
+    fn text_param(param: &str) -> String {
+        format!("TO_CHAR({})", param)
+    }
+
+    fn next_value(sequence: &str) -> String {
+        format!("{}.NEXTVAL", sequence)
+    }
+}
+
+/// Renders INSERT statements through [`ScriptSQLRender`]; the other DML
+/// statements are not implemented for Oracle yet.
 pub struct OracleSQLRender {}
+
+type OracleInsertRender = ScriptSQLRender<OracleDialect>;
+
+/// An empty statement must not pass for a rendered one.
+fn unsupported<T>() -> RdbcResult<T> {
+    Err(RdbcError::Unsupported(
+        "Oracle DML rendering is only implemented for INSERT".to_string(),
+    ))
+}
+
 impl RdbcSQLRender for OracleSQLRender {
-    fn render_query(query: &RdbcQueryWrapper) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+    fn render_query(query: &RdbcQueryWrapper) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        unsupported()
     }
 
-    fn render_update(query: &RdbcUpdateWrapper) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+    fn render_update(query: &RdbcUpdateWrapper) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        unsupported()
     }
 
-    fn render_insert(query: &RdbcInsertWrapper) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+    fn render_insert(query: &RdbcInsertWrapper) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        OracleInsertRender::render_insert(query)
     }
 
-    fn render_delete(query: &RdbcDeleteWrapper) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+    fn render_delete(query: &RdbcDeleteWrapper) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        unsupported()
     }
 
     fn render_query_with_params(
         sql_wrapper: &RdbcQueryWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        unsupported()
     }
 
     fn render_update_with_prams(
         sql_wrapper: &RdbcUpdateWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        unsupported()
     }
 
     fn render_insert_with_params(
         sql_wrapper: &RdbcInsertWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        OracleInsertRender::render_insert_with_params(sql_wrapper, params)
     }
 
     fn render_delete_with_params(
         sql_wrapper: &RdbcDeleteWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        ("".to_string(), vec![])
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        unsupported()
     }
 
     fn render_query_script(sql_wrapper: &RdbcQueryWrapper) -> (String, HashMap<String, RdbcValue>) {
@@ -54,20 +86,20 @@ impl RdbcSQLRender for OracleSQLRender {
 
     fn render_update_script(
         sql_wrapper: &RdbcUpdateWrapper,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
+        unsupported()
     }
 
     fn render_insert_script(
         sql_wrapper: &RdbcInsertWrapper,
     ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
+        OracleInsertRender::render_insert_script(sql_wrapper)
     }
 
     fn render_delete_script(
         sql_wrapper: &RdbcDeleteWrapper,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
+        unsupported()
     }
 
     fn render_query_script_with_params(
@@ -80,21 +112,25 @@ impl RdbcSQLRender for OracleSQLRender {
     fn render_update_script_with_params(
         sql_wrapper: &RdbcUpdateWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
+        unsupported()
     }
 
     fn render_insert_script_with_params(
         sql_wrapper: &RdbcInsertWrapper,
         params: &HashMap<String, RdbcValue>,
     ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
+        OracleInsertRender::render_insert_script_with_params(sql_wrapper, params)
     }
 
     fn render_delete_script_with_params(
         sql_wrapper: &RdbcDeleteWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>) {
-        ("".to_string(), HashMap::new())
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
+        unsupported()
+    }
+
+    fn render_next_value(sequence: &str) -> String {
+        OracleDialect::next_value(sequence)
     }
 }
@@ -0,0 +1,229 @@
+use crate::render::client::OracleSQLRender;
+use crate::render::render::{
+    ansi_literal, hex_string, qualified_name, quote_literal, sequence_options, RdbcDDLRender,
+};
+use crate::{
+    AlterColumn, AlterSequenceDDLWrapper, ColumnDDLWrapper, CommentDDLWrapper, DropIndexDDLWrapper,
+    DropSequenceDDLWrapper, DropTableDDLWrapper, DropViewDDLWrapper, IndexColumn, IndexDDLWrapper,
+    RdbcColumnType, RdbcError, RdbcResult, RdbcValue, ReferentialAction,
+};
+
+impl RdbcDDLRender for OracleSQLRender {
+    fn render_add_column(table_name: &str, column: &ColumnDDLWrapper) -> RdbcResult<String> {
+        let primary_key = if column.primary_key {
+            " PRIMARY KEY"
+        } else {
+            ""
+        };
+        Ok(format!(
+            "ALTER TABLE {} ADD ({}{})",
+            table_name,
+            Self::render_column(column)?,
+            primary_key
+        ))
+    }
+
+    fn render_alter_sequence(alter: &AlterSequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        let mut options = sequence_options(
+            alter.increment,
+            alter.min_value,
+            alter.max_value,
+            alter.cache,
+        );
+        if let Some(cycle) = alter.cycle {
+            options.push_str(if cycle { " CYCLE" } else { " NOCYCLE" });
+        }
+        if let Some(restart) = alter.restart {
+            options.push_str(&format!(" RESTART START WITH {}", restart));
+        }
+        if options.is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(vec![format!(
+            "ALTER SEQUENCE {}{}",
+            qualified_name(&alter.schema, &alter.name),
+            options
+        )])
+    }
+
+    fn render_drop_sequence(drop: &DropSequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        if drop.cascade {
+            return Err(RdbcError::Unsupported(format!(
+                "Oracle has no DROP SEQUENCE {} CASCADE",
+                drop.name
+            )));
+        }
+        Ok(vec![format!(
+            "DROP SEQUENCE {}{}",
+            if drop.if_exists { "IF EXISTS " } else { "" },
+            qualified_name(&drop.schema, &drop.name)
+        )])
+    }
+
+    fn render_alter_column(table_name: &str, alter: &AlterColumn) -> RdbcResult<Vec<String>> {
+        let column = &alter.column;
+        let mut spec = column.name.clone();
+        if alter.change_type {
+            spec.push(' ');
+            spec.push_str(&Self::render_column_type(&column.column_type));
+        }
+        if alter.change_default {
+            spec.push_str(" DEFAULT ");
+            spec.push_str(&match column.default_value.as_ref() {
+                Some(default_value) => Self::render_default(default_value),
+                None => "NULL".to_string(),
+            });
+        }
+        if alter.change_nullable {
+            spec.push_str(if column.nullable {
+                " NULL"
+            } else {
+                " NOT NULL"
+            });
+        }
+        let mut statements = vec![];
+        if spec != column.name {
+            statements.push(format!("ALTER TABLE {} MODIFY ({})", table_name, spec));
+        }
+        if alter.change_unique {
+            statements.push(format!(
+                "ALTER TABLE {} {} UNIQUE ({})",
+                table_name,
+                if column.unique { "ADD" } else { "DROP" },
+                column.name
+            ));
+        }
+        Ok(statements)
+    }
+
+    fn render_drop_table(drop: &DropTableDDLWrapper) -> RdbcResult<Vec<String>> {
+        let if_exists = if drop.if_exists { "IF EXISTS " } else { "" };
+        let cascade = if drop.cascade {
+            " CASCADE CONSTRAINTS"
+        } else {
+            ""
+        };
+        Ok(vec![format!(
+            "DROP TABLE {}{}{}",
+            if_exists,
+            qualified_name(&drop.schema, &drop.name),
+            cascade
+        )])
+    }
+
+    /// Oracle takes only a string literal, `''` removes the comment.
+    fn render_comment(comment: &CommentDDLWrapper) -> RdbcResult<Vec<String>> {
+        let table_name = qualified_name(&comment.schema, &comment.table);
+        let target = match comment.column.as_ref() {
+            Some(column) => format!("COLUMN {}.{}", table_name, column),
+            None => format!("TABLE {}", table_name),
+        };
+        Ok(vec![format!(
+            "COMMENT ON {} IS {}",
+            target,
+            Self::render_literal(&RdbcValue::from(comment.comment.as_str()))
+        )])
+    }
+
+    fn render_column_type(column_type: &RdbcColumnType) -> String {
+        match column_type {
+            RdbcColumnType::Char(len) => format!("CHAR({})", len),
+            RdbcColumnType::Varchar(len) => format!("VARCHAR2({})", len),
+            RdbcColumnType::Text | RdbcColumnType::LongText | RdbcColumnType::Json => {
+                "CLOB".to_string()
+            }
+            RdbcColumnType::SmallInt => "NUMBER(5)".to_string(),
+            RdbcColumnType::Int => "NUMBER(10)".to_string(),
+            RdbcColumnType::BigInt => "NUMBER(19)".to_string(),
+            RdbcColumnType::Float => "BINARY_FLOAT".to_string(),
+            RdbcColumnType::Double => "BINARY_DOUBLE".to_string(),
+            RdbcColumnType::Decimal(precision, scale) => {
+                format!("NUMBER({},{})", precision, scale)
+            }
+            RdbcColumnType::Boolean => "NUMBER(1)".to_string(),
+            RdbcColumnType::Date => "DATE".to_string(),
+            RdbcColumnType::DateTime => "TIMESTAMP".to_string(),
+            RdbcColumnType::Time => "INTERVAL DAY(0) TO SECOND".to_string(),
+            RdbcColumnType::Timestamp => "TIMESTAMP WITH TIME ZONE".to_string(),
+            RdbcColumnType::Bytes => "BLOB".to_string(),
+            RdbcColumnType::Uuid => "VARCHAR2(36)".to_string(),
+            RdbcColumnType::Custom(name) => name.clone(),
+        }
+    }
+
+    fn render_literal(value: &RdbcValue) -> String {
+        match value {
+            RdbcValue::Time(v) => format!("INTERVAL '0 {}' DAY TO SECOND", v),
+            RdbcValue::Bytes(v) => format!("HEXTORAW({})", quote_literal(&hex_string(v))),
+            RdbcValue::Boolean(v) => if *v { "1" } else { "0" }.to_string(),
+            _ => ansi_literal(value),
+        }
+    }
+
+    fn render_referential_action(event: &str, action: ReferentialAction) -> RdbcResult<String> {
+        match (event, action) {
+            ("DELETE", ReferentialAction::Cascade) => Ok("CASCADE".to_string()),
+            ("DELETE", ReferentialAction::SetNull) => Ok("SET NULL".to_string()),
+            _ => Err(RdbcError::Unsupported(format!(
+                "Oracle only supports ON DELETE CASCADE and ON DELETE SET NULL, not ON {} {:?}",
+                event, action
+            ))),
+        }
+    }
+
+    fn render_create_index(index: &IndexDDLWrapper) -> RdbcResult<Vec<String>> {
+        if index.where_condition.is_some() {
+            return Err(RdbcError::Unsupported(format!(
+                "Oracle has no partial index {}",
+                index.name
+            )));
+        }
+        let columns: Vec<String> = index
+            .columns
+            .iter()
+            .map(|column| match column {
+                IndexColumn::Column(name) => name.clone(),
+                IndexColumn::Expression(expression) => expression.clone(),
+            })
+            .collect();
+        Ok(vec![format!(
+            "CREATE {}INDEX {}{} ON {} ({})",
+            if index.unique { "UNIQUE " } else { "" },
+            if index.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            qualified_name(&index.schema, &index.name),
+            qualified_name(&index.schema, &index.table),
+            columns.join(", ")
+        )])
+    }
+
+    fn render_drop_index(drop: &DropIndexDDLWrapper) -> RdbcResult<Vec<String>> {
+        Ok(vec![format!(
+            "DROP INDEX {}{}",
+            if drop.if_exists { "IF EXISTS " } else { "" },
+            qualified_name(&drop.schema, &drop.name)
+        )])
+    }
+
+    fn render_drop_view(drop: &DropViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        if drop.materialized {
+            return Err(RdbcError::Unsupported(format!(
+                "materialized view {}",
+                drop.name
+            )));
+        }
+        Ok(vec![format!(
+            "DROP VIEW {}{}{}",
+            if drop.if_exists { "IF EXISTS " } else { "" },
+            qualified_name(&drop.schema, &drop.name),
+            if drop.cascade {
+                " CASCADE CONSTRAINTS"
+            } else {
+                ""
+            }
+        )])
+    }
+}
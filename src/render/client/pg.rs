@@ -1,54 +1,80 @@
-use crate::render::client::util::extract_map_params;
-use crate::render::render::RdbcSQLRender;
+use crate::render::client::util::{
+    check_full_table, check_next_value, convert_script_to_sql, extract_map_params, render_raw_sql,
+};
+use crate::render::render::{quote_literal, RdbcDmlDialect, RdbcSQLRender};
 use crate::{
     CompareColumn, CompareKind, CompareLikeKind, ConditionColumn, ConditionKind, DmlColumn,
-    FuncColumn, JoinTable, JoinType, OrderType, QueryColumn, QueryTable, RdbcColumn,
-    RdbcColumnValue, RdbcCondition, RdbcDeleteWrapper, RdbcFunc, RdbcInsertWrapper, RdbcOrder,
-    RdbcQueryWrapper, RdbcTable, RdbcUpdateWrapper, RdbcValue, SQLTable, SchemaTable, TableColumn,
-    UnionTable, UnionType, ValueColumn,
+    FuncColumn, JoinTable, JoinType, OrderType, QueryColumn, QueryTable, RawColumn, RawTable,
+    RdbcColumn, RdbcColumnValue, RdbcCondition, RdbcDeleteWrapper, RdbcFunc, RdbcInsertWrapper,
+    RdbcOrder, RdbcQueryWrapper, RdbcResult, RdbcTable, RdbcUpdateWrapper, RdbcValue, SQLTable,
+    SchemaTable, TableColumn, UnionTable, UnionType, ValueColumn,
 };
 
 use serde_json;
 use std::cmp::PartialEq;
 use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// DML renderer building `#{}` scripts, spelled for the dialect `D`.
+pub struct ScriptSQLRender<D> {
+    dialect: PhantomData<D>,
+}
+
+pub struct PgDialect;
 
-pub struct PgSQLRender {}
+impl RdbcDmlDialect for PgDialect {
+    fn placeholder(index: usize) -> String {
+        format!("${}", index)
+    }
+
+    fn text_param(param: &str) -> String {
+        format!("{}::text", param)
+    }
 
-impl RdbcSQLRender for PgSQLRender {
-    fn render_query(sql_wrapper: &RdbcQueryWrapper) -> (String, Vec<RdbcValue>) {
+    fn next_value(sequence: &str) -> String {
+        format!("nextval({})", quote_literal(sequence))
+    }
+}
+
+pub type PgSQLRender = ScriptSQLRender<PgDialect>;
+
+impl<D: RdbcDmlDialect> RdbcSQLRender for ScriptSQLRender<D> {
+    fn render_query(sql_wrapper: &RdbcQueryWrapper) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        Self::check_query_next_value(sql_wrapper)?;
         let (sql, params_map) = Self::render_query_script(sql_wrapper);
         Self::convert_script_to_sql(sql, params_map)
     }
-    fn render_update(sql_wrapper: &RdbcUpdateWrapper) -> (String, Vec<RdbcValue>) {
-        let (sql, params_map) = Self::render_update_script(sql_wrapper);
+    fn render_update(sql_wrapper: &RdbcUpdateWrapper) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        let (sql, params_map) = Self::render_update_script(sql_wrapper)?;
         Self::convert_script_to_sql(sql, params_map)
     }
-    fn render_insert(sql_wrapper: &RdbcInsertWrapper) -> (String, Vec<RdbcValue>) {
+    fn render_insert(sql_wrapper: &RdbcInsertWrapper) -> RdbcResult<(String, Vec<RdbcValue>)> {
         let (sql, params_map) = Self::render_insert_script(sql_wrapper);
         Self::convert_script_to_sql(sql, params_map)
     }
-    fn render_delete(sql_wrapper: &RdbcDeleteWrapper) -> (String, Vec<RdbcValue>) {
-        let (sql, params_map) = Self::render_delete_script(sql_wrapper);
+    fn render_delete(sql_wrapper: &RdbcDeleteWrapper) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        let (sql, params_map) = Self::render_delete_script(sql_wrapper)?;
         Self::convert_script_to_sql(sql, params_map)
     }
     fn render_query_with_params(
         sql_wrapper: &RdbcQueryWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        Self::check_query_next_value(sql_wrapper)?;
         let (sql, params_map) = Self::render_query_script_with_params(sql_wrapper, params);
         Self::convert_script_to_sql(sql, params_map)
     }
     fn render_update_with_prams(
         sql_wrapper: &RdbcUpdateWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        let (sql, params_map) = Self::render_update_script_with_params(sql_wrapper, params);
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        let (sql, params_map) = Self::render_update_script_with_params(sql_wrapper, params)?;
         Self::convert_script_to_sql(sql, params_map)
     }
     fn render_insert_with_params(
         sql_wrapper: &RdbcInsertWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
         let (sql, params_map) = Self::render_insert_script_with_params(sql_wrapper, params);
         Self::convert_script_to_sql(sql, params_map)
     }
@@ -56,8 +82,8 @@ impl RdbcSQLRender for PgSQLRender {
     fn render_delete_with_params(
         sql_wrapper: &RdbcDeleteWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        let (sql, params_map) = Self::render_delete_script_with_params(sql_wrapper, params);
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        let (sql, params_map) = Self::render_delete_script_with_params(sql_wrapper, params)?;
         Self::convert_script_to_sql(sql, params_map)
     }
 
@@ -67,7 +93,7 @@ impl RdbcSQLRender for PgSQLRender {
 
     fn render_update_script(
         sql_wrapper: &RdbcUpdateWrapper,
-    ) -> (String, HashMap<String, RdbcValue>) {
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
         Self::render_update_script_with_params(sql_wrapper, &HashMap::new())
     }
 
@@ -79,7 +105,7 @@ impl RdbcSQLRender for PgSQLRender {
 
     fn render_delete_script(
         sql_wrapper: &RdbcDeleteWrapper,
-    ) -> (String, HashMap<String, RdbcValue>) {
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
         Self::render_delete_script_with_params(sql_wrapper, &HashMap::new())
     }
     fn render_query_script_with_params(
@@ -88,6 +114,7 @@ impl RdbcSQLRender for PgSQLRender {
     ) -> (String, HashMap<String, RdbcValue>) {
         let mut query_vec = vec![];
         let mut map_params = extract_map_params(params);
+        map_params.extend(extract_map_params(&sql_wrapper.params));
 
         // select
         let (select, select_params) =
@@ -158,9 +185,17 @@ impl RdbcSQLRender for PgSQLRender {
     fn render_update_script_with_params(
         sql_wrapper: &RdbcUpdateWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>) {
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
+        if !D::SEQUENCES {
+            check_next_value(
+                "UPDATE",
+                &sql_wrapper.column_dml,
+                &[sql_wrapper.where_condition.as_ref()],
+            )?;
+        }
         let mut update_vec = vec![];
         let mut map_params = extract_map_params(params);
+        map_params.extend(extract_map_params(&sql_wrapper.params));
 
         let (table, table_params) = Self::render_table_slice(sql_wrapper.from_table.as_slice());
         if !table.is_empty() {
@@ -175,12 +210,13 @@ impl RdbcSQLRender for PgSQLRender {
         }
         let (where_condition, where_params) =
             Self::render_where_condition(sql_wrapper.where_condition.as_ref());
-        if !where_condition.is_empty() {
+        check_full_table("UPDATE", &where_condition, sql_wrapper.allow_full_table)?;
+        if !where_condition.trim().is_empty() {
             update_vec.push(format!("WHERE {}", where_condition));
             map_params.extend(where_params);
         }
 
-        (update_vec.join("\n"), map_params)
+        Ok((update_vec.join("\n"), map_params))
     }
     fn render_insert_script_with_params(
         sql_wrapper: &RdbcInsertWrapper,
@@ -213,19 +249,29 @@ impl RdbcSQLRender for PgSQLRender {
                 RdbcColumnValue::StaticValue(v) => {
                     let column_id = uuid::Uuid::new_v4().to_string();
                     insert_columns.push(column_name);
-                    insert_values.push(format!("#{{{}}})", column_id));
+                    insert_values.push(format!("#{{{}}}", column_id));
                     map_params.insert(column_id, v.clone());
                 }
                 RdbcColumnValue::ScriptValue(s) => {
                     insert_columns.push(column_name);
                     insert_values.push(s.clone());
                 }
+                RdbcColumnValue::RawValue(raw) => {
+                    let (raw_sql, raw_params) = render_raw_sql(raw);
+                    insert_columns.push(column_name);
+                    insert_values.push(raw_sql);
+                    map_params.extend(raw_params);
+                }
                 RdbcColumnValue::NullValue => {
                     let column_id = uuid::Uuid::new_v4().to_string();
                     insert_columns.push(column_name);
-                    insert_values.push(format!("#{{{}}})", column_id));
+                    insert_values.push(format!("#{{{}}}", column_id));
                     map_params.insert(column_id, RdbcValue::Null);
                 }
+                RdbcColumnValue::NextVal(sequence) => {
+                    insert_columns.push(column_name);
+                    insert_values.push(Self::render_next_value(sequence));
+                }
             }
         }
 
@@ -243,17 +289,25 @@ impl RdbcSQLRender for PgSQLRender {
                 }
                 RdbcColumnValue::StaticValue(v) => {
                     let column_id = uuid::Uuid::new_v4().to_string();
-                    insert_values.push(format!("#{{{}}})", column_id));
+                    insert_values.push(format!("#{{{}}}", column_id));
                     map_params.insert(column_id, v.clone());
                 }
                 RdbcColumnValue::ScriptValue(s) => {
                     insert_values.push(s.clone());
                 }
+                RdbcColumnValue::RawValue(raw) => {
+                    let (raw_sql, raw_params) = render_raw_sql(raw);
+                    insert_values.push(raw_sql);
+                    map_params.extend(raw_params);
+                }
                 RdbcColumnValue::NullValue => {
                     let column_id = uuid::Uuid::new_v4().to_string();
-                    insert_values.push(format!("#{{{}}})", column_id));
+                    insert_values.push(format!("#{{{}}}", column_id));
                     map_params.insert(column_id, RdbcValue::Null);
                 }
+                RdbcColumnValue::NextVal(sequence) => {
+                    insert_values.push(Self::render_next_value(sequence));
+                }
             }
         }
 
@@ -267,15 +321,20 @@ impl RdbcSQLRender for PgSQLRender {
         if let Some(query) = sql_wrapper.column_query.as_ref() {
             let (query_sql, query_params) = Self::render_query_table(query);
             insert_sql = format!("{} {}", insert_sql, query_sql);
+            map_params.extend(query_params);
         }
         (insert_sql, map_params)
     }
     fn render_delete_script_with_params(
         sql_wrapper: &RdbcDeleteWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>) {
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
+        if !D::SEQUENCES {
+            check_next_value("DELETE", &[], &[sql_wrapper.where_condition.as_ref()])?;
+        }
         let mut delete_vec = vec![];
         let mut map_params = extract_map_params(params);
+        map_params.extend(extract_map_params(&sql_wrapper.params));
         let (table_sql, table_params) = Self::render_table_slice(sql_wrapper.from_table.as_slice());
         if !table_sql.is_empty() {
             delete_vec.push(format!("DELETE FROM {}", table_sql));
@@ -283,11 +342,16 @@ impl RdbcSQLRender for PgSQLRender {
         }
         let (where_condition, where_params) =
             Self::render_where_condition(sql_wrapper.where_condition.as_ref());
-        if !where_condition.is_empty() {
+        check_full_table("DELETE", &where_condition, sql_wrapper.allow_full_table)?;
+        if !where_condition.trim().is_empty() {
             delete_vec.push(format!(" WHERE {}", where_condition));
             map_params.extend(where_params);
         }
-        (delete_vec.join("\n"), map_params)
+        Ok((delete_vec.join("\n"), map_params))
+    }
+
+    fn render_next_value(sequence: &str) -> String {
+        D::next_value(sequence)
     }
 }
 
@@ -297,22 +361,26 @@ impl PartialEq for CompareKind {
     }
 }
 
-impl PgSQLRender {
+impl<D: RdbcDmlDialect> ScriptSQLRender<D> {
+    fn check_query_next_value(sql_wrapper: &RdbcQueryWrapper) -> RdbcResult<()> {
+        if D::SEQUENCES {
+            return Ok(());
+        }
+        check_next_value(
+            "SELECT",
+            &[],
+            &[
+                sql_wrapper.where_condition.as_ref(),
+                sql_wrapper.having_condition.as_ref(),
+            ],
+        )
+    }
+
     fn convert_script_to_sql(
-        mut sql: String,
+        sql: String,
         params_map: HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>) {
-        let mut params_vec = vec![];
-        for (k, v) in params_map.iter() {
-            if sql.contains(k) {
-                sql = sql.replace(
-                    format!("#{{{}}}", k).as_str(),
-                    format!("${}", params_vec.len() + 1).as_str(),
-                );
-                params_vec.push(v.clone());
-            }
-        }
-        (sql, params_vec)
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        convert_script_to_sql(sql.as_str(), &params_map, D::placeholder)
     }
     fn render_select_columns(
         select_columns: &[RdbcColumn],
@@ -345,6 +413,7 @@ impl PgSQLRender {
             RdbcTable::SchemaTable(c) => Self::render_schema_table(c),
             RdbcTable::SQLTable(c) => Self::render_sql_table(c),
             RdbcTable::QueryTable(c) => Self::render_query_table(c),
+            RdbcTable::RawTable(c) => Self::render_raw_table(c),
         }
     }
     fn render_join_table(table_slice: &[JoinTable]) -> (String, HashMap<String, RdbcValue>) {
@@ -388,8 +457,18 @@ impl PgSQLRender {
                 ConditionColumn::Compare(c) => Self::render_compare_column(c),
                 ConditionColumn::SubCondition(sc) => {
                     let (temp_sql, temp_params) = Self::render_where_condition(Some(sc));
+                    if temp_sql.trim().is_empty() {
+                        continue;
+                    }
                     (format!("({})", temp_sql), temp_params)
                 }
+                ConditionColumn::Raw(raw) => {
+                    let (raw_sql, raw_params) = render_raw_sql(raw);
+                    if raw_sql.trim().is_empty() {
+                        continue;
+                    }
+                    (format!("({})", raw_sql), raw_params)
+                }
             };
             if !column_sql.is_empty() {
                 condition_vec.push(column_sql);
@@ -415,20 +494,16 @@ impl PgSQLRender {
             RdbcColumnValue::StaticValue(v) => match &column.kind {
                 CompareKind::Like(like) | CompareKind::NotLike(like) => {
                     let value_id = uuid::Uuid::new_v4().to_string();
+                    let pattern = D::text_param(&format!("#{{{}}}", value_id));
                     match like {
                         CompareLikeKind::Left => {
-                            column_sql =
-                                format!("{} LIKE CONCAT(#{{{}}}::text,'%')", column_sql, value_id);
+                            column_sql = format!("{} LIKE CONCAT({},'%')", column_sql, pattern);
                         }
                         CompareLikeKind::Right => {
-                            column_sql =
-                                format!("{} LIKE CONCAT('%',#{{{}}}::text)", column_sql, value_id);
+                            column_sql = format!("{} LIKE CONCAT('%',{})", column_sql, pattern);
                         }
                         CompareLikeKind::Both => {
-                            column_sql = format!(
-                                "{} LIKE CONCAT('%',#{{{}}}::text,'%')",
-                                column_sql, value_id
-                            );
+                            column_sql = format!("{} LIKE CONCAT('%',{},'%')", column_sql, pattern);
                         }
                     }
                     params.insert(value_id, v.clone());
@@ -465,6 +540,16 @@ impl PgSQLRender {
                         )
                     }
                 }
+                CompareKind::In | CompareKind::NotIn => {
+                    let value_id = uuid::Uuid::new_v4().to_string();
+                    column_sql = format!(
+                        "{} {} (#{{{}}})",
+                        column_sql,
+                        column.kind.compare(),
+                        value_id
+                    );
+                    params.insert(value_id, v.clone());
+                }
                 _ => {
                     let value_id = uuid::Uuid::new_v4().to_string();
                     column_sql =
@@ -472,9 +557,27 @@ impl PgSQLRender {
                     params.insert(value_id, v.clone());
                 }
             },
-            RdbcColumnValue::ScriptValue(v) => {
-                column_sql = format!("{} {} {} ", column_sql, column.kind.compare(), v);
+            RdbcColumnValue::RawValue(raw) => {
+                let (raw_sql, raw_params) = render_raw_sql(raw);
+                column_sql = match &column.kind {
+                    CompareKind::In
+                    | CompareKind::NotIn
+                    | CompareKind::Exists
+                    | CompareKind::NotExists => {
+                        format!("{} {} ({})", column_sql, column.kind.compare(), raw_sql)
+                    }
+                    _ => format!("{} {} {}", column_sql, column.kind.compare(), raw_sql),
+                };
+                params.extend(raw_params);
             }
+            RdbcColumnValue::ScriptValue(v) => match &column.kind {
+                CompareKind::In | CompareKind::NotIn if !v.trim_start().starts_with('(') => {
+                    column_sql = format!("{} {} ({})", column_sql, column.kind.compare(), v);
+                }
+                _ => {
+                    column_sql = format!("{} {} {} ", column_sql, column.kind.compare(), v);
+                }
+            },
             RdbcColumnValue::NullValue => {
                 if column.ignore_null {
                     column_sql = format!("{} {} NULL", column_sql, column.kind.compare());
@@ -482,6 +585,14 @@ impl PgSQLRender {
                     column_sql = "".to_string();
                 }
             }
+            RdbcColumnValue::NextVal(sequence) => {
+                column_sql = format!(
+                    "{} {} {}",
+                    column_sql,
+                    column.kind.compare(),
+                    Self::render_next_value(sequence)
+                );
+            }
         };
         (column_sql, params)
     }
@@ -491,6 +602,7 @@ impl PgSQLRender {
             RdbcColumn::QueryColumn(c) => Self::render_query_column_with_alias(c, true),
             RdbcColumn::FuncColumn(c) => Self::render_func_column_with_alias(c, true),
             RdbcColumn::ValueColumn(c) => Self::render_value_column_with_alias(c, true),
+            RdbcColumn::RawColumn(c) => Self::render_raw_column_with_alias(c, true),
         }
     }
     fn render_column_for_compare(column: &RdbcColumn) -> (String, HashMap<String, RdbcValue>) {
@@ -499,6 +611,7 @@ impl PgSQLRender {
             RdbcColumn::QueryColumn(c) => Self::render_query_column_with_alias(c, false),
             RdbcColumn::FuncColumn(c) => Self::render_func_column_with_alias(c, false),
             RdbcColumn::ValueColumn(c) => Self::render_value_column_with_alias(c, false),
+            RdbcColumn::RawColumn(c) => Self::render_raw_column_with_alias(c, false),
         }
     }
 
@@ -579,6 +692,17 @@ impl PgSQLRender {
         return (column_sql.to_string(), HashMap::new());
     }
 
+    fn render_raw_column_with_alias(
+        column: &RawColumn,
+        has_alias: bool,
+    ) -> (String, HashMap<String, RdbcValue>) {
+        let (mut column_sql, params_map) = render_raw_sql(&column.raw);
+        if !column.column_alias.is_empty() && has_alias {
+            column_sql = format!("{} AS {}", column_sql, column.column_alias);
+        }
+        (column_sql, params_map)
+    }
+
     fn render_rdbc_value(value: &RdbcValue) -> String {
         match value {
             RdbcValue::Char(c) => {
@@ -710,6 +834,14 @@ impl PgSQLRender {
         return (table, HashMap::new());
     }
 
+    fn render_raw_table(raw_table: &RawTable) -> (String, HashMap<String, RdbcValue>) {
+        let (mut table, params_map) = render_raw_sql(&raw_table.raw);
+        if !raw_table.table_alias.is_empty() {
+            table = format!("{} AS {}", table, raw_table.table_alias);
+        }
+        (table, params_map)
+    }
+
     fn render_query_table(query_table: &QueryTable) -> (String, HashMap<String, RdbcValue>) {
         let (query_sql, query_params) = Self::render_query_script(&query_table.query);
         let mut table = format!("({})", query_sql);
@@ -746,9 +878,17 @@ impl PgSQLRender {
             RdbcColumnValue::ScriptValue(sc) => {
                 column_sql = format!("{}={}", column_sql, sc);
             }
+            RdbcColumnValue::RawValue(raw) => {
+                let (raw_sql, raw_params) = render_raw_sql(raw);
+                column_sql = format!("{}={}", column_sql, raw_sql);
+                column_params.extend(raw_params);
+            }
             RdbcColumnValue::NullValue => {
                 column_sql = format!("{} = NULL", column_sql);
             }
+            RdbcColumnValue::NextVal(sequence) => {
+                column_sql = format!("{}={}", column_sql, Self::render_next_value(sequence));
+            }
         }
         (column_sql, column_params)
     }
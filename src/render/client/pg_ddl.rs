@@ -0,0 +1,169 @@
+use crate::render::client::PgSQLRender;
+use crate::render::render::{
+    ansi_literal, hex_string, qualified_name, render_view_columns, RdbcDDLRender,
+};
+use crate::{
+    AlterColumn, DropIndexDDLWrapper, DropViewDDLWrapper, IndexDDLWrapper, RdbcColumnType,
+    RdbcError, RdbcResult, RdbcValue, RefreshViewDDLWrapper, ViewDDLWrapper,
+};
+
+impl RdbcDDLRender for PgSQLRender {
+    fn render_alter_column(table_name: &str, alter: &AlterColumn) -> RdbcResult<Vec<String>> {
+        let column = &alter.column;
+        let prefix = format!("ALTER TABLE {} ALTER COLUMN {}", table_name, column.name);
+        let mut statements = vec![];
+        if alter.change_type {
+            statements.push(format!(
+                "{} TYPE {}",
+                prefix,
+                Self::render_column_type(&column.column_type)
+            ));
+        }
+        if alter.change_nullable {
+            let action = if column.nullable { "DROP" } else { "SET" };
+            statements.push(format!("{} {} NOT NULL", prefix, action));
+        }
+        if alter.change_default {
+            statements.push(match column.default_value.as_ref() {
+                Some(default_value) => {
+                    format!(
+                        "{} SET DEFAULT {}",
+                        prefix,
+                        Self::render_default(default_value)
+                    )
+                }
+                None => format!("{} DROP DEFAULT", prefix),
+            });
+        }
+        if alter.change_unique {
+            // The name PostgreSQL gives an inline UNIQUE constraint.
+            let table = table_name.rsplit('.').next().unwrap_or(table_name);
+            let constraint = format!("{}_{}_key", table, column.name);
+            statements.push(if column.unique {
+                format!(
+                    "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({})",
+                    table_name, constraint, column.name
+                )
+            } else {
+                format!("ALTER TABLE {} DROP CONSTRAINT {}", table_name, constraint)
+            });
+        }
+        Ok(statements)
+    }
+
+    fn render_column_type(column_type: &RdbcColumnType) -> String {
+        match column_type {
+            RdbcColumnType::Char(len) => format!("CHAR({})", len),
+            RdbcColumnType::Varchar(len) => format!("VARCHAR({})", len),
+            RdbcColumnType::Text | RdbcColumnType::LongText => "TEXT".to_string(),
+            RdbcColumnType::SmallInt => "SMALLINT".to_string(),
+            RdbcColumnType::Int => "INTEGER".to_string(),
+            RdbcColumnType::BigInt => "BIGINT".to_string(),
+            RdbcColumnType::Float => "REAL".to_string(),
+            RdbcColumnType::Double => "DOUBLE PRECISION".to_string(),
+            RdbcColumnType::Decimal(precision, scale) => {
+                format!("NUMERIC({},{})", precision, scale)
+            }
+            RdbcColumnType::Boolean => "BOOLEAN".to_string(),
+            RdbcColumnType::Date => "DATE".to_string(),
+            RdbcColumnType::DateTime => "TIMESTAMP".to_string(),
+            RdbcColumnType::Time => "TIME".to_string(),
+            RdbcColumnType::Timestamp => "TIMESTAMPTZ".to_string(),
+            RdbcColumnType::Bytes => "BYTEA".to_string(),
+            RdbcColumnType::Json => "JSONB".to_string(),
+            RdbcColumnType::Uuid => "UUID".to_string(),
+            RdbcColumnType::Custom(name) => name.clone(),
+        }
+    }
+
+    fn render_literal(value: &RdbcValue) -> String {
+        match value {
+            RdbcValue::Bytes(v) => format!("'\\x{}'::bytea", hex_string(v)),
+            _ => ansi_literal(value),
+        }
+    }
+
+    fn render_create_index(index: &IndexDDLWrapper) -> RdbcResult<Vec<String>> {
+        let mut sql = format!(
+            "CREATE {}INDEX {}{}{} ON {} ({})",
+            if index.unique { "UNIQUE " } else { "" },
+            if index.concurrently {
+                "CONCURRENTLY "
+            } else {
+                ""
+            },
+            if index.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            index.name,
+            qualified_name(&index.schema, &index.table),
+            Self::render_index_columns(index)
+        );
+        if let Some(condition) = index.where_condition.as_ref() {
+            sql.push_str(&format!(" WHERE {}", condition));
+        }
+        Ok(vec![sql])
+    }
+
+    fn render_drop_index(drop: &DropIndexDDLWrapper) -> RdbcResult<Vec<String>> {
+        Ok(vec![format!(
+            "DROP INDEX {}{}{}",
+            if drop.concurrently {
+                "CONCURRENTLY "
+            } else {
+                ""
+            },
+            if drop.if_exists { "IF EXISTS " } else { "" },
+            qualified_name(&drop.schema, &drop.name)
+        )])
+    }
+
+    fn render_create_view(view: &ViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        if view.materialized && view.or_replace {
+            return Err(RdbcError::Unsupported(format!(
+                "PostgreSQL cannot replace materialized view {}",
+                view.name
+            )));
+        }
+        let kind = match (view.or_replace, view.materialized) {
+            (true, _) => "OR REPLACE VIEW",
+            (false, true) => "MATERIALIZED VIEW",
+            (false, false) => "VIEW",
+        };
+        Ok(vec![format!(
+            "CREATE {} {}{} AS {}",
+            kind,
+            qualified_name(&view.schema, &view.name),
+            render_view_columns(view),
+            Self::render_view_query(&view.query)?
+        )])
+    }
+
+    fn render_drop_view(drop: &DropViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        Ok(vec![format!(
+            "DROP {}VIEW {}{}{}",
+            if drop.materialized {
+                "MATERIALIZED "
+            } else {
+                ""
+            },
+            if drop.if_exists { "IF EXISTS " } else { "" },
+            qualified_name(&drop.schema, &drop.name),
+            if drop.cascade { " CASCADE" } else { "" }
+        )])
+    }
+
+    fn render_refresh_view(refresh: &RefreshViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        Ok(vec![format!(
+            "REFRESH MATERIALIZED VIEW {}{}",
+            if refresh.concurrently {
+                "CONCURRENTLY "
+            } else {
+                ""
+            },
+            qualified_name(&refresh.schema, &refresh.name)
+        )])
+    }
+}
@@ -0,0 +1,231 @@
+use crate::render::client::SqliteSQLRender;
+use crate::render::render::{
+    ansi_literal, qualified_name, quote_literal, render_view_columns, RdbcDDLRender,
+};
+use crate::{
+    AlterColumn, AlterSequenceDDLWrapper, ColumnDDLWrapper, ColumnIdentity, CommentDDLWrapper,
+    DropIndexDDLWrapper, DropSequenceDDLWrapper, DropTableDDLWrapper, DropViewDDLWrapper,
+    IndexDDLWrapper, RdbcColumnType, RdbcError, RdbcResult, RdbcValue, SequenceDDLWrapper,
+    TableConstraint, ViewDDLWrapper,
+};
+
+impl RdbcDDLRender for SqliteSQLRender {
+    fn render_alter_column(table_name: &str, alter: &AlterColumn) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "SQLite cannot alter column {}.{}, the table has to be rebuilt",
+            table_name, alter.column.name
+        )))
+    }
+
+    fn render_add_column(table_name: &str, column: &ColumnDDLWrapper) -> RdbcResult<String> {
+        if column.primary_key || column.identity.is_some() {
+            return Err(RdbcError::Unsupported(format!(
+                "SQLite cannot add primary key column {}.{}",
+                table_name, column.name
+            )));
+        }
+        Ok(format!(
+            "ALTER TABLE {} ADD COLUMN {}",
+            table_name,
+            Self::render_column(column)?
+        ))
+    }
+
+    fn render_column_spec(column: &ColumnDDLWrapper) -> RdbcResult<String> {
+        if let Some(identity) = column.identity.as_ref() {
+            if !column.primary_key {
+                return Err(RdbcError::Unsupported(format!(
+                    "SQLite numbers only an INTEGER PRIMARY KEY, column {}",
+                    column.name
+                )));
+            }
+            // AUTOINCREMENT requires the exact type name INTEGER.
+            return Ok(format!(
+                "{} INTEGER{}",
+                column.name,
+                Self::render_identity(identity)?
+            ));
+        }
+        let mut spec = format!(
+            "{} {}",
+            column.name,
+            Self::render_column_type(&column.column_type)
+        );
+        if let Some(default_value) = column.default_value.as_ref() {
+            spec.push_str(" DEFAULT ");
+            spec.push_str(&Self::render_default(default_value));
+        }
+        if !column.nullable {
+            spec.push_str(" NOT NULL");
+        }
+        Ok(spec)
+    }
+
+    fn render_identity(identity: &ColumnIdentity) -> RdbcResult<String> {
+        if identity.start.is_some() || identity.increment.is_some() || identity.always {
+            return Err(RdbcError::Unsupported(
+                "SQLite AUTOINCREMENT takes no start, increment or GENERATED ALWAYS".to_string(),
+            ));
+        }
+        Ok(" PRIMARY KEY AUTOINCREMENT".to_string())
+    }
+
+    fn inline_primary_key(column: &ColumnDDLWrapper) -> bool {
+        column.identity.is_some()
+    }
+
+    fn render_create_sequence(sequence: &SequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "SQLite has no sequence {}, use an INTEGER PRIMARY KEY AUTOINCREMENT column",
+            sequence.name
+        )))
+    }
+
+    fn render_alter_sequence(alter: &AlterSequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "SQLite has no sequence {}",
+            alter.name
+        )))
+    }
+
+    fn render_drop_sequence(drop: &DropSequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "SQLite has no sequence {}",
+            drop.name
+        )))
+    }
+
+    fn render_add_constraint(
+        table_name: &str,
+        _constraint: &TableConstraint,
+    ) -> RdbcResult<String> {
+        Err(RdbcError::Unsupported(format!(
+            "SQLite cannot add a constraint to existing table {}",
+            table_name
+        )))
+    }
+
+    fn render_drop_constraint(table_name: &str, name: &str) -> RdbcResult<String> {
+        Err(RdbcError::Unsupported(format!(
+            "SQLite cannot drop constraint {} of table {}",
+            name, table_name
+        )))
+    }
+
+    fn render_drop_table(drop: &DropTableDDLWrapper) -> RdbcResult<Vec<String>> {
+        let if_exists = if drop.if_exists { "IF EXISTS " } else { "" };
+        Ok(vec![format!(
+            "DROP TABLE {}{}",
+            if_exists,
+            qualified_name(&drop.schema, &drop.name)
+        )])
+    }
+
+    fn render_column_type(column_type: &RdbcColumnType) -> String {
+        match column_type {
+            RdbcColumnType::Char(len) => format!("CHAR({})", len),
+            RdbcColumnType::Varchar(len) => format!("VARCHAR({})", len),
+            RdbcColumnType::Text | RdbcColumnType::LongText => "TEXT".to_string(),
+            RdbcColumnType::SmallInt => "SMALLINT".to_string(),
+            RdbcColumnType::Int => "INTEGER".to_string(),
+            RdbcColumnType::BigInt => "BIGINT".to_string(),
+            RdbcColumnType::Float => "REAL".to_string(),
+            RdbcColumnType::Double => "DOUBLE".to_string(),
+            RdbcColumnType::Decimal(precision, scale) => {
+                format!("DECIMAL({},{})", precision, scale)
+            }
+            RdbcColumnType::Boolean => "BOOLEAN".to_string(),
+            RdbcColumnType::Date => "DATE".to_string(),
+            RdbcColumnType::DateTime => "DATETIME".to_string(),
+            RdbcColumnType::Time => "TIME".to_string(),
+            RdbcColumnType::Timestamp => "TIMESTAMP".to_string(),
+            RdbcColumnType::Bytes => "BLOB".to_string(),
+            RdbcColumnType::Json => "JSON".to_string(),
+            RdbcColumnType::Uuid => "UUID".to_string(),
+            RdbcColumnType::Custom(name) => name.clone(),
+        }
+    }
+
+    fn render_literal(value: &RdbcValue) -> String {
+        match value {
+            RdbcValue::Date(_) | RdbcValue::DateTime(_) | RdbcValue::Time(_) => {
+                quote_literal(&value.to_string())
+            }
+            RdbcValue::Boolean(v) => if *v { "1" } else { "0" }.to_string(),
+            _ => ansi_literal(value),
+        }
+    }
+
+    fn render_create_index(index: &IndexDDLWrapper) -> RdbcResult<Vec<String>> {
+        let mut sql = format!(
+            "CREATE {}INDEX {}{} ON {} ({})",
+            if index.unique { "UNIQUE " } else { "" },
+            if index.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            qualified_name(&index.schema, &index.name),
+            index.table,
+            Self::render_index_columns(index)
+        );
+        if let Some(condition) = index.where_condition.as_ref() {
+            sql.push_str(&format!(" WHERE {}", condition));
+        }
+        Ok(vec![sql])
+    }
+
+    fn render_drop_index(drop: &DropIndexDDLWrapper) -> RdbcResult<Vec<String>> {
+        Ok(vec![format!(
+            "DROP INDEX {}{}",
+            if drop.if_exists { "IF EXISTS " } else { "" },
+            qualified_name(&drop.schema, &drop.name)
+        )])
+    }
+
+    fn render_comments(
+        _schema: &str,
+        _table: &str,
+        _table_comment: Option<&String>,
+        _columns: &[ColumnDDLWrapper],
+    ) -> RdbcResult<Vec<String>> {
+        // SQLite keeps no comments, they only document the model.
+        Ok(vec![])
+    }
+
+    fn render_comment(comment: &CommentDDLWrapper) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "SQLite has no comment on {}",
+            comment.table
+        )))
+    }
+
+    fn render_create_view(view: &ViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        if view.or_replace || view.materialized {
+            return Err(RdbcError::Unsupported(format!(
+                "SQLite has neither CREATE OR REPLACE VIEW nor materialized views, view {}",
+                view.name
+            )));
+        }
+        Ok(vec![format!(
+            "CREATE VIEW {}{} AS {}",
+            qualified_name(&view.schema, &view.name),
+            render_view_columns(view),
+            Self::render_view_query(&view.query)?
+        )])
+    }
+
+    fn render_drop_view(drop: &DropViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        if drop.materialized {
+            return Err(RdbcError::Unsupported(format!(
+                "SQLite has no materialized view {}",
+                drop.name
+            )));
+        }
+        Ok(vec![format!(
+            "DROP VIEW {}{}",
+            if drop.if_exists { "IF EXISTS " } else { "" },
+            qualified_name(&drop.schema, &drop.name)
+        )])
+    }
+}
@@ -1,4 +1,7 @@
-use crate::RdbcValue;
+use crate::{
+    ConditionColumn, DmlColumn, RdbcColumnValue, RdbcCondition, RdbcError, RdbcRawSql, RdbcResult,
+    RdbcValue,
+};
 use std::collections::HashMap;
 
 pub fn extract_map_params(params: &HashMap<String, RdbcValue>) -> HashMap<String, RdbcValue> {
@@ -55,3 +58,185 @@ pub fn extract_vec_params(array: &[RdbcValue]) -> HashMap<String, RdbcValue> {
     }
     params_new
 }
+
+/// Rejects UPDATE/DELETE statements whose WHERE clause rendered empty,
+/// unless the wrapper opted in with `allow_full_table()`.
+pub fn check_full_table(
+    statement: &str,
+    where_sql: &str,
+    allow_full_table: bool,
+) -> RdbcResult<()> {
+    if where_sql.trim().is_empty() && !allow_full_table {
+        return Err(RdbcError::FullTableStatement(statement.to_string()));
+    }
+    Ok(())
+}
+
+/// Rejects a sequence value outside an INSERT for dialects without sequences,
+/// where it would otherwise render as `NULL`.
+pub fn check_next_value(
+    statement: &str,
+    set_columns: &[DmlColumn],
+    conditions: &[Option<&RdbcCondition>],
+) -> RdbcResult<()> {
+    let in_set = set_columns
+        .iter()
+        .any(|column| matches!(column.value, RdbcColumnValue::NextVal(_)));
+    if in_set
+        || conditions
+            .iter()
+            .flatten()
+            .any(|c| condition_has_next_value(c))
+    {
+        return Err(RdbcError::Unsupported(format!(
+            "sequence value in {} without database sequences",
+            statement
+        )));
+    }
+    Ok(())
+}
+
+fn condition_has_next_value(condition: &RdbcCondition) -> bool {
+    condition.column.iter().any(|item| match item {
+        ConditionColumn::Compare(c) => matches!(c.value, RdbcColumnValue::NextVal(_)),
+        ConditionColumn::SubCondition(sc) => condition_has_next_value(sc),
+        ConditionColumn::Raw(_) => false,
+    })
+}
+
+/// Replaces every `#{path}` placeholder in `sql` with a positional placeholder
+/// produced by `placeholder` and collects the bound values in order.
+///
+/// Array values referenced inside `IN (...)` are expanded into one placeholder
+/// per element. Unknown paths are reported as [`RdbcError::UnknownParam`].
+pub fn convert_script_to_sql<F>(
+    sql: &str,
+    params: &HashMap<String, RdbcValue>,
+    placeholder: F,
+) -> RdbcResult<(String, Vec<RdbcValue>)>
+where
+    F: Fn(usize) -> String,
+{
+    let mut sql_new = String::with_capacity(sql.len());
+    let mut params_vec = vec![];
+    let mut rest = sql;
+    while let Some(start) = rest.find("#{") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        sql_new.push_str(&rest[..start]);
+        let key = rest[start + 2..end].trim();
+        let value = params
+            .get(key)
+            .ok_or_else(|| RdbcError::UnknownParam(key.to_string()))?;
+        match value {
+            RdbcValue::Array(items) if is_in_list(&sql_new) => {
+                if items.is_empty() {
+                    return Err(RdbcError::InvalidParam(format!(
+                        "empty array bound to IN (#{{{}}})",
+                        key
+                    )));
+                }
+                let mut holders = vec![];
+                for item in items {
+                    params_vec.push(item.clone());
+                    holders.push(placeholder(params_vec.len()));
+                }
+                sql_new.push_str(holders.join(",").as_str());
+            }
+            _ => {
+                params_vec.push(value.clone());
+                sql_new.push_str(placeholder(params_vec.len()).as_str());
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    sql_new.push_str(rest);
+    Ok((sql_new, params_vec))
+}
+
+/// Whether the rendered text ends with an open `IN (` list. `IN` must be a
+/// word of its own, so `MIN(` or `JOIN (` do not count.
+fn is_in_list(sql: &str) -> bool {
+    let head = match sql.trim_end().strip_suffix('(') {
+        Some(head) => head.trim_end(),
+        None => return false,
+    };
+    let upper = head.to_uppercase();
+    match upper.strip_suffix("IN") {
+        Some(before) => !before.ends_with(|c: char| c.is_alphanumeric() || c == '_'),
+        None => false,
+    }
+}
+
+/// Renders a raw fragment to script form, renaming its own `#{key}` params to
+/// unique ids so they cannot collide with params of the enclosing wrapper.
+/// Keys the fragment does not define are kept for the wrapper params.
+pub fn render_raw_sql(raw: &RdbcRawSql) -> (String, HashMap<String, RdbcValue>) {
+    let raw_params = extract_map_params(&raw.params);
+    let mut params = HashMap::new();
+    let mut sql = String::with_capacity(raw.sql.len());
+    let mut rest = raw.sql.as_str();
+    while let Some(start) = rest.find("#{") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        sql.push_str(&rest[..start]);
+        let key = rest[start + 2..end].trim();
+        match raw_params.get(key) {
+            Some(value) => {
+                let value_id = uuid::Uuid::new_v4().to_string();
+                sql.push_str(format!("#{{{}}}", value_id).as_str());
+                params.insert(value_id, value.clone());
+            }
+            None => sql.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    sql.push_str(rest);
+    (sql, params)
+}
+
+/// Replaces every `#{path}` placeholder in `sql` with the literal produced by
+/// `literal`, for statements such as views that cannot take bound params.
+/// Array values inside `IN (...)` are expanded into a literal list.
+pub fn inline_script_params<F>(
+    sql: &str,
+    params: &HashMap<String, RdbcValue>,
+    literal: F,
+) -> RdbcResult<String>
+where
+    F: Fn(&RdbcValue) -> String,
+{
+    let mut sql_new = String::with_capacity(sql.len());
+    let mut rest = sql;
+    while let Some(start) = rest.find("#{") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        sql_new.push_str(&rest[..start]);
+        let key = rest[start + 2..end].trim();
+        let value = params
+            .get(key)
+            .ok_or_else(|| RdbcError::UnknownParam(key.to_string()))?;
+        match value {
+            RdbcValue::Array(items) if is_in_list(&sql_new) => {
+                if items.is_empty() {
+                    return Err(RdbcError::InvalidParam(format!(
+                        "empty array bound to IN (#{{{}}})",
+                        key
+                    )));
+                }
+                let literals: Vec<String> = items.iter().map(&literal).collect();
+                sql_new.push_str(literals.join(",").as_str());
+            }
+            _ => sql_new.push_str(literal(value).as_str()),
+        }
+        rest = &rest[end + 1..];
+    }
+    sql_new.push_str(rest);
+    Ok(sql_new)
+}
@@ -1,12 +1,23 @@
 use crate::db::DataBase;
+use crate::render::client::util::convert_script_to_sql;
 use crate::render::client::{MysqlSQLRender, SqliteSQLRender};
-use crate::render::render::RdbcSQLRender;
-use crate::{RdbcDeleteWrapper, RdbcInsertWrapper, RdbcQueryWrapper, RdbcUpdateWrapper, RdbcValue};
+use crate::render::render::{RdbcDDLRender, RdbcSQLRender};
+use crate::{
+    ColumnDefault, RdbcColumnType, RdbcDdlWrapper, RdbcDeleteWrapper, RdbcInsertWrapper,
+    RdbcQueryWrapper, RdbcRawSql, RdbcResult, RdbcUpdateWrapper, RdbcValue,
+};
+use std::collections::HashMap;
 
 mod client;
 mod render;
 
-pub fn render_query(query: &RdbcQueryWrapper, db_type: DataBase) -> (String, Vec<RdbcValue>) {
+pub(crate) use client::util::extract_map_params;
+pub(crate) use render::qualified_name;
+
+pub fn render_query(
+    query: &RdbcQueryWrapper,
+    db_type: DataBase,
+) -> RdbcResult<(String, Vec<RdbcValue>)> {
     match db_type {
         DataBase::MySql => MysqlSQLRender::render_query(query),
         DataBase::Sqlite => SqliteSQLRender::render_query(query),
@@ -14,7 +25,10 @@ pub fn render_query(query: &RdbcQueryWrapper, db_type: DataBase) -> (String, Vec
         DataBase::Oracle => client::OracleSQLRender::render_query(query),
     }
 }
-pub fn render_insert(insert: &RdbcInsertWrapper, db_type: DataBase) -> (String, Vec<RdbcValue>) {
+pub fn render_insert(
+    insert: &RdbcInsertWrapper,
+    db_type: DataBase,
+) -> RdbcResult<(String, Vec<RdbcValue>)> {
     match db_type {
         DataBase::MySql => MysqlSQLRender::render_insert(insert),
         DataBase::Sqlite => SqliteSQLRender::render_insert(insert),
@@ -22,7 +36,10 @@ pub fn render_insert(insert: &RdbcInsertWrapper, db_type: DataBase) -> (String,
         DataBase::Oracle => client::OracleSQLRender::render_insert(insert),
     }
 }
-pub fn render_update(update: &RdbcUpdateWrapper, db_type: DataBase) -> (String, Vec<RdbcValue>) {
+pub fn render_update(
+    update: &RdbcUpdateWrapper,
+    db_type: DataBase,
+) -> RdbcResult<(String, Vec<RdbcValue>)> {
     match db_type {
         DataBase::MySql => MysqlSQLRender::render_update(update),
         DataBase::Sqlite => SqliteSQLRender::render_update(update),
@@ -31,7 +48,10 @@ pub fn render_update(update: &RdbcUpdateWrapper, db_type: DataBase) -> (String,
     }
 }
 
-pub fn render_delete(delete: &RdbcDeleteWrapper, db_type: DataBase) -> (String, Vec<RdbcValue>) {
+pub fn render_delete(
+    delete: &RdbcDeleteWrapper,
+    db_type: DataBase,
+) -> RdbcResult<(String, Vec<RdbcValue>)> {
     match db_type {
         DataBase::MySql => MysqlSQLRender::render_delete(delete),
         DataBase::Sqlite => SqliteSQLRender::render_delete(delete),
@@ -39,3 +59,85 @@ pub fn render_delete(delete: &RdbcDeleteWrapper, db_type: DataBase) -> (String,
         DataBase::Oracle => client::OracleSQLRender::render_delete(delete),
     }
 }
+
+/// Statement an executor can run: renders to SQL with positional params in
+/// the placeholder style of `db_type`.
+pub trait RdbcStatement {
+    fn render(&self, db_type: DataBase) -> RdbcResult<(String, Vec<RdbcValue>)>;
+}
+
+impl RdbcStatement for RdbcQueryWrapper {
+    fn render(&self, db_type: DataBase) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        render_query(self, db_type)
+    }
+}
+impl RdbcStatement for RdbcInsertWrapper {
+    fn render(&self, db_type: DataBase) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        render_insert(self, db_type)
+    }
+}
+impl RdbcStatement for RdbcUpdateWrapper {
+    fn render(&self, db_type: DataBase) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        render_update(self, db_type)
+    }
+}
+impl RdbcStatement for RdbcDeleteWrapper {
+    fn render(&self, db_type: DataBase) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        render_delete(self, db_type)
+    }
+}
+impl RdbcStatement for RdbcRawSql {
+    fn render(&self, db_type: DataBase) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        render_script(&self.sql, &self.params, db_type)
+    }
+}
+
+/// Renders a DDL statement for `db_type`. Some statements expand to several
+/// SQL statements, which are returned in execution order.
+pub fn render_ddl(ddl: &RdbcDdlWrapper, db_type: DataBase) -> RdbcResult<Vec<String>> {
+    match db_type {
+        DataBase::MySql => MysqlSQLRender::render_ddl(ddl),
+        DataBase::Sqlite => SqliteSQLRender::render_ddl(ddl),
+        DataBase::Postgres => client::PgSQLRender::render_ddl(ddl),
+        DataBase::Oracle => client::OracleSQLRender::render_ddl(ddl),
+    }
+}
+
+/// Column type as `db_type` declares it, used to compare column models.
+pub(crate) fn render_column_type(column_type: &RdbcColumnType, db_type: DataBase) -> String {
+    match db_type {
+        DataBase::MySql => MysqlSQLRender::render_column_type(column_type),
+        DataBase::Sqlite => SqliteSQLRender::render_column_type(column_type),
+        DataBase::Postgres => client::PgSQLRender::render_column_type(column_type),
+        DataBase::Oracle => client::OracleSQLRender::render_column_type(column_type),
+    }
+}
+
+/// Column default as `db_type` renders it, used to compare column models.
+pub(crate) fn render_column_default(default_value: &ColumnDefault, db_type: DataBase) -> String {
+    match db_type {
+        DataBase::MySql => MysqlSQLRender::render_default(default_value),
+        DataBase::Sqlite => SqliteSQLRender::render_default(default_value),
+        DataBase::Postgres => client::PgSQLRender::render_default(default_value),
+        DataBase::Oracle => client::OracleSQLRender::render_default(default_value),
+    }
+}
+
+/// Binds the `#{path}` placeholders of a SQL script against `params`, using
+/// the positional placeholder style of `db_type`.
+pub fn render_script(
+    sql: &str,
+    params: &HashMap<String, RdbcValue>,
+    db_type: DataBase,
+) -> RdbcResult<(String, Vec<RdbcValue>)> {
+    let map_params = extract_map_params(params);
+    match db_type {
+        DataBase::MySql | DataBase::Sqlite => {
+            convert_script_to_sql(sql, &map_params, |_| "?".to_string())
+        }
+        DataBase::Postgres => {
+            convert_script_to_sql(sql, &map_params, |index| format!("${}", index))
+        }
+        DataBase::Oracle => convert_script_to_sql(sql, &map_params, |index| format!(":{}", index)),
+    }
+}
@@ -1,38 +1,47 @@
-use crate::{RdbcDeleteWrapper, RdbcInsertWrapper, RdbcQueryWrapper, RdbcUpdateWrapper, RdbcValue};
+use crate::render::client::util::inline_script_params;
+use crate::{
+    AlterColumn, AlterSequenceDDLWrapper, AlterTableAction, AlterTableDDLWrapper, ColumnDDLWrapper,
+    ColumnDefault, ColumnIdentity, CommentDDLWrapper, CopyTableDDLWrapper, DropIndexDDLWrapper,
+    DropSequenceDDLWrapper, DropTableDDLWrapper, DropViewDDLWrapper, ForeignKeyDDLWrapper,
+    IndexColumn, IndexDDLWrapper, RdbcColumnType, RdbcDdlWrapper, RdbcDeleteWrapper, RdbcError,
+    RdbcInsertWrapper, RdbcQueryWrapper, RdbcResult, RdbcUpdateWrapper, RdbcValue,
+    ReferentialAction, RefreshViewDDLWrapper, SequenceDDLWrapper, TableConstraint, TableDDLWrapper,
+    ViewDDLWrapper,
+};
 use std::collections::HashMap;
 
 pub trait RdbcSQLRender {
-    fn render_query(sql_wrapper: &RdbcQueryWrapper) -> (String, Vec<RdbcValue>);
-    fn render_update(sql_wrapper: &RdbcUpdateWrapper) -> (String, Vec<RdbcValue>);
-    fn render_insert(sql_wrapper: &RdbcInsertWrapper) -> (String, Vec<RdbcValue>);
-    fn render_delete(sql_wrapper: &RdbcDeleteWrapper) -> (String, Vec<RdbcValue>);
+    fn render_query(sql_wrapper: &RdbcQueryWrapper) -> RdbcResult<(String, Vec<RdbcValue>)>;
+    fn render_update(sql_wrapper: &RdbcUpdateWrapper) -> RdbcResult<(String, Vec<RdbcValue>)>;
+    fn render_insert(sql_wrapper: &RdbcInsertWrapper) -> RdbcResult<(String, Vec<RdbcValue>)>;
+    fn render_delete(sql_wrapper: &RdbcDeleteWrapper) -> RdbcResult<(String, Vec<RdbcValue>)>;
     fn render_query_with_params(
         sql_wrapper: &RdbcQueryWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>);
+    ) -> RdbcResult<(String, Vec<RdbcValue>)>;
     fn render_update_with_prams(
         sql_wrapper: &RdbcUpdateWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>);
+    ) -> RdbcResult<(String, Vec<RdbcValue>)>;
     fn render_insert_with_params(
         sql_wrapper: &RdbcInsertWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>);
+    ) -> RdbcResult<(String, Vec<RdbcValue>)>;
     fn render_delete_with_params(
         sql_wrapper: &RdbcDeleteWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, Vec<RdbcValue>);
+    ) -> RdbcResult<(String, Vec<RdbcValue>)>;
 
     fn render_query_script(sql_wrapper: &RdbcQueryWrapper) -> (String, HashMap<String, RdbcValue>);
     fn render_update_script(
         sql_wrapper: &RdbcUpdateWrapper,
-    ) -> (String, HashMap<String, RdbcValue>);
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)>;
     fn render_insert_script(
         sql_wrapper: &RdbcInsertWrapper,
     ) -> (String, HashMap<String, RdbcValue>);
     fn render_delete_script(
         sql_wrapper: &RdbcDeleteWrapper,
-    ) -> (String, HashMap<String, RdbcValue>);
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)>;
 
     fn render_query_script_with_params(
         sql_wrapper: &RdbcQueryWrapper,
@@ -41,7 +50,7 @@ pub trait RdbcSQLRender {
     fn render_update_script_with_params(
         sql_wrapper: &RdbcUpdateWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>);
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)>;
     fn render_insert_script_with_params(
         sql_wrapper: &RdbcInsertWrapper,
         params: &HashMap<String, RdbcValue>,
@@ -49,5 +58,580 @@ pub trait RdbcSQLRender {
     fn render_delete_script_with_params(
         sql_wrapper: &RdbcDeleteWrapper,
         params: &HashMap<String, RdbcValue>,
-    ) -> (String, HashMap<String, RdbcValue>);
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)>;
+
+    /// Expression taking the next value of a sequence.
+    fn render_next_value(sequence: &str) -> String {
+        format!("nextval({})", quote_literal(sequence))
+    }
+}
+
+/// Spelling of the DML scripts of [`crate::render::client::ScriptSQLRender`]
+/// that differs between dialects.
+pub trait RdbcDmlDialect {
+    /// Positional placeholder of the param at `index`, counted from 1.
+    fn placeholder(index: usize) -> String;
+    /// A bound param used as text, e.g. inside a LIKE pattern.
+    fn text_param(param: &str) -> String;
+    /// Expression taking the next value of a sequence.
+    fn next_value(sequence: &str) -> String;
+    /// Whether the database has sequences. Without them `next_value` only
+    /// works as an INSERT value, so SET and WHERE reject it.
+    const SEQUENCES: bool = true;
+}
+
+/// Renders DDL statements. Dialects supply the column types and the
+/// ALTER COLUMN forms and override the statements they spell differently.
+pub trait RdbcDDLRender: RdbcSQLRender {
+    fn render_ddl(ddl: &RdbcDdlWrapper) -> RdbcResult<Vec<String>> {
+        match ddl {
+            RdbcDdlWrapper::CreateTable(table) => Self::render_create_table(table),
+            RdbcDdlWrapper::AlterTable(alter) => Self::render_alter_table(alter),
+            RdbcDdlWrapper::DropTable(drop) => Self::render_drop_table(drop),
+            RdbcDdlWrapper::CreateIndex(index) => Self::render_create_index(index),
+            RdbcDdlWrapper::DropIndex(drop) => Self::render_drop_index(drop),
+            RdbcDdlWrapper::Comment(comment) => Self::render_comment(comment),
+            RdbcDdlWrapper::CreateView(view) => Self::render_create_view(view),
+            RdbcDdlWrapper::DropView(drop) => Self::render_drop_view(drop),
+            RdbcDdlWrapper::RefreshView(refresh) => Self::render_refresh_view(refresh),
+            RdbcDdlWrapper::CopyTable(copy) => Self::render_copy_table(copy),
+            RdbcDdlWrapper::CreateSequence(sequence) => Self::render_create_sequence(sequence),
+            RdbcDdlWrapper::AlterSequence(alter) => Self::render_alter_sequence(alter),
+            RdbcDdlWrapper::DropSequence(drop) => Self::render_drop_sequence(drop),
+        }
+    }
+
+    fn render_create_sequence(sequence: &SequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        let mut sql = format!(
+            "CREATE SEQUENCE {}{}",
+            if sequence.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            qualified_name(&sequence.schema, &sequence.name)
+        );
+        if let Some(start) = sequence.start {
+            sql.push_str(&format!(" START WITH {}", start));
+        }
+        sql.push_str(&sequence_options(
+            sequence.increment,
+            sequence.min_value,
+            sequence.max_value,
+            sequence.cache,
+        ));
+        if sequence.cycle {
+            sql.push_str(" CYCLE");
+        }
+        Ok(vec![sql])
+    }
+
+    fn render_alter_sequence(alter: &AlterSequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        let mut options = sequence_options(
+            alter.increment,
+            alter.min_value,
+            alter.max_value,
+            alter.cache,
+        );
+        if let Some(cycle) = alter.cycle {
+            options.push_str(if cycle { " CYCLE" } else { " NO CYCLE" });
+        }
+        if let Some(restart) = alter.restart {
+            options.push_str(&format!(" RESTART WITH {}", restart));
+        }
+        if options.is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(vec![format!(
+            "ALTER SEQUENCE {}{}",
+            qualified_name(&alter.schema, &alter.name),
+            options
+        )])
+    }
+
+    fn render_drop_sequence(drop: &DropSequenceDDLWrapper) -> RdbcResult<Vec<String>> {
+        Ok(vec![format!(
+            "DROP SEQUENCE {}{}{}",
+            if drop.if_exists { "IF EXISTS " } else { "" },
+            qualified_name(&drop.schema, &drop.name),
+            if drop.cascade { " CASCADE" } else { "" }
+        )])
+    }
+
+    fn render_copy_table(copy: &CopyTableDDLWrapper) -> RdbcResult<Vec<String>> {
+        let columns = copy.columns.join(", ");
+        Ok(vec![format!(
+            "INSERT INTO {} ({}) SELECT {} FROM {}",
+            qualified_name(&copy.schema, &copy.to),
+            columns,
+            columns,
+            qualified_name(&copy.schema, &copy.from)
+        )])
+    }
+
+    fn render_create_view(view: &ViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        if view.materialized {
+            return Err(RdbcError::Unsupported(format!(
+                "materialized view {}",
+                view.name
+            )));
+        }
+        let or_replace = if view.or_replace { "OR REPLACE " } else { "" };
+        Ok(vec![format!(
+            "CREATE {}VIEW {}{} AS {}",
+            or_replace,
+            qualified_name(&view.schema, &view.name),
+            render_view_columns(view),
+            Self::render_view_query(&view.query)?
+        )])
+    }
+
+    /// Query of a view with its params inlined as literals.
+    fn render_view_query(query: &RdbcQueryWrapper) -> RdbcResult<String> {
+        let (sql, params) = Self::render_query_script(query);
+        if sql.is_empty() {
+            return Err(RdbcError::Unsupported(
+                "view query renders empty for this database".to_string(),
+            ));
+        }
+        inline_script_params(&sql, &params, Self::render_literal)
+    }
+
+    fn render_drop_view(drop: &DropViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        if drop.materialized {
+            return Err(RdbcError::Unsupported(format!(
+                "materialized view {}",
+                drop.name
+            )));
+        }
+        let if_exists = if drop.if_exists { "IF EXISTS " } else { "" };
+        let cascade = if drop.cascade { " CASCADE" } else { "" };
+        Ok(vec![format!(
+            "DROP VIEW {}{}{}",
+            if_exists,
+            qualified_name(&drop.schema, &drop.name),
+            cascade
+        )])
+    }
+
+    fn render_refresh_view(refresh: &RefreshViewDDLWrapper) -> RdbcResult<Vec<String>> {
+        Err(RdbcError::Unsupported(format!(
+            "refresh of materialized view {}",
+            refresh.name
+        )))
+    }
+
+    fn render_create_table(table: &TableDDLWrapper) -> RdbcResult<Vec<String>> {
+        let mut items = vec![];
+        for column in table.columns.iter() {
+            items.push(Self::render_column(column)?);
+        }
+        let primary_columns: Vec<String> = table
+            .columns
+            .iter()
+            .filter(|column| column.primary_key && !Self::inline_primary_key(column))
+            .map(|column| column.name.clone())
+            .collect();
+        if !primary_columns.is_empty() && table.columns.iter().any(Self::inline_primary_key) {
+            return Err(RdbcError::Unsupported(format!(
+                "auto-increment primary key of table {} can not span several columns",
+                table.name
+            )));
+        }
+        let table_primary_key = table
+            .constraints
+            .iter()
+            .find_map(|constraint| match constraint {
+                TableConstraint::PrimaryKey { columns, .. } => Some(columns),
+                _ => None,
+            });
+        if let Some(table_columns) = table_primary_key {
+            // Column-level keys restating the table key are dropped; any other
+            // mix would declare two primary keys.
+            let conflicting = table.columns.iter().any(|column| {
+                (column.primary_key && !table_columns.contains(&column.name))
+                    || Self::inline_primary_key(column)
+            });
+            if conflicting {
+                return Err(RdbcError::InvalidParam(format!(
+                    "table {} declares its primary key on both columns and the table",
+                    table.name
+                )));
+            }
+        } else if !primary_columns.is_empty() {
+            items.push(Self::render_constraint(&TableConstraint::PrimaryKey {
+                name: None,
+                columns: primary_columns,
+            })?);
+        }
+        for constraint in table.constraints.iter() {
+            items.push(Self::render_constraint(constraint)?);
+        }
+        let if_not_exists = if table.if_not_exists {
+            "IF NOT EXISTS "
+        } else {
+            ""
+        };
+        let mut statements = vec![format!(
+            "CREATE TABLE {}{} (\n  {}\n){}",
+            if_not_exists,
+            qualified_name(&table.schema, &table.name),
+            items.join(",\n  "),
+            Self::render_table_options(table)
+        )];
+        statements.extend(Self::render_comments(
+            &table.schema,
+            &table.name,
+            table.comment.as_ref(),
+            &table.columns,
+        )?);
+        Ok(statements)
+    }
+
+    /// Whether the column definition itself declares the column as primary
+    /// key, leaving it out of the table PRIMARY KEY constraint.
+    fn inline_primary_key(_column: &ColumnDDLWrapper) -> bool {
+        false
+    }
+
+    /// Trailing table options of CREATE TABLE.
+    fn render_table_options(_table: &TableDDLWrapper) -> String {
+        "".to_string()
+    }
+
+    /// Comment statements following CREATE TABLE or ADD COLUMN. Dialects that
+    /// inline comments into the definitions return none.
+    fn render_comments(
+        schema: &str,
+        table: &str,
+        table_comment: Option<&String>,
+        columns: &[ColumnDDLWrapper],
+    ) -> RdbcResult<Vec<String>> {
+        let mut statements = vec![];
+        if let Some(comment) = table_comment {
+            let mut wrapper = CommentDDLWrapper::on_table(table, comment);
+            wrapper.schema(schema);
+            statements.extend(Self::render_comment(&wrapper)?);
+        }
+        for column in columns.iter() {
+            if let Some(comment) = column.comment.as_ref() {
+                let mut wrapper = CommentDDLWrapper::on_column(table, &column.name, comment);
+                wrapper.schema(schema);
+                statements.extend(Self::render_comment(&wrapper)?);
+            }
+        }
+        Ok(statements)
+    }
+
+    /// `COMMENT ON TABLE` or `COMMENT ON COLUMN`.
+    fn render_comment(comment: &CommentDDLWrapper) -> RdbcResult<Vec<String>> {
+        let table_name = qualified_name(&comment.schema, &comment.table);
+        let target = match comment.column.as_ref() {
+            Some(column) => format!("COLUMN {}.{}", table_name, column),
+            None => format!("TABLE {}", table_name),
+        };
+        let text = if comment.comment.is_empty() {
+            "NULL".to_string()
+        } else {
+            Self::render_literal(&RdbcValue::from(comment.comment.as_str()))
+        };
+        Ok(vec![format!("COMMENT ON {} IS {}", target, text)])
+    }
+
+    fn render_alter_table(alter: &AlterTableDDLWrapper) -> RdbcResult<Vec<String>> {
+        let table_name = qualified_name(&alter.schema, &alter.name);
+        let mut statements = vec![];
+        for action in alter.actions.iter() {
+            match action {
+                AlterTableAction::AddColumn(column) => {
+                    statements.push(Self::render_add_column(&table_name, column)?);
+                    statements.extend(Self::render_comments(
+                        &alter.schema,
+                        &alter.name,
+                        None,
+                        std::slice::from_ref(column),
+                    )?);
+                }
+                AlterTableAction::DropColumn(column) => {
+                    statements.push(format!("ALTER TABLE {} DROP COLUMN {}", table_name, column))
+                }
+                AlterTableAction::AlterColumn(alter_column) => {
+                    statements.extend(Self::render_alter_column(&table_name, alter_column)?)
+                }
+                AlterTableAction::RenameColumn { from, to } => statements.push(format!(
+                    "ALTER TABLE {} RENAME COLUMN {} TO {}",
+                    table_name, from, to
+                )),
+                AlterTableAction::RenameTable(name) => {
+                    statements.push(format!("ALTER TABLE {} RENAME TO {}", table_name, name))
+                }
+                AlterTableAction::AddConstraint(constraint) => {
+                    statements.push(Self::render_add_constraint(&table_name, constraint)?)
+                }
+                AlterTableAction::DropConstraint(name) => {
+                    statements.push(Self::render_drop_constraint(&table_name, name)?)
+                }
+            }
+        }
+        Ok(statements)
+    }
+
+    fn render_add_column(table_name: &str, column: &ColumnDDLWrapper) -> RdbcResult<String> {
+        let primary_key = if column.primary_key {
+            " PRIMARY KEY"
+        } else {
+            ""
+        };
+        Ok(format!(
+            "ALTER TABLE {} ADD COLUMN {}{}",
+            table_name,
+            Self::render_column(column)?,
+            primary_key
+        ))
+    }
+
+    fn render_alter_column(table_name: &str, alter: &AlterColumn) -> RdbcResult<Vec<String>>;
+
+    fn render_add_constraint(table_name: &str, constraint: &TableConstraint) -> RdbcResult<String> {
+        Ok(format!(
+            "ALTER TABLE {} ADD {}",
+            table_name,
+            Self::render_constraint(constraint)?
+        ))
+    }
+
+    fn render_drop_constraint(table_name: &str, name: &str) -> RdbcResult<String> {
+        Ok(format!(
+            "ALTER TABLE {} DROP CONSTRAINT {}",
+            table_name, name
+        ))
+    }
+
+    fn render_drop_table(drop: &DropTableDDLWrapper) -> RdbcResult<Vec<String>> {
+        let if_exists = if drop.if_exists { "IF EXISTS " } else { "" };
+        let cascade = if drop.cascade { " CASCADE" } else { "" };
+        Ok(vec![format!(
+            "DROP TABLE {}{}{}",
+            if_exists,
+            qualified_name(&drop.schema, &drop.name),
+            cascade
+        )])
+    }
+
+    /// Column name, type, identity, default and nullability.
+    fn render_column_spec(column: &ColumnDDLWrapper) -> RdbcResult<String> {
+        let mut spec = format!(
+            "{} {}",
+            column.name,
+            Self::render_column_type(&column.column_type)
+        );
+        if let Some(identity) = column.identity.as_ref() {
+            spec.push_str(&Self::render_identity(identity)?);
+        }
+        if let Some(default_value) = column.default_value.as_ref() {
+            spec.push_str(" DEFAULT ");
+            spec.push_str(&Self::render_default(default_value));
+        }
+        if !column.nullable {
+            spec.push_str(" NOT NULL");
+        }
+        Ok(spec)
+    }
+
+    /// Identity clause following the column type.
+    fn render_identity(identity: &ColumnIdentity) -> RdbcResult<String> {
+        let mut options = vec![];
+        if let Some(start) = identity.start {
+            options.push(format!("START WITH {}", start));
+        }
+        if let Some(increment) = identity.increment {
+            options.push(format!("INCREMENT BY {}", increment));
+        }
+        Ok(format!(
+            " GENERATED {} AS IDENTITY{}",
+            if identity.always {
+                "ALWAYS"
+            } else {
+                "BY DEFAULT"
+            },
+            if options.is_empty() {
+                "".to_string()
+            } else {
+                format!(" ({})", options.join(" "))
+            }
+        ))
+    }
+
+    /// Column spec followed by its inline UNIQUE and CHECK constraints.
+    fn render_column(column: &ColumnDDLWrapper) -> RdbcResult<String> {
+        let mut definition = Self::render_column_spec(column)?;
+        if column.unique {
+            definition.push_str(" UNIQUE");
+        }
+        if let Some(check) = column.check.as_ref() {
+            definition.push_str(&format!(" CHECK ({})", check));
+        }
+        Ok(definition)
+    }
+
+    fn render_column_type(column_type: &RdbcColumnType) -> String;
+
+    fn render_constraint(constraint: &TableConstraint) -> RdbcResult<String> {
+        let (name, body) = match constraint {
+            TableConstraint::PrimaryKey { name, columns } => {
+                (name, format!("PRIMARY KEY ({})", columns.join(", ")))
+            }
+            TableConstraint::Unique { name, columns } => {
+                (name, format!("UNIQUE ({})", columns.join(", ")))
+            }
+            TableConstraint::Check { name, expression } => {
+                (name, format!("CHECK ({})", expression))
+            }
+            TableConstraint::ForeignKey(foreign_key) => {
+                (&foreign_key.name, Self::render_foreign_key(foreign_key)?)
+            }
+        };
+        Ok(match name {
+            Some(name) => format!("CONSTRAINT {} {}", name, body),
+            None => body,
+        })
+    }
+
+    /// FOREIGN KEY clause without the constraint name.
+    fn render_foreign_key(foreign_key: &ForeignKeyDDLWrapper) -> RdbcResult<String> {
+        let mut clause = format!(
+            "FOREIGN KEY ({}) REFERENCES {} ({})",
+            foreign_key.columns.join(", "),
+            foreign_key.ref_table,
+            foreign_key.ref_columns.join(", ")
+        );
+        if let Some(action) = foreign_key.on_delete {
+            clause.push_str(&format!(
+                " ON DELETE {}",
+                Self::render_referential_action("DELETE", action)?
+            ));
+        }
+        if let Some(action) = foreign_key.on_update {
+            clause.push_str(&format!(
+                " ON UPDATE {}",
+                Self::render_referential_action("UPDATE", action)?
+            ));
+        }
+        Ok(clause)
+    }
+
+    /// Action keyword of an `ON DELETE` or `ON UPDATE` clause.
+    fn render_referential_action(_event: &str, action: ReferentialAction) -> RdbcResult<String> {
+        Ok(match action {
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+        }
+        .to_string())
+    }
+
+    fn render_create_index(index: &IndexDDLWrapper) -> RdbcResult<Vec<String>>;
+
+    fn render_drop_index(drop: &DropIndexDDLWrapper) -> RdbcResult<Vec<String>>;
+
+    /// Comma separated index columns, with expressions wrapped in parentheses.
+    fn render_index_columns(index: &IndexDDLWrapper) -> String {
+        index
+            .columns
+            .iter()
+            .map(|column| match column {
+                IndexColumn::Column(name) => name.clone(),
+                IndexColumn::Expression(expression) => format!("({})", expression),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn render_default(default_value: &ColumnDefault) -> String {
+        match default_value {
+            ColumnDefault::Value(value) => Self::render_literal(value),
+            ColumnDefault::Expression(expression) => expression.clone(),
+        }
+    }
+
+    /// Renders a value as an inline SQL literal.
+    fn render_literal(value: &RdbcValue) -> String {
+        ansi_literal(value)
+    }
+}
+
+/// Parenthesized column list of a view, empty when the query names them.
+pub(crate) fn render_view_columns(view: &ViewDDLWrapper) -> String {
+    if view.columns.is_empty() {
+        "".to_string()
+    } else {
+        format!(" ({})", view.columns.join(", "))
+    }
+}
+
+/// `INCREMENT BY`, `MINVALUE`, `MAXVALUE` and `CACHE` options of a sequence.
+pub(crate) fn sequence_options(
+    increment: Option<i64>,
+    min_value: Option<i64>,
+    max_value: Option<i64>,
+    cache: Option<i64>,
+) -> String {
+    let mut options = String::new();
+    if let Some(increment) = increment {
+        options.push_str(&format!(" INCREMENT BY {}", increment));
+    }
+    if let Some(min_value) = min_value {
+        options.push_str(&format!(" MINVALUE {}", min_value));
+    }
+    if let Some(max_value) = max_value {
+        options.push_str(&format!(" MAXVALUE {}", max_value));
+    }
+    if let Some(cache) = cache {
+        options.push_str(&format!(" CACHE {}", cache));
+    }
+    options
+}
+
+/// `schema.name`, or just `name` without a schema.
+pub(crate) fn qualified_name(schema: &str, name: &str) -> String {
+    if schema.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", schema, name)
+    }
+}
+
+/// Wraps `value` in single quotes, doubling embedded quotes.
+pub(crate) fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+pub(crate) fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+/// ANSI literal of `value`; dialects override the variants they spell differently.
+pub(crate) fn ansi_literal(value: &RdbcValue) -> String {
+    match value {
+        RdbcValue::Char(_)
+        | RdbcValue::Varchar(_)
+        | RdbcValue::Text(_)
+        | RdbcValue::LongText(_)
+        | RdbcValue::Array(_)
+        | RdbcValue::Object(_) => quote_literal(&value.to_string()),
+        RdbcValue::SmallInt(_)
+        | RdbcValue::Int(_)
+        | RdbcValue::BigInt(_)
+        | RdbcValue::Double(_)
+        | RdbcValue::BigDouble(_)
+        | RdbcValue::TimeStamp(_) => value.to_string(),
+        RdbcValue::Date(v) => format!("DATE '{}'", v),
+        RdbcValue::DateTime(v) => format!("TIMESTAMP '{}'", v),
+        RdbcValue::Time(v) => format!("TIME '{}'", v),
+        RdbcValue::Bytes(v) => format!("X'{}'", hex_string(v)),
+        RdbcValue::Boolean(v) => if *v { "TRUE" } else { "FALSE" }.to_string(),
+        RdbcValue::Null => "NULL".to_string(),
+    }
 }
@@ -0,0 +1,373 @@
+use crate::render::{render_column_default, render_column_type};
+use crate::{
+    AlterColumn, AlterTableAction, AlterTableDDLWrapper, ColumnDDLWrapper, CommentDDLWrapper,
+    CopyTableDDLWrapper, DataBase, DropIndexDDLWrapper, DropTableDDLWrapper, IndexColumn,
+    IndexDDLWrapper, RdbcDdlWrapper, RdbcError, RdbcResult, TableConstraint, TableDDLWrapper,
+};
+
+/// Compares a desired table model, e.g. from `RdbcTableIdent::table_ddl`,
+/// with the one read by [`crate::RdbcIntrospector`] and lists the statements
+/// turning the database into the desired state.
+///
+/// Types and defaults are compared as `db_type` renders them, so models that
+/// map to the same declaration are equal. SQLite cannot alter columns, there
+/// the table is rebuilt: created under a temporary name, filled from the old
+/// table, and renamed once the old table is dropped.
+///
+/// A NOT NULL column added without a default can not be filled for existing
+/// rows: its ADD COLUMN, or the copy of a SQLite rebuild, fails unless the
+/// table is empty. A changed primary key is only applied by a SQLite rebuild
+/// and reported as [`RdbcError::Unsupported`] for the other databases. Safe
+/// mode refuses both.
+#[derive(Debug, Clone, Copy)]
+pub struct RdbcSchemaDiff {
+    db_type: DataBase,
+    safe: bool,
+}
+
+impl RdbcSchemaDiff {
+    pub fn new(db_type: DataBase) -> Self {
+        RdbcSchemaDiff {
+            db_type,
+            safe: false,
+        }
+    }
+
+    /// Refuses changes that may lose data, dropped columns and changed column
+    /// types, with [`RdbcError::Destructive`] instead of emitting them.
+    pub fn safe(&mut self) -> &mut Self {
+        self.safe = true;
+        self
+    }
+
+    /// Statements in execution order. `actual` is `None` when the table does
+    /// not exist yet; indexes present only in the database are dropped,
+    /// unless they back a UNIQUE constraint of `desired`.
+    pub fn diff_table(
+        &self,
+        desired: &TableDDLWrapper,
+        desired_indexes: &[IndexDDLWrapper],
+        actual: Option<&TableDDLWrapper>,
+        actual_indexes: &[IndexDDLWrapper],
+    ) -> RdbcResult<Vec<RdbcDdlWrapper>> {
+        let actual = match actual {
+            Some(actual) => actual,
+            None => {
+                let mut statements = vec![RdbcDdlWrapper::CreateTable(desired.clone())];
+                statements.extend(desired_indexes.iter().cloned().map(RdbcDdlWrapper::from));
+                return Ok(statements);
+            }
+        };
+
+        let added: Vec<&ColumnDDLWrapper> = desired
+            .columns
+            .iter()
+            .filter(|column| find_column(actual, &column.name).is_none())
+            .collect();
+        let dropped: Vec<&ColumnDDLWrapper> = actual
+            .columns
+            .iter()
+            .filter(|column| find_column(desired, &column.name).is_none())
+            .collect();
+        let mut altered: Vec<AlterColumn> = vec![];
+        for column in desired.columns.iter() {
+            if let Some(current) = find_column(actual, &column.name) {
+                if let Some(alter) = self.diff_column(desired, column, current) {
+                    altered.push(alter);
+                }
+            }
+        }
+
+        let change_primary_key = primary_key(desired) != primary_key(actual);
+        if self.safe {
+            if let Some(column) = added.iter().find(|column| needs_value(column)) {
+                return Err(RdbcError::Unsupported(format!(
+                    "add NOT NULL column {}.{} without a default",
+                    desired.name, column.name
+                )));
+            }
+            if change_primary_key {
+                return Err(RdbcError::Unsupported(format!(
+                    "change primary key of table {}",
+                    desired.name
+                )));
+            }
+            if let Some(column) = dropped.first() {
+                return Err(RdbcError::Destructive(format!(
+                    "drop column {}.{}",
+                    desired.name, column.name
+                )));
+            }
+            if let Some(alter) = altered.iter().find(|alter| alter.change_type) {
+                return Err(RdbcError::Destructive(format!(
+                    "change type of column {}.{}",
+                    desired.name, alter.column.name
+                )));
+            }
+        }
+
+        if self.db_type == DataBase::Sqlite
+            && (!altered.is_empty() || !dropped.is_empty() || change_primary_key)
+        {
+            return Ok(self.rebuild_table(desired, desired_indexes, actual));
+        }
+        if change_primary_key {
+            return Err(RdbcError::Unsupported(format!(
+                "change primary key of table {}",
+                desired.name
+            )));
+        }
+
+        let mut statements = vec![];
+        let mut created_indexes = vec![];
+        for index in actual_indexes.iter() {
+            match find_index(desired_indexes, &index.name) {
+                Some(wanted) if same_index(wanted, index) => {}
+                Some(wanted) => {
+                    statements.push(drop_index(desired, index));
+                    created_indexes.push(wanted.clone());
+                }
+                None if backs_unique_constraint(desired, index) => {}
+                None => statements.push(drop_index(desired, index)),
+            }
+        }
+        for index in desired_indexes.iter() {
+            if find_index(actual_indexes, &index.name).is_none() {
+                created_indexes.push(index.clone());
+            }
+        }
+
+        let mut alter = AlterTableDDLWrapper::new(&desired.name);
+        alter.schema(&desired.schema);
+        for column in added {
+            alter.add_column(column.clone());
+        }
+        for alter_column in altered {
+            alter
+                .actions
+                .push(AlterTableAction::AlterColumn(alter_column));
+        }
+        for column in dropped {
+            alter.drop_column(&column.name);
+        }
+        if !alter.actions.is_empty() {
+            statements.push(alter.into());
+        }
+
+        statements.extend(self.diff_comments(desired, actual));
+        statements.extend(created_indexes.into_iter().map(RdbcDdlWrapper::from));
+        Ok(statements)
+    }
+
+    fn diff_column(
+        &self,
+        table: &TableDDLWrapper,
+        desired: &ColumnDDLWrapper,
+        current: &ColumnDDLWrapper,
+    ) -> Option<AlterColumn> {
+        let change_type = normalize_sql(&render_column_type(&desired.column_type, self.db_type))
+            != normalize_sql(&render_column_type(&current.column_type, self.db_type));
+        let change_nullable = desired.nullable != current.nullable;
+        let render_default = |column: &ColumnDDLWrapper| {
+            column
+                .default_value
+                .as_ref()
+                .map(|default_value| render_column_default(default_value, self.db_type))
+        };
+        let change_default = render_default(desired) != render_default(current);
+        let unique = is_unique_column(table, &desired.name);
+        let change_unique = unique != current.unique;
+        if !change_type && !change_nullable && !change_default && !change_unique {
+            return None;
+        }
+        let mut alter = AlterColumn::new(desired.clone());
+        alter.column.unique = unique;
+        alter.change_type = change_type;
+        alter.change_nullable = change_nullable;
+        alter.change_default = change_default;
+        alter.change_unique = change_unique;
+        Some(alter)
+    }
+
+    fn diff_comments(
+        &self,
+        desired: &TableDDLWrapper,
+        actual: &TableDDLWrapper,
+    ) -> Vec<RdbcDdlWrapper> {
+        // SQLite keeps no comments to compare with.
+        if self.db_type == DataBase::Sqlite {
+            return vec![];
+        }
+        let mut statements = vec![];
+        let table_comment = desired.comment.clone().unwrap_or_default();
+        if table_comment != actual.comment.clone().unwrap_or_default() {
+            let mut comment = CommentDDLWrapper::on_table(&desired.name, table_comment);
+            comment.schema(&desired.schema);
+            statements.push(comment.into());
+        }
+        for column in desired.columns.iter() {
+            let current = match find_column(actual, &column.name) {
+                Some(current) => current,
+                None => continue,
+            };
+            let column_comment = column.comment.clone().unwrap_or_default();
+            if column_comment != current.comment.clone().unwrap_or_default() {
+                let mut comment =
+                    CommentDDLWrapper::on_column(&desired.name, &column.name, column_comment);
+                comment.schema(&desired.schema);
+                comment.column_definition(column.clone());
+                statements.push(comment.into());
+            }
+        }
+        statements
+    }
+
+    fn rebuild_table(
+        &self,
+        desired: &TableDDLWrapper,
+        desired_indexes: &[IndexDDLWrapper],
+        actual: &TableDDLWrapper,
+    ) -> Vec<RdbcDdlWrapper> {
+        let rebuild_name = format!("{}__rebuild", desired.name);
+        let mut rebuild = desired.clone();
+        rebuild.name = rebuild_name.clone();
+        rebuild.if_not_exists = false;
+        let kept_columns: Vec<&String> = desired
+            .columns
+            .iter()
+            .filter(|column| find_column(actual, &column.name).is_some())
+            .map(|column| &column.name)
+            .collect();
+        let mut copy = CopyTableDDLWrapper::new(&actual.name, &rebuild_name, &kept_columns);
+        copy.schema(&desired.schema);
+        let mut drop = DropTableDDLWrapper::new(&actual.name);
+        drop.schema(&desired.schema);
+        let mut rename = AlterTableDDLWrapper::new(&rebuild_name);
+        rename.schema(&desired.schema).rename_to(&desired.name);
+
+        let mut statements = vec![
+            RdbcDdlWrapper::CreateTable(rebuild),
+            copy.into(),
+            drop.into(),
+            rename.into(),
+        ];
+        statements.extend(desired_indexes.iter().cloned().map(RdbcDdlWrapper::from));
+        statements
+    }
+}
+
+fn find_column<'a>(table: &'a TableDDLWrapper, name: &str) -> Option<&'a ColumnDDLWrapper> {
+    table
+        .columns
+        .iter()
+        .find(|column| column.name.eq_ignore_ascii_case(name))
+}
+
+fn find_index<'a>(indexes: &'a [IndexDDLWrapper], name: &str) -> Option<&'a IndexDDLWrapper> {
+    indexes
+        .iter()
+        .find(|index| index.name.eq_ignore_ascii_case(name))
+}
+
+/// Lower-cased, sorted primary key columns, inline or by a table constraint.
+fn primary_key(table: &TableDDLWrapper) -> Vec<String> {
+    let mut columns: Vec<String> = table
+        .columns
+        .iter()
+        .filter(|column| column.primary_key)
+        .map(|column| column.name.to_lowercase())
+        .collect();
+    for constraint in table.constraints.iter() {
+        if let TableConstraint::PrimaryKey { columns: keys, .. } = constraint {
+            columns.extend(keys.iter().map(|key| key.to_lowercase()));
+        }
+    }
+    columns.sort();
+    columns.dedup();
+    columns
+}
+
+/// Whether existing rows need a value the column definition does not give.
+fn needs_value(column: &ColumnDDLWrapper) -> bool {
+    (!column.nullable || column.primary_key)
+        && column.default_value.is_none()
+        && column.identity.is_none()
+}
+
+/// Whether the column alone is UNIQUE, inline or by a table constraint.
+fn is_unique_column(table: &TableDDLWrapper, name: &str) -> bool {
+    unique_constraints(table)
+        .any(|columns| columns.len() == 1 && columns[0].eq_ignore_ascii_case(name))
+}
+
+/// Column lists of the UNIQUE constraints of `table`, inline ones included.
+fn unique_constraints(table: &TableDDLWrapper) -> impl Iterator<Item = Vec<&str>> {
+    let inline = table
+        .columns
+        .iter()
+        .filter(|column| column.unique)
+        .map(|column| vec![column.name.as_str()]);
+    let constraints = table
+        .constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            TableConstraint::Unique { columns, .. } => {
+                Some(columns.iter().map(String::as_str).collect())
+            }
+            _ => None,
+        });
+    inline.chain(constraints)
+}
+
+/// Whether `index` is the one a UNIQUE constraint of `table` creates.
+fn backs_unique_constraint(table: &TableDDLWrapper, index: &IndexDDLWrapper) -> bool {
+    if !index.unique || index.where_condition.is_some() {
+        return false;
+    }
+    let mut index_columns = vec![];
+    for column in index.columns.iter() {
+        match column {
+            IndexColumn::Column(name) => index_columns.push(name.as_str()),
+            IndexColumn::Expression(_) => return false,
+        }
+    }
+    unique_constraints(table).any(|columns| {
+        columns.len() == index_columns.len()
+            && columns
+                .iter()
+                .zip(index_columns.iter())
+                .all(|(left, right)| left.eq_ignore_ascii_case(right))
+    })
+}
+
+fn drop_index(table: &TableDDLWrapper, index: &IndexDDLWrapper) -> RdbcDdlWrapper {
+    let mut drop = DropIndexDDLWrapper::new(&table.name, &index.name);
+    drop.schema(&table.schema);
+    drop.into()
+}
+
+/// Databases echo expressions back with their own casing, quoting and
+/// parentheses, which are ignored when comparing.
+fn normalize_sql(sql: &str) -> String {
+    sql.chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '(' | ')' | '"' | '`'))
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+fn same_index(left: &IndexDDLWrapper, right: &IndexDDLWrapper) -> bool {
+    let index_columns = |index: &IndexDDLWrapper| -> Vec<String> {
+        index
+            .columns
+            .iter()
+            .map(|column| match column {
+                IndexColumn::Column(name) => normalize_sql(name),
+                IndexColumn::Expression(expression) => normalize_sql(expression),
+            })
+            .collect()
+    };
+    let condition = |index: &IndexDDLWrapper| index.where_condition.as_deref().map(normalize_sql);
+    left.unique == right.unique
+        && index_columns(left) == index_columns(right)
+        && condition(left) == condition(right)
+}
@@ -0,0 +1,460 @@
+use crate::{
+    ColumnDDLWrapper, ColumnDefault, DataBase, IndexColumn, IndexDDLWrapper, RdbcColumnType,
+    RdbcResult, RdbcValue, TableDDLWrapper,
+};
+use std::collections::HashMap;
+
+/// Builds the metadata queries of one database and parses their rows back
+/// into the DDL model. The queries are plain SQL with positional params, so
+/// any executor can run them; rows are expected as column name to value maps.
+///
+/// Every dialect selects the same column aliases:
+/// - table query: `table_comment`, one row when the table exists;
+/// - columns query: `column_name`, `data_type`, `char_length`, `num_precision`,
+///   `num_scale`, `is_nullable`, `column_default`, `is_primary`, `is_unique`
+///   (the column alone has a UNIQUE constraint), `column_comment`;
+/// - indexes query: `index_name`, `column_name`, `expression`, `is_unique`,
+///   `seq`, `where_condition`, excluding indexes backing a primary key or
+///   unique constraint.
+#[derive(Debug, Clone, Copy)]
+pub struct RdbcIntrospector {
+    db_type: DataBase,
+}
+
+impl RdbcIntrospector {
+    pub fn new(db_type: DataBase) -> Self {
+        RdbcIntrospector { db_type }
+    }
+
+    /// Query returning one row with the table comment if the table exists.
+    /// An empty schema means the current schema of the connection.
+    pub fn table_query(&self, schema: &str, table: &str) -> (String, Vec<RdbcValue>) {
+        let sql = match self.db_type {
+            DataBase::Postgres => {
+                "SELECT obj_description(c.oid, 'pg_class') AS table_comment \
+                 FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+                 WHERE n.nspname = COALESCE(NULLIF($1, ''), current_schema()) \
+                 AND c.relname = $2 AND c.relkind IN ('r', 'p')"
+            }
+            DataBase::MySql => {
+                "SELECT NULLIF(table_comment, '') AS table_comment \
+                 FROM information_schema.tables \
+                 WHERE table_schema = COALESCE(NULLIF(?, ''), DATABASE()) \
+                 AND table_name = ? AND table_type = 'BASE TABLE'"
+            }
+            DataBase::Sqlite => {
+                "SELECT NULL AS table_comment FROM pragma_table_list \
+                 WHERE schema = COALESCE(NULLIF(?, ''), 'main') AND name = ? AND type = 'table'"
+            }
+            DataBase::Oracle => {
+                "SELECT c.comments AS \"table_comment\" FROM all_tables t \
+                 LEFT JOIN all_tab_comments c ON c.owner = t.owner AND c.table_name = t.table_name \
+                 WHERE t.owner = UPPER(COALESCE(:1, SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA'))) \
+                 AND t.table_name = UPPER(:2)"
+            }
+        };
+        (sql.to_string(), schema_table_params(schema, table))
+    }
+
+    /// Query returning one row per column in declaration order.
+    pub fn columns_query(&self, schema: &str, table: &str) -> (String, Vec<RdbcValue>) {
+        let sql = match self.db_type {
+            DataBase::Postgres => {
+                "SELECT c.column_name, \
+                 CASE WHEN c.data_type IN ('USER-DEFINED', 'ARRAY') THEN c.udt_name ELSE c.data_type END AS data_type, \
+                 c.character_maximum_length AS char_length, c.numeric_precision AS num_precision, \
+                 c.numeric_scale AS num_scale, c.is_nullable, c.column_default, \
+                 CASE WHEN pk.column_name IS NULL THEN 0 ELSE 1 END AS is_primary, \
+                 CASE WHEN EXISTS (SELECT 1 FROM pg_constraint uc \
+                 WHERE uc.conrelid = format('%I.%I', c.table_schema, c.table_name)::regclass \
+                 AND uc.contype = 'u' AND uc.conkey = ARRAY[c.ordinal_position::smallint]) \
+                 THEN 1 ELSE 0 END AS is_unique, \
+                 col_description(format('%I.%I', c.table_schema, c.table_name)::regclass, c.ordinal_position) AS column_comment \
+                 FROM information_schema.columns c \
+                 LEFT JOIN (SELECT kcu.table_schema, kcu.table_name, kcu.column_name \
+                 FROM information_schema.table_constraints tc \
+                 JOIN information_schema.key_column_usage kcu ON kcu.constraint_name = tc.constraint_name \
+                 AND kcu.table_schema = tc.table_schema AND kcu.table_name = tc.table_name \
+                 WHERE tc.constraint_type = 'PRIMARY KEY') pk ON pk.table_schema = c.table_schema \
+                 AND pk.table_name = c.table_name AND pk.column_name = c.column_name \
+                 WHERE c.table_schema = COALESCE(NULLIF($1, ''), current_schema()) AND c.table_name = $2 \
+                 ORDER BY c.ordinal_position"
+            }
+            DataBase::MySql => {
+                "SELECT column_name AS column_name, column_type AS data_type, \
+                 character_maximum_length AS char_length, numeric_precision AS num_precision, \
+                 numeric_scale AS num_scale, is_nullable AS is_nullable, column_default AS column_default, \
+                 CASE WHEN column_key = 'PRI' THEN 1 ELSE 0 END AS is_primary, \
+                 CASE WHEN column_key = 'UNI' THEN 1 ELSE 0 END AS is_unique, \
+                 NULLIF(column_comment, '') AS column_comment \
+                 FROM information_schema.columns \
+                 WHERE table_schema = COALESCE(NULLIF(?, ''), DATABASE()) AND table_name = ? \
+                 ORDER BY ordinal_position"
+            }
+            DataBase::Sqlite => {
+                "SELECT ti.name AS column_name, ti.type AS data_type, NULL AS char_length, \
+                 NULL AS num_precision, NULL AS num_scale, \
+                 CASE WHEN ti.\"notnull\" = 1 THEN 'NO' ELSE 'YES' END AS is_nullable, \
+                 ti.dflt_value AS column_default, CASE WHEN ti.pk > 0 THEN 1 ELSE 0 END AS is_primary, \
+                 CASE WHEN EXISTS (SELECT 1 FROM pragma_index_list(?2, COALESCE(NULLIF(?1, ''), 'main')) il \
+                 WHERE il.origin = 'u' AND (SELECT GROUP_CONCAT(ii.name) \
+                 FROM pragma_index_info(il.name, COALESCE(NULLIF(?1, ''), 'main')) ii) = ti.name) \
+                 THEN 1 ELSE 0 END AS is_unique, NULL AS column_comment \
+                 FROM pragma_table_info(?2, COALESCE(NULLIF(?1, ''), 'main')) ti ORDER BY ti.cid"
+            }
+            DataBase::Oracle => {
+                "SELECT LOWER(c.column_name) AS \"column_name\", c.data_type AS \"data_type\", \
+                 c.char_length AS \"char_length\", c.data_precision AS \"num_precision\", \
+                 c.data_scale AS \"num_scale\", CASE c.nullable WHEN 'Y' THEN 'YES' ELSE 'NO' END AS \"is_nullable\", \
+                 c.data_default AS \"column_default\", \
+                 CASE WHEN pk.column_name IS NULL THEN 0 ELSE 1 END AS \"is_primary\", \
+                 CASE WHEN EXISTS (SELECT 1 FROM all_constraints uc JOIN all_cons_columns ucc \
+                 ON ucc.owner = uc.owner AND ucc.constraint_name = uc.constraint_name \
+                 WHERE uc.owner = c.owner AND uc.table_name = c.table_name AND uc.constraint_type = 'U' \
+                 AND ucc.column_name = c.column_name AND NOT EXISTS (SELECT 1 FROM all_cons_columns o \
+                 WHERE o.owner = uc.owner AND o.constraint_name = uc.constraint_name \
+                 AND o.column_name <> c.column_name)) THEN 1 ELSE 0 END AS \"is_unique\", \
+                 cc.comments AS \"column_comment\" \
+                 FROM all_tab_columns c \
+                 LEFT JOIN (SELECT cols.owner, cols.table_name, cols.column_name FROM all_constraints cons \
+                 JOIN all_cons_columns cols ON cols.owner = cons.owner AND cols.constraint_name = cons.constraint_name \
+                 WHERE cons.constraint_type = 'P') pk ON pk.owner = c.owner AND pk.table_name = c.table_name \
+                 AND pk.column_name = c.column_name \
+                 LEFT JOIN all_col_comments cc ON cc.owner = c.owner AND cc.table_name = c.table_name \
+                 AND cc.column_name = c.column_name \
+                 WHERE c.owner = UPPER(COALESCE(:1, SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA'))) \
+                 AND c.table_name = UPPER(:2) ORDER BY c.column_id"
+            }
+        };
+        (sql.to_string(), schema_table_params(schema, table))
+    }
+
+    /// Query returning one row per index column, ordered by index and position.
+    pub fn indexes_query(&self, schema: &str, table: &str) -> (String, Vec<RdbcValue>) {
+        let sql = match self.db_type {
+            DataBase::Postgres => {
+                "SELECT i.relname AS index_name, \
+                 CASE WHEN ix.indkey[k.n] = 0 THEN NULL ELSE pg_get_indexdef(ix.indexrelid, k.n + 1, true) END AS column_name, \
+                 CASE WHEN ix.indkey[k.n] = 0 THEN pg_get_indexdef(ix.indexrelid, k.n + 1, true) END AS expression, \
+                 CASE WHEN ix.indisunique THEN 1 ELSE 0 END AS is_unique, k.n + 1 AS seq, \
+                 pg_get_expr(ix.indpred, ix.indrelid) AS where_condition \
+                 FROM pg_index ix JOIN pg_class t ON t.oid = ix.indrelid \
+                 JOIN pg_class i ON i.oid = ix.indexrelid JOIN pg_namespace ns ON ns.oid = t.relnamespace \
+                 CROSS JOIN LATERAL generate_series(0, ix.indnkeyatts - 1) AS k(n) \
+                 WHERE NOT ix.indisprimary AND ns.nspname = COALESCE(NULLIF($1, ''), current_schema()) \
+                 AND t.relname = $2 AND NOT EXISTS (SELECT 1 FROM pg_constraint c \
+                 WHERE c.conindid = ix.indexrelid AND c.conrelid = ix.indrelid AND c.contype IN ('u', 'x')) \
+                 ORDER BY i.relname, k.n"
+            }
+            DataBase::MySql => {
+                "SELECT index_name AS index_name, column_name AS column_name, expression AS expression, \
+                 CASE WHEN non_unique = 0 THEN 1 ELSE 0 END AS is_unique, seq_in_index AS seq, \
+                 NULL AS where_condition FROM information_schema.statistics s \
+                 WHERE s.table_schema = COALESCE(NULLIF(?, ''), DATABASE()) AND s.table_name = ? \
+                 AND s.index_name <> 'PRIMARY' AND NOT EXISTS (SELECT 1 \
+                 FROM information_schema.table_constraints tc WHERE tc.table_schema = s.table_schema \
+                 AND tc.table_name = s.table_name AND tc.constraint_name = s.index_name \
+                 AND tc.constraint_type = 'UNIQUE') ORDER BY s.index_name, s.seq_in_index"
+            }
+            DataBase::Sqlite => {
+                "SELECT il.name AS index_name, ii.name AS column_name, NULL AS expression, \
+                 il.\"unique\" AS is_unique, ii.seqno + 1 AS seq, \
+                 CASE WHEN il.partial = 1 THEN m.sql END AS where_condition \
+                 FROM pragma_index_list(?2, COALESCE(NULLIF(?1, ''), 'main')) il \
+                 JOIN pragma_index_info(il.name, COALESCE(NULLIF(?1, ''), 'main')) ii \
+                 LEFT JOIN sqlite_master m ON m.type = 'index' AND m.name = il.name \
+                 WHERE il.origin = 'c' ORDER BY il.name, ii.seqno"
+            }
+            DataBase::Oracle => {
+                "SELECT LOWER(i.index_name) AS \"index_name\", LOWER(ic.column_name) AS \"column_name\", \
+                 ie.column_expression AS \"expression\", \
+                 CASE i.uniqueness WHEN 'UNIQUE' THEN 1 ELSE 0 END AS \"is_unique\", \
+                 ic.column_position AS \"seq\", NULL AS \"where_condition\" \
+                 FROM all_indexes i JOIN all_ind_columns ic ON ic.index_owner = i.owner \
+                 AND ic.index_name = i.index_name \
+                 LEFT JOIN all_ind_expressions ie ON ie.index_owner = ic.index_owner \
+                 AND ie.index_name = ic.index_name AND ie.column_position = ic.column_position \
+                 WHERE i.table_owner = UPPER(COALESCE(:1, SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA'))) \
+                 AND i.table_name = UPPER(:2) AND NOT EXISTS (SELECT 1 FROM all_constraints c \
+                 WHERE c.owner = i.table_owner AND c.index_name = i.index_name AND c.constraint_type IN ('P', 'U')) \
+                 ORDER BY i.index_name, ic.column_position"
+            }
+        };
+        (sql.to_string(), schema_table_params(schema, table))
+    }
+
+    /// Builds the table model from the rows of `table_query` and
+    /// `columns_query`, or `None` when the table does not exist.
+    pub fn parse_table(
+        &self,
+        schema: &str,
+        table: &str,
+        table_rows: &[HashMap<String, RdbcValue>],
+        column_rows: &[HashMap<String, RdbcValue>],
+    ) -> RdbcResult<Option<TableDDLWrapper>> {
+        let table_row = match table_rows.first() {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+        let mut table_ddl = TableDDLWrapper::new(table);
+        table_ddl.schema(schema);
+        table_ddl.comment = row_string(table_row, "table_comment");
+        for row in column_rows.iter() {
+            table_ddl.column(self.parse_column(row));
+        }
+        Ok(Some(table_ddl))
+    }
+
+    /// Groups the rows of `indexes_query` into index models.
+    pub fn parse_indexes(
+        &self,
+        schema: &str,
+        table: &str,
+        rows: &[HashMap<String, RdbcValue>],
+    ) -> RdbcResult<Vec<IndexDDLWrapper>> {
+        let mut indexes: Vec<IndexDDLWrapper> = vec![];
+        for row in rows.iter() {
+            let name = row_string(row, "index_name").unwrap_or_default();
+            if indexes
+                .last()
+                .map(|index| index.name != name)
+                .unwrap_or(true)
+            {
+                let mut index = IndexDDLWrapper::new(table, &name);
+                index.schema(schema);
+                index.unique = row_i64(row, "is_unique").unwrap_or(0) != 0;
+                index.where_condition = row_string(row, "where_condition").map(|condition| {
+                    match self.db_type {
+                        // SQLite only exposes the CREATE INDEX statement.
+                        DataBase::Sqlite => index_sql_predicate(&condition),
+                        _ => condition,
+                    }
+                });
+                indexes.push(index);
+            }
+            let index = indexes.last_mut().unwrap();
+            match row_string(row, "expression") {
+                Some(expression) => index.columns.push(IndexColumn::Expression(expression)),
+                None => index.columns.push(IndexColumn::Column(
+                    row_string(row, "column_name").unwrap_or_default(),
+                )),
+            }
+        }
+        Ok(indexes)
+    }
+
+    fn parse_column(&self, row: &HashMap<String, RdbcValue>) -> ColumnDDLWrapper {
+        let data_type = row_string(row, "data_type").unwrap_or_default();
+        let char_length = row_i64(row, "char_length");
+        let precision = row_i64(row, "num_precision");
+        let scale = row_i64(row, "num_scale");
+        let column_type = match self.db_type {
+            DataBase::Postgres => {
+                let declared = match (data_type.as_str(), char_length, precision) {
+                    ("character varying" | "character", Some(length), _) => {
+                        format!("{}({})", data_type, length)
+                    }
+                    ("numeric", _, Some(precision)) => {
+                        format!("numeric({},{})", precision, scale.unwrap_or(0))
+                    }
+                    _ => data_type.clone(),
+                };
+                parse_declared_type(&declared)
+            }
+            DataBase::Oracle => parse_oracle_type(&data_type, char_length, precision, scale),
+            DataBase::MySql | DataBase::Sqlite => parse_declared_type(&data_type),
+        };
+        let mut column = ColumnDDLWrapper::new(
+            row_string(row, "column_name").unwrap_or_default(),
+            column_type,
+        );
+        column.nullable = row_string(row, "is_nullable")
+            .map(|nullable| nullable.eq_ignore_ascii_case("YES"))
+            .unwrap_or(true);
+        column.primary_key = row_i64(row, "is_primary").unwrap_or(0) != 0;
+        column.unique = row_i64(row, "is_unique").unwrap_or(0) != 0;
+        column.comment = row_string(row, "column_comment");
+        column.default_value = row_string(row, "column_default")
+            .and_then(|raw| parse_default(self.db_type, &raw, &column.column_type));
+        column
+    }
+}
+
+fn schema_table_params(schema: &str, table: &str) -> Vec<RdbcValue> {
+    vec![RdbcValue::from(schema), RdbcValue::from(table)]
+}
+
+/// Value of `key`, matched case-insensitively; NULL counts as missing.
+fn row_value<'a>(row: &'a HashMap<String, RdbcValue>, key: &str) -> Option<&'a RdbcValue> {
+    let value = match row.get(key) {
+        Some(value) => Some(value),
+        None => row
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value),
+    };
+    value.filter(|value| !matches!(value, RdbcValue::Null))
+}
+
+pub(crate) fn row_string(row: &HashMap<String, RdbcValue>, key: &str) -> Option<String> {
+    row_value(row, key).map(|value| value.to_string())
+}
+
+pub(crate) fn row_i64(row: &HashMap<String, RdbcValue>, key: &str) -> Option<i64> {
+    match row_value(row, key)? {
+        RdbcValue::SmallInt(v) => Some(*v as i64),
+        RdbcValue::Int(v) => Some(*v as i64),
+        RdbcValue::BigInt(v) => Some(*v),
+        RdbcValue::Double(v) => Some(*v as i64),
+        RdbcValue::BigDouble(v) => Some(*v as i64),
+        RdbcValue::Boolean(v) => Some(*v as i64),
+        value => value.to_string().trim().parse().ok(),
+    }
+}
+
+/// Predicate after the last `WHERE` of a `CREATE INDEX` statement.
+fn index_sql_predicate(sql: &str) -> String {
+    match sql.to_uppercase().rfind(" WHERE ") {
+        Some(position) => sql[position + 7..].trim().to_string(),
+        None => sql.to_string(),
+    }
+}
+
+/// Maps a declared type such as `VARCHAR(64)`, `decimal(10,2)` or
+/// `timestamp with time zone` to the column type it was rendered from.
+pub(crate) fn parse_declared_type(declared: &str) -> RdbcColumnType {
+    let upper = declared.trim().to_uppercase();
+    let (name, args) = match upper.find('(') {
+        Some(open) => {
+            let close = upper.rfind(')').unwrap_or(upper.len());
+            let args: Vec<u32> = upper[open + 1..close]
+                .split(',')
+                .filter_map(|arg| arg.trim().parse().ok())
+                .collect();
+            let rest = upper[(close + 1).min(upper.len())..].trim();
+            (format!("{} {}", upper[..open].trim(), rest), args)
+        }
+        None => (upper.clone(), vec![]),
+    };
+    match (name.trim(), args.as_slice()) {
+        ("CHAR" | "CHARACTER" | "NCHAR", [length]) => RdbcColumnType::Char(*length),
+        ("VARCHAR" | "CHARACTER VARYING" | "VARCHAR2" | "NVARCHAR" | "NVARCHAR2", [length]) => {
+            RdbcColumnType::Varchar(*length)
+        }
+        ("TEXT" | "CLOB" | "MEDIUMTEXT", []) => RdbcColumnType::Text,
+        ("LONGTEXT", []) => RdbcColumnType::LongText,
+        ("TINYINT", [1]) | ("BOOLEAN" | "BOOL", _) => RdbcColumnType::Boolean,
+        ("SMALLINT" | "INT2" | "TINYINT", _) => RdbcColumnType::SmallInt,
+        ("INT" | "INTEGER" | "INT4" | "MEDIUMINT", _) => RdbcColumnType::Int,
+        ("BIGINT" | "INT8", _) => RdbcColumnType::BigInt,
+        ("REAL" | "FLOAT" | "FLOAT4", _) => RdbcColumnType::Float,
+        ("DOUBLE" | "DOUBLE PRECISION" | "FLOAT8", _) => RdbcColumnType::Double,
+        ("DECIMAL" | "NUMERIC", [precision]) => RdbcColumnType::Decimal(*precision, 0),
+        ("DECIMAL" | "NUMERIC", [precision, scale]) => RdbcColumnType::Decimal(*precision, *scale),
+        ("DATE", []) => RdbcColumnType::Date,
+        ("DATETIME" | "TIMESTAMP WITHOUT TIME ZONE", _) => RdbcColumnType::DateTime,
+        ("TIMESTAMP" | "TIMESTAMPTZ" | "TIMESTAMP WITH TIME ZONE", _) => RdbcColumnType::Timestamp,
+        ("TIME" | "TIME WITHOUT TIME ZONE", _) => RdbcColumnType::Time,
+        ("BLOB" | "BYTEA" | "LONGBLOB" | "MEDIUMBLOB", []) => RdbcColumnType::Bytes,
+        ("JSON" | "JSONB", []) => RdbcColumnType::Json,
+        ("UUID", []) => RdbcColumnType::Uuid,
+        _ => RdbcColumnType::Custom(declared.trim().to_string()),
+    }
+}
+
+fn parse_oracle_type(
+    data_type: &str,
+    char_length: Option<i64>,
+    precision: Option<i64>,
+    scale: Option<i64>,
+) -> RdbcColumnType {
+    let upper = data_type.to_uppercase();
+    match upper.as_str() {
+        "VARCHAR2" | "NVARCHAR2" => RdbcColumnType::Varchar(char_length.unwrap_or(0) as u32),
+        "CHAR" | "NCHAR" => RdbcColumnType::Char(char_length.unwrap_or(0) as u32),
+        "CLOB" | "NCLOB" => RdbcColumnType::Text,
+        "BLOB" => RdbcColumnType::Bytes,
+        "DATE" => RdbcColumnType::Date,
+        "BINARY_FLOAT" => RdbcColumnType::Float,
+        "BINARY_DOUBLE" => RdbcColumnType::Double,
+        "NUMBER" => match (precision, scale.unwrap_or(0)) {
+            (Some(1), 0) => RdbcColumnType::Boolean,
+            (Some(5), 0) => RdbcColumnType::SmallInt,
+            (Some(10), 0) => RdbcColumnType::Int,
+            (Some(19), 0) => RdbcColumnType::BigInt,
+            (Some(precision), scale) => RdbcColumnType::Decimal(precision as u32, scale as u32),
+            (None, _) => RdbcColumnType::Custom("NUMBER".to_string()),
+        },
+        _ if upper.starts_with("TIMESTAMP") && upper.ends_with("WITH TIME ZONE") => {
+            RdbcColumnType::Timestamp
+        }
+        _ if upper.starts_with("TIMESTAMP") => RdbcColumnType::DateTime,
+        _ if upper.starts_with("INTERVAL DAY") => RdbcColumnType::Time,
+        _ => RdbcColumnType::Custom(data_type.to_string()),
+    }
+}
+
+/// Turns a reported column default back into a literal value where possible,
+/// keeping anything else as an expression.
+pub(crate) fn parse_default(
+    db_type: DataBase,
+    raw: &str,
+    column_type: &RdbcColumnType,
+) -> Option<ColumnDefault> {
+    let mut text = raw.trim();
+    if text.is_empty() || text.eq_ignore_ascii_case("NULL") {
+        return None;
+    }
+    if db_type == DataBase::Postgres {
+        // 'abc'::character varying, (0)::numeric
+        while let Some(position) = text.rfind("::") {
+            let head = text[..position].trim();
+            if head.ends_with('\'') || head.ends_with(')') || head.parse::<f64>().is_ok() {
+                text = head;
+            } else {
+                break;
+            }
+        }
+        if text.starts_with('(') && text.ends_with(')') {
+            text = text[1..text.len() - 1].trim();
+        }
+    }
+    let is_text = matches!(
+        column_type,
+        RdbcColumnType::Char(_)
+            | RdbcColumnType::Varchar(_)
+            | RdbcColumnType::Text
+            | RdbcColumnType::LongText
+            | RdbcColumnType::Uuid
+    );
+    if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+        let value = text[1..text.len() - 1].replace("''", "'");
+        return Some(ColumnDefault::Value(RdbcValue::Varchar(value)));
+    }
+    if *column_type == RdbcColumnType::Boolean {
+        match text.to_uppercase().as_str() {
+            "1" | "TRUE" => return Some(ColumnDefault::Value(RdbcValue::Boolean(true))),
+            "0" | "FALSE" => return Some(ColumnDefault::Value(RdbcValue::Boolean(false))),
+            _ => {}
+        }
+    }
+    if !is_text {
+        if let Ok(value) = text.parse::<i64>() {
+            return Some(ColumnDefault::Value(RdbcValue::BigInt(value)));
+        }
+        if let Ok(value) = text.parse::<f64>() {
+            return Some(ColumnDefault::Value(RdbcValue::BigDouble(value)));
+        }
+    }
+    let upper = text.to_uppercase();
+    let is_expression = upper.starts_with("CURRENT_")
+        || upper.starts_with("LOCALTIMESTAMP")
+        || upper.starts_with("SYSDATE")
+        || upper.starts_with("SYSTIMESTAMP")
+        || upper.contains('(');
+    if db_type == DataBase::MySql && is_text && !is_expression {
+        // MySQL reports string defaults without quotes.
+        return Some(ColumnDefault::Value(RdbcValue::Varchar(text.to_string())));
+    }
+    Some(ColumnDefault::Expression(text.to_string()))
+}
@@ -0,0 +1,7 @@
+mod diff;
+mod introspect;
+
+pub use diff::*;
+pub use introspect::*;
+
+pub(crate) use introspect::{row_i64, row_string};
@@ -0,0 +1,362 @@
+use crate::{RdbcError, RdbcResult, RdbcValue};
+use std::cmp::Ordering;
+
+/// Parsed `test` expression of `<if>` and `<when>` tags.
+///
+/// Supports the OGNL subset commonly used in MyBatis mappers: `null`,
+/// booleans, numbers, quoted strings, dotted param paths with `size()`,
+/// `length()` and `isEmpty()`, comparisons (`== != < <= > >=` and their
+/// `eq neq lt lte gt gte` spellings), `and`/`&&`, `or`/`||`, `not`/`!` and
+/// parentheses.
+#[derive(Debug, Clone)]
+pub enum TestExpr {
+    Literal(RdbcValue),
+    Path(String),
+    Size(String),
+    IsEmpty(String),
+    Not(Box<TestExpr>),
+    And(Box<TestExpr>, Box<TestExpr>),
+    Or(Box<TestExpr>, Box<TestExpr>),
+    Compare(Box<TestExpr>, CompareOp, Box<TestExpr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+impl TestExpr {
+    pub fn parse(text: &str) -> RdbcResult<TestExpr> {
+        let tokens = tokenize(text)?;
+        let mut parser = ExprParser {
+            tokens,
+            pos: 0,
+            text,
+        };
+        let expr = parser.parse_or()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(parser.error("unexpected trailing tokens"));
+        }
+        Ok(expr)
+    }
+
+    /// Evaluates the expression, resolving paths with `lookup`.
+    pub fn eval<F>(&self, lookup: &F) -> RdbcValue
+    where
+        F: Fn(&str) -> Option<RdbcValue>,
+    {
+        match self {
+            TestExpr::Literal(v) => v.clone(),
+            TestExpr::Path(path) => lookup(path).unwrap_or(RdbcValue::Null),
+            TestExpr::Size(path) => RdbcValue::BigInt(size_of(&lookup(path))),
+            TestExpr::IsEmpty(path) => RdbcValue::Boolean(size_of(&lookup(path)) == 0),
+            TestExpr::Not(e) => RdbcValue::Boolean(!is_truthy(&e.eval(lookup))),
+            TestExpr::And(l, r) => {
+                RdbcValue::Boolean(is_truthy(&l.eval(lookup)) && is_truthy(&r.eval(lookup)))
+            }
+            TestExpr::Or(l, r) => {
+                RdbcValue::Boolean(is_truthy(&l.eval(lookup)) || is_truthy(&r.eval(lookup)))
+            }
+            TestExpr::Compare(l, op, r) => {
+                let left = l.eval(lookup);
+                let right = r.eval(lookup);
+                RdbcValue::Boolean(compare(&left, *op, &right))
+            }
+        }
+    }
+
+    pub fn test<F>(&self, lookup: &F) -> bool
+    where
+        F: Fn(&str) -> Option<RdbcValue>,
+    {
+        is_truthy(&self.eval(lookup))
+    }
+}
+
+fn size_of(value: &Option<RdbcValue>) -> i64 {
+    match value {
+        Some(RdbcValue::Array(v)) => v.len() as i64,
+        Some(RdbcValue::Object(v)) => v.len() as i64,
+        Some(RdbcValue::Null) | None => 0,
+        Some(v) => match v.as_string() {
+            Some(s) => s.chars().count() as i64,
+            None => 1,
+        },
+    }
+}
+
+fn is_truthy(value: &RdbcValue) -> bool {
+    match value {
+        RdbcValue::Null => false,
+        RdbcValue::Boolean(v) => *v,
+        RdbcValue::Char(_) => true,
+        RdbcValue::Varchar(v) | RdbcValue::Text(v) | RdbcValue::LongText(v) => !v.is_empty(),
+        v if v.is_number() => to_f64(v).map(|n| n != 0.0).unwrap_or(false),
+        _ => true,
+    }
+}
+
+fn to_f64(value: &RdbcValue) -> Option<f64> {
+    match value {
+        RdbcValue::SmallInt(v) => Some(*v as f64),
+        RdbcValue::Int(v) => Some(*v as f64),
+        RdbcValue::BigInt(v) => Some(*v as f64),
+        RdbcValue::Double(v) => Some(*v as f64),
+        RdbcValue::BigDouble(v) => Some(*v),
+        RdbcValue::TimeStamp(v) => Some(*v as f64),
+        v => v.as_string().and_then(|s| s.trim().parse::<f64>().ok()),
+    }
+}
+
+fn compare(left: &RdbcValue, op: CompareOp, right: &RdbcValue) -> bool {
+    if left.is_null() || right.is_null() {
+        let both = left.is_null() && right.is_null();
+        return match op {
+            CompareOp::Eq => both,
+            CompareOp::Ne => !both,
+            _ => false,
+        };
+    }
+    let ordering = match (left, right) {
+        (RdbcValue::Boolean(l), RdbcValue::Boolean(r)) => Some(l.cmp(r)),
+        (l, r) if l.is_number() || r.is_number() => match (to_f64(l), to_f64(r)) {
+            (Some(l), Some(r)) => l.partial_cmp(&r),
+            _ => None,
+        },
+        (l, r) => Some(l.to_string().cmp(&r.to_string())),
+    };
+    match ordering {
+        Some(ordering) => match op {
+            CompareOp::Eq => ordering == Ordering::Equal,
+            CompareOp::Ne => ordering != Ordering::Equal,
+            CompareOp::Lt => ordering == Ordering::Less,
+            CompareOp::Le => ordering != Ordering::Greater,
+            CompareOp::Gt => ordering == Ordering::Greater,
+            CompareOp::Ge => ordering != Ordering::Less,
+        },
+        None => op == CompareOp::Ne,
+    }
+}
+
+fn tokenize(text: &str) -> RdbcResult<Vec<Token>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        match c {
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '\'' | '"' => {
+                let mut value = String::new();
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    value.push(chars[i]);
+                    i += 1;
+                }
+                if i == chars.len() {
+                    return Err(RdbcError::Template(format!(
+                        "unterminated string in test \"{}\"",
+                        text
+                    )));
+                }
+                i += 1;
+                tokens.push(Token::Str(value));
+            }
+            '=' | '!' | '<' | '>' | '&' | '|' => {
+                let next = chars.get(i + 1).copied();
+                let (op, len) = match (c, next) {
+                    ('=', Some('=')) => ("==", 2),
+                    ('!', Some('=')) => ("!=", 2),
+                    ('<', Some('=')) => ("<=", 2),
+                    ('>', Some('=')) => (">=", 2),
+                    ('&', Some('&')) => ("and", 2),
+                    ('|', Some('|')) => ("or", 2),
+                    ('!', _) => ("not", 1),
+                    ('<', _) => ("<", 1),
+                    ('>', _) => (">", 1),
+                    _ => {
+                        return Err(RdbcError::Template(format!(
+                            "unexpected '{}' in test \"{}\"",
+                            c, text
+                        )))
+                    }
+                };
+                tokens.push(Token::Op(op));
+                i += len;
+            }
+            c if c.is_ascii_digit() || (c == '-' && next_is_digit(&chars, i)) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let number: String = chars[start..i].iter().collect();
+                let value = number.parse::<f64>().map_err(|_| {
+                    RdbcError::Template(format!("invalid number {} in test \"{}\"", number, text))
+                })?;
+                tokens.push(Token::Num(value));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    i += 1;
+                }
+                let mut ident: String = chars[start..i].iter().collect();
+                // `size()`-style calls are kept on the identifier.
+                if i + 1 < chars.len() && chars[i] == '(' && chars[i + 1] == ')' {
+                    ident.push_str("()");
+                    i += 2;
+                }
+                let token = match ident.as_str() {
+                    "and" => Token::Op("and"),
+                    "or" => Token::Op("or"),
+                    "not" => Token::Op("not"),
+                    "eq" => Token::Op("=="),
+                    "neq" => Token::Op("!="),
+                    "lt" => Token::Op("<"),
+                    "lte" => Token::Op("<="),
+                    "gt" => Token::Op(">"),
+                    "gte" => Token::Op(">="),
+                    _ => Token::Ident(ident),
+                };
+                tokens.push(token);
+            }
+            _ => {
+                return Err(RdbcError::Template(format!(
+                    "unexpected '{}' in test \"{}\"",
+                    c, text
+                )))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn next_is_digit(chars: &[char], i: usize) -> bool {
+    chars
+        .get(i + 1)
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(false)
+}
+
+struct ExprParser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    text: &'a str,
+}
+
+impl ExprParser<'_> {
+    fn error(&self, msg: &str) -> RdbcError {
+        RdbcError::Template(format!("{} in test \"{}\"", msg, self.text))
+    }
+    fn peek_op(&self, op: &str) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token::Op(o)) if *o == op)
+    }
+    fn parse_or(&mut self) -> RdbcResult<TestExpr> {
+        let mut left = self.parse_and()?;
+        while self.peek_op("or") {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = TestExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+    fn parse_and(&mut self) -> RdbcResult<TestExpr> {
+        let mut left = self.parse_not()?;
+        while self.peek_op("and") {
+            self.pos += 1;
+            let right = self.parse_not()?;
+            left = TestExpr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+    fn parse_not(&mut self) -> RdbcResult<TestExpr> {
+        if self.peek_op("not") {
+            self.pos += 1;
+            return Ok(TestExpr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_compare()
+    }
+    fn parse_compare(&mut self) -> RdbcResult<TestExpr> {
+        let left = self.parse_primary()?;
+        let op = match self.tokens.get(self.pos) {
+            Some(Token::Op("==")) => CompareOp::Eq,
+            Some(Token::Op("!=")) => CompareOp::Ne,
+            Some(Token::Op("<")) => CompareOp::Lt,
+            Some(Token::Op("<=")) => CompareOp::Le,
+            Some(Token::Op(">")) => CompareOp::Gt,
+            Some(Token::Op(">=")) => CompareOp::Ge,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.parse_primary()?;
+        Ok(TestExpr::Compare(Box::new(left), op, Box::new(right)))
+    }
+    fn parse_primary(&mut self) -> RdbcResult<TestExpr> {
+        let token = match self.tokens.get(self.pos) {
+            Some(token) => token.clone(),
+            None => return Err(self.error("unexpected end")),
+        };
+        self.pos += 1;
+        match token {
+            Token::LParen => {
+                let expr = self.parse_or()?;
+                if self.tokens.get(self.pos) != Some(&Token::RParen) {
+                    return Err(self.error("missing ')'"));
+                }
+                self.pos += 1;
+                Ok(expr)
+            }
+            Token::Str(v) => Ok(TestExpr::Literal(RdbcValue::from(v))),
+            Token::Num(v) => Ok(TestExpr::Literal(RdbcValue::BigDouble(v))),
+            Token::Ident(ident) => Ok(match ident.as_str() {
+                "null" => TestExpr::Literal(RdbcValue::Null),
+                "true" => TestExpr::Literal(RdbcValue::Boolean(true)),
+                "false" => TestExpr::Literal(RdbcValue::Boolean(false)),
+                _ => {
+                    if let Some(path) = ident
+                        .strip_suffix(".size()")
+                        .or_else(|| ident.strip_suffix(".length()"))
+                    {
+                        TestExpr::Size(path.to_string())
+                    } else if let Some(path) = ident.strip_suffix(".isEmpty()") {
+                        TestExpr::IsEmpty(path.to_string())
+                    } else if ident.ends_with("()") {
+                        return Err(self.error(&format!("unsupported call {}", ident)));
+                    } else {
+                        TestExpr::Path(ident)
+                    }
+                }
+            }),
+            _ => Err(self.error("unexpected operator")),
+        }
+    }
+}
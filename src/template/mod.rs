@@ -0,0 +1,340 @@
+mod expr;
+mod parser;
+mod registry;
+
+pub use registry::*;
+
+use crate::template::parser::{parse_template, SqlNode};
+use crate::{extract_map_params, render_script, DataBase, RdbcError, RdbcResult, RdbcValue};
+use std::collections::{HashMap, HashSet};
+
+/// MyBatis-style dynamic SQL template.
+///
+/// Supports `<if test>`, `<choose>/<when>/<otherwise>`, `<where>`, `<set>`,
+/// `<trim>` and `<foreach>` around SQL text with `#{path}` bound params and
+/// `${path}` raw substitutions. Raw substitutions are only accepted for keys
+/// registered with [`RdbcSqlTemplate::allow_raw`] and values in that allowlist.
+#[derive(Debug, Clone)]
+pub struct RdbcSqlTemplate {
+    nodes: Vec<SqlNode>,
+    raw_allowlist: HashMap<String, HashSet<String>>,
+}
+
+impl RdbcSqlTemplate {
+    pub fn parse(template: &str) -> RdbcResult<Self> {
+        Ok(RdbcSqlTemplate {
+            nodes: parse_template(template)?,
+            raw_allowlist: HashMap::new(),
+        })
+    }
+
+    /// Allows `${key}` to be substituted with any of `values`.
+    pub fn allow_raw<K, I, V>(&mut self, key: K, values: I) -> &mut Self
+    where
+        K: ToString,
+        I: IntoIterator<Item = V>,
+        V: ToString,
+    {
+        self.raw_allowlist
+            .entry(key.to_string())
+            .or_default()
+            .extend(values.into_iter().map(|v| v.to_string()));
+        self
+    }
+
+    /// Renders the template to SQL with positional placeholders for `db_type`.
+    pub fn render(
+        &self,
+        params: &HashMap<String, RdbcValue>,
+        db_type: DataBase,
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        let (sql, params_map) = self.render_script(params)?;
+        render_script(sql.as_str(), &params_map, db_type)
+    }
+
+    /// Renders the template to a `#{}` script with its bound params.
+    pub fn render_script(
+        &self,
+        params: &HashMap<String, RdbcValue>,
+    ) -> RdbcResult<(String, HashMap<String, RdbcValue>)> {
+        let mut context = RenderContext {
+            scopes: vec![extract_map_params(params)],
+            params: HashMap::new(),
+            raw_allowlist: &self.raw_allowlist,
+        };
+        let sql = context.render_nodes(&self.nodes)?;
+        Ok((compact_sql(&sql), context.params))
+    }
+}
+
+struct RenderContext<'a> {
+    /// Flattened params; foreach items push a scope of their own.
+    scopes: Vec<HashMap<String, RdbcValue>>,
+    params: HashMap<String, RdbcValue>,
+    raw_allowlist: &'a HashMap<String, HashSet<String>>,
+}
+
+impl RenderContext<'_> {
+    fn lookup(&self, path: &str) -> Option<RdbcValue> {
+        let prefix = format!("{}.", path);
+        for scope in self.scopes.iter().rev() {
+            if let Some(value) = scope.get(path) {
+                return Some(value.clone());
+            }
+            // Objects are flattened away; rebuild the nested keys one level deep.
+            let nested: HashMap<String, RdbcValue> = scope
+                .iter()
+                .filter_map(|(k, v)| {
+                    k.strip_prefix(prefix.as_str())
+                        .map(|rest| (rest.to_string(), v.clone()))
+                })
+                .collect();
+            if !nested.is_empty() {
+                return Some(RdbcValue::Object(nested));
+            }
+        }
+        None
+    }
+
+    fn render_nodes(&mut self, nodes: &[SqlNode]) -> RdbcResult<String> {
+        let mut sql = String::new();
+        for node in nodes {
+            let part = self.render_node(node)?;
+            if !part.is_empty() {
+                sql.push(' ');
+                sql.push_str(part.as_str());
+            }
+        }
+        Ok(sql)
+    }
+
+    fn render_node(&mut self, node: &SqlNode) -> RdbcResult<String> {
+        match node {
+            SqlNode::Text(text) => self.render_text(text),
+            SqlNode::If { test, children } => {
+                if test.test(&|path| self.lookup(path)) {
+                    self.render_nodes(children)
+                } else {
+                    Ok("".to_string())
+                }
+            }
+            SqlNode::Choose { whens, otherwise } => {
+                for (test, children) in whens {
+                    if test.test(&|path| self.lookup(path)) {
+                        return self.render_nodes(children);
+                    }
+                }
+                match otherwise {
+                    Some(children) => self.render_nodes(children),
+                    None => Ok("".to_string()),
+                }
+            }
+            SqlNode::When { .. } | SqlNode::Otherwise(_) => Err(RdbcError::Template(
+                "<when> and <otherwise> must be placed inside <choose>".to_string(),
+            )),
+            SqlNode::Trim {
+                prefix,
+                suffix,
+                prefix_overrides,
+                suffix_overrides,
+                children,
+            } => {
+                let body = self.render_nodes(children)?;
+                Ok(trim_body(
+                    &body,
+                    prefix,
+                    suffix,
+                    prefix_overrides,
+                    suffix_overrides,
+                ))
+            }
+            SqlNode::Foreach {
+                collection,
+                item,
+                index,
+                open,
+                separator,
+                close,
+                children,
+            } => {
+                let items: Vec<(RdbcValue, RdbcValue)> = match self.lookup(collection) {
+                    Some(RdbcValue::Array(items)) => items
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, v)| (RdbcValue::from(i), v))
+                        .collect(),
+                    Some(RdbcValue::Object(map)) => {
+                        let mut entries: Vec<(String, RdbcValue)> = map.into_iter().collect();
+                        entries.sort_by(|a, b| a.0.cmp(&b.0));
+                        entries
+                            .into_iter()
+                            .map(|(k, v)| (RdbcValue::from(k), v))
+                            .collect()
+                    }
+                    Some(RdbcValue::Null) | None => vec![],
+                    Some(v) => vec![(RdbcValue::from(0), v)],
+                };
+                if items.is_empty() {
+                    return Ok("".to_string());
+                }
+                let mut parts = vec![];
+                for (key, value) in items {
+                    let mut scope = HashMap::new();
+                    scope.insert(item.clone(), value);
+                    scope.insert(index.clone(), key);
+                    self.scopes.push(extract_map_params(&scope));
+                    let part = self.render_nodes(children);
+                    self.scopes.pop();
+                    parts.push(part?.trim().to_string());
+                }
+                Ok(format!("{}{}{}", open, parts.join(separator), close))
+            }
+        }
+    }
+
+    /// Rewrites `#{path}` to uniquely named params and expands `${path}`.
+    fn render_text(&mut self, text: &str) -> RdbcResult<String> {
+        let mut sql = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find(['#', '$']) {
+            if rest[start + 1..].starts_with('{') {
+                let end = match rest[start..].find('}') {
+                    Some(end) => start + end,
+                    None => break,
+                };
+                sql.push_str(&rest[..start]);
+                // `#{id,jdbcType=VARCHAR}` style options are ignored.
+                let path = rest[start + 2..end].split(',').next().unwrap_or("").trim();
+                let value = self
+                    .lookup(path)
+                    .ok_or_else(|| RdbcError::UnknownParam(path.to_string()))?;
+                if rest[start..].starts_with('#') {
+                    let value_id = uuid::Uuid::new_v4().to_string();
+                    sql.push_str(format!("#{{{}}}", value_id).as_str());
+                    self.params.insert(value_id, value);
+                } else {
+                    sql.push_str(self.raw_value(path, &value)?.as_str());
+                }
+                rest = &rest[end + 1..];
+            } else {
+                sql.push_str(&rest[..start + 1]);
+                rest = &rest[start + 1..];
+            }
+        }
+        sql.push_str(rest);
+        Ok(sql.trim().to_string())
+    }
+
+    fn raw_value(&self, path: &str, value: &RdbcValue) -> RdbcResult<String> {
+        let raw = value.to_string();
+        match self.raw_allowlist.get(path) {
+            Some(allowed) if allowed.contains(&raw) => Ok(raw),
+            _ => Err(RdbcError::InvalidParam(format!(
+                "${{{}}} value \"{}\" is not in the raw allowlist",
+                path, raw
+            ))),
+        }
+    }
+}
+
+fn trim_body(
+    body: &str,
+    prefix: &str,
+    suffix: &str,
+    prefix_overrides: &[String],
+    suffix_overrides: &[String],
+) -> String {
+    let mut body = body.trim();
+    for token in prefix_overrides {
+        if let Some(rest) = strip_keyword_prefix(body, token) {
+            body = rest.trim_start();
+            break;
+        }
+    }
+    for token in suffix_overrides {
+        if body.len() >= token.len()
+            && body.is_char_boundary(body.len() - token.len())
+            && body[body.len() - token.len()..].eq_ignore_ascii_case(token)
+        {
+            body = body[..body.len() - token.len()].trim_end();
+            break;
+        }
+    }
+    if body.is_empty() {
+        return "".to_string();
+    }
+    [prefix, body, suffix]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .copied()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Strips a leading keyword such as `AND`, only when it is a whole word.
+fn strip_keyword_prefix<'b>(body: &'b str, token: &str) -> Option<&'b str> {
+    if body.len() < token.len() || !body.is_char_boundary(token.len()) {
+        return None;
+    }
+    let (head, rest) = body.split_at(token.len());
+    if !head.eq_ignore_ascii_case(token) {
+        return None;
+    }
+    let word_token = token.chars().all(|c| c.is_alphanumeric());
+    match rest.chars().next() {
+        Some(c) if word_token && (c.is_alphanumeric() || c == '_') => None,
+        _ => Some(rest),
+    }
+}
+
+/// Collapses whitespace outside of quoted literals. `--` and `/* */`
+/// comments are dropped first, so that joining lines cannot pull the rest of
+/// the statement into a line comment; `/*+ */` optimizer hints are kept.
+fn compact_sql(sql: &str) -> String {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut compact = String::with_capacity(sql.len());
+    let mut quote: Option<char> = None;
+    let mut pending_space = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match quote {
+            Some(q) => {
+                compact.push(c);
+                if c == q {
+                    quote = None;
+                }
+            }
+            None if c.is_whitespace() => pending_space = true,
+            None if c == '-' && next == Some('-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                pending_space = true;
+                continue;
+            }
+            None if c == '/' && next == Some('*') && chars.get(i + 2) != Some(&'+') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                pending_space = true;
+                continue;
+            }
+            None => {
+                if pending_space && !compact.is_empty() {
+                    compact.push(' ');
+                }
+                pending_space = false;
+                if c == '\'' || c == '"' {
+                    quote = Some(c);
+                }
+                compact.push(c);
+            }
+        }
+        i += 1;
+    }
+    compact
+}
@@ -0,0 +1,279 @@
+use crate::template::expr::TestExpr;
+use crate::{RdbcError, RdbcResult};
+use std::collections::HashMap;
+
+/// Node of a parsed dynamic SQL template.
+#[derive(Debug, Clone)]
+pub enum SqlNode {
+    Text(String),
+    If {
+        test: TestExpr,
+        children: Vec<SqlNode>,
+    },
+    Choose {
+        whens: Vec<(TestExpr, Vec<SqlNode>)>,
+        otherwise: Option<Vec<SqlNode>>,
+    },
+    Trim {
+        prefix: String,
+        suffix: String,
+        prefix_overrides: Vec<String>,
+        suffix_overrides: Vec<String>,
+        children: Vec<SqlNode>,
+    },
+    /// `<when>`, only valid as a direct child of `<choose>`.
+    When {
+        test: TestExpr,
+        children: Vec<SqlNode>,
+    },
+    /// `<otherwise>`, only valid as a direct child of `<choose>`.
+    Otherwise(Vec<SqlNode>),
+    Foreach {
+        collection: String,
+        item: String,
+        index: String,
+        open: String,
+        separator: String,
+        close: String,
+        children: Vec<SqlNode>,
+    },
+}
+
+const TAGS: [&str; 12] = [
+    "if",
+    "choose",
+    "when",
+    "otherwise",
+    "where",
+    "set",
+    "trim",
+    "foreach",
+    "select",
+    "insert",
+    "update",
+    "delete",
+];
+
+pub fn parse_template(text: &str) -> RdbcResult<Vec<SqlNode>> {
+    let mut parser = TemplateParser { text, pos: 0 };
+    let (nodes, closing) = parser.parse_nodes()?;
+    if let Some(tag) = closing {
+        return Err(RdbcError::Template(format!("unexpected </{}>", tag)));
+    }
+    Ok(nodes)
+}
+
+struct TemplateParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> TemplateParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// Parses nodes until end of input or a closing tag, which is returned.
+    fn parse_nodes(&mut self) -> RdbcResult<(Vec<SqlNode>, Option<String>)> {
+        let mut nodes = vec![];
+        let mut text = String::new();
+        while self.pos < self.text.len() {
+            let rest = self.rest();
+            if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata
+                    .find("]]>")
+                    .ok_or_else(|| RdbcError::Template("unterminated CDATA".to_string()))?;
+                push_text(&mut nodes, &mut text);
+                nodes.push(SqlNode::Text(cdata[..end].to_string()));
+                self.pos += "<![CDATA[".len() + end + "]]>".len();
+                continue;
+            }
+            if let Some(tag) = Self::tag_at(rest, "</") {
+                push_text(&mut nodes, &mut text);
+                let end = rest
+                    .find('>')
+                    .ok_or_else(|| RdbcError::Template(format!("unterminated </{}", tag)))?;
+                self.pos += end + 1;
+                return Ok((nodes, Some(tag)));
+            }
+            if let Some(tag) = Self::tag_at(rest, "<") {
+                push_text(&mut nodes, &mut text);
+                nodes.extend(self.parse_element(tag)?);
+                continue;
+            }
+            let c = rest.chars().next().unwrap_or_default();
+            text.push(c);
+            self.pos += c.len_utf8();
+        }
+        push_text(&mut nodes, &mut text);
+        Ok((nodes, None))
+    }
+
+    /// Returns the known tag name starting at `rest` after `open`, if any.
+    fn tag_at(rest: &str, open: &str) -> Option<String> {
+        let after = rest.strip_prefix(open)?;
+        TAGS.iter()
+            .find(|tag| {
+                after.starts_with(*tag)
+                    && after[tag.len()..]
+                        .chars()
+                        .next()
+                        .map(|c| c.is_whitespace() || c == '>' || c == '/')
+                        .unwrap_or(false)
+            })
+            .map(|tag| tag.to_string())
+    }
+
+    fn parse_element(&mut self, tag: String) -> RdbcResult<Vec<SqlNode>> {
+        self.pos += 1 + tag.len();
+        let (attrs, self_closing) = self.parse_attrs(&tag)?;
+        let children = if self_closing {
+            vec![]
+        } else {
+            let (children, closing) = self.parse_nodes()?;
+            match closing {
+                Some(closing) if closing == tag => children,
+                Some(closing) => {
+                    return Err(RdbcError::Template(format!(
+                        "<{}> closed by </{}>",
+                        tag, closing
+                    )))
+                }
+                None => return Err(RdbcError::Template(format!("<{}> is not closed", tag))),
+            }
+        };
+        let attr = |name: &str| attrs.get(name).cloned().unwrap_or_default();
+        let node = match tag.as_str() {
+            "if" => SqlNode::If {
+                test: TestExpr::parse(&required(&attrs, &tag, "test")?)?,
+                children,
+            },
+            "choose" => {
+                let mut whens = vec![];
+                let mut otherwise = None;
+                for child in children {
+                    match child {
+                        SqlNode::When { test, children } => whens.push((test, children)),
+                        SqlNode::Otherwise(children) => otherwise = Some(children),
+                        SqlNode::Text(t) if t.trim().is_empty() => {}
+                        _ => {
+                            return Err(RdbcError::Template(
+                                "<choose> accepts only <when> and <otherwise>".to_string(),
+                            ))
+                        }
+                    }
+                }
+                SqlNode::Choose { whens, otherwise }
+            }
+            "when" => SqlNode::When {
+                test: TestExpr::parse(&required(&attrs, &tag, "test")?)?,
+                children,
+            },
+            "otherwise" => SqlNode::Otherwise(children),
+            "where" => SqlNode::Trim {
+                prefix: "WHERE".to_string(),
+                suffix: "".to_string(),
+                prefix_overrides: vec!["AND".to_string(), "OR".to_string()],
+                suffix_overrides: vec![],
+                children,
+            },
+            "set" => SqlNode::Trim {
+                prefix: "SET".to_string(),
+                suffix: "".to_string(),
+                prefix_overrides: vec![],
+                suffix_overrides: vec![",".to_string()],
+                children,
+            },
+            "trim" => SqlNode::Trim {
+                prefix: attr("prefix"),
+                suffix: attr("suffix"),
+                prefix_overrides: split_overrides(&attr("prefixOverrides")),
+                suffix_overrides: split_overrides(&attr("suffixOverrides")),
+                children,
+            },
+            "foreach" => SqlNode::Foreach {
+                collection: required(&attrs, &tag, "collection")?,
+                item: attrs
+                    .get("item")
+                    .cloned()
+                    .unwrap_or_else(|| "item".to_string()),
+                index: attrs
+                    .get("index")
+                    .cloned()
+                    .unwrap_or_else(|| "index".to_string()),
+                open: attr("open"),
+                separator: attr("separator"),
+                close: attr("close"),
+                children,
+            },
+            // Mapper statement tags are transparent containers.
+            _ => return Ok(children),
+        };
+        Ok(vec![node])
+    }
+
+    fn parse_attrs(&mut self, tag: &str) -> RdbcResult<(HashMap<String, String>, bool)> {
+        let mut attrs = HashMap::new();
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if let Some(after) = trimmed.strip_prefix("/>") {
+                self.pos = self.text.len() - after.len();
+                return Ok((attrs, true));
+            }
+            if let Some(after) = trimmed.strip_prefix('>') {
+                self.pos = self.text.len() - after.len();
+                return Ok((attrs, false));
+            }
+            let eq = trimmed
+                .find('=')
+                .ok_or_else(|| RdbcError::Template(format!("malformed attributes on <{}>", tag)))?;
+            let name = trimmed[..eq].trim().to_string();
+            let value_part = trimmed[eq + 1..].trim_start();
+            let quote = value_part
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'')
+                .ok_or_else(|| {
+                    RdbcError::Template(format!("attribute {} on <{}> must be quoted", name, tag))
+                })?;
+            let end = value_part[1..].find(quote).ok_or_else(|| {
+                RdbcError::Template(format!("unterminated attribute {} on <{}>", name, tag))
+            })?;
+            attrs.insert(name, decode_entities(&value_part[1..1 + end]));
+            let consumed = value_part[1 + end + 1..].len();
+            self.pos = self.text.len() - consumed;
+        }
+    }
+}
+
+fn required(attrs: &HashMap<String, String>, tag: &str, name: &str) -> RdbcResult<String> {
+    attrs
+        .get(name)
+        .cloned()
+        .ok_or_else(|| RdbcError::Template(format!("<{}> requires attribute {}", tag, name)))
+}
+
+fn split_overrides(value: &str) -> Vec<String> {
+    value
+        .split('|')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn push_text(nodes: &mut Vec<SqlNode>, text: &mut String) {
+    if !text.is_empty() {
+        nodes.push(SqlNode::Text(decode_entities(text)));
+        text.clear();
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
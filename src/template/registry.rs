@@ -0,0 +1,198 @@
+use crate::{DataBase, RdbcError, RdbcResult, RdbcSqlTemplate, RdbcValue};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Registry of named SQL statements loaded from `.sql` files.
+///
+/// Each statement starts with a `-- name: xxx` header line. An optional
+/// `-- dialect: postgres` line right after it marks a per-dialect override;
+/// a file named like `report.postgres.sql` applies that dialect to all of its
+/// statements. Statement bodies are parsed as [`RdbcSqlTemplate`], so `#{}`
+/// params and dynamic tags work the same as in templates.
+#[derive(Debug, Clone, Default)]
+pub struct RdbcSqlRegistry {
+    statements: HashMap<String, NamedSql>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct NamedSql {
+    default: Option<RdbcSqlTemplate>,
+    dialects: HashMap<DataBase, RdbcSqlTemplate>,
+}
+
+impl RdbcSqlRegistry {
+    pub fn new() -> Self {
+        RdbcSqlRegistry::default()
+    }
+
+    /// Loads every `.sql` file below `dir`, in file name order.
+    pub fn load_dir<P: AsRef<Path>>(&mut self, dir: P) -> RdbcResult<&mut Self> {
+        let dir = dir.as_ref();
+        let entries = fs::read_dir(dir)
+            .map_err(|e| RdbcError::Registry(format!("read dir {}: {}", dir.display(), e)))?;
+        let mut paths = vec![];
+        for entry in entries {
+            let entry = entry.map_err(|e| RdbcError::Registry(e.to_string()))?;
+            paths.push(entry.path());
+        }
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                self.load_dir(&path)?;
+            } else if path.extension().map(|e| e == "sql").unwrap_or(false) {
+                self.load_file(&path)?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Loads one `.sql` file; `name.<dialect>.sql` sets the file dialect.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> RdbcResult<&mut Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| RdbcError::Registry(format!("read file {}: {}", path.display(), e)))?;
+        let dialect = path
+            .file_stem()
+            .and_then(|stem| Path::new(stem).extension())
+            .and_then(|ext| DataBase::from_str(&ext.to_string_lossy()).ok());
+        self.load_str(&text, dialect)
+    }
+
+    /// Loads statements from SQL text with `-- name:` headers.
+    pub fn load_str(&mut self, text: &str, dialect: Option<DataBase>) -> RdbcResult<&mut Self> {
+        let mut current: Option<(String, Option<DataBase>, Vec<&str>)> = None;
+        for line in text.lines() {
+            let trimmed = line.trim();
+            if let Some(name) = header_value(trimmed, "name") {
+                if let Some((name, dialect, body)) = current.take() {
+                    self.add(name, body.join("\n"), dialect)?;
+                }
+                current = Some((name.to_string(), dialect, vec![]));
+                continue;
+            }
+            match current.as_mut() {
+                Some((_, statement_dialect, body)) => {
+                    if let Some(value) = header_value(trimmed, "dialect") {
+                        if body.is_empty() {
+                            *statement_dialect = Some(DataBase::from_str(value)?);
+                            continue;
+                        }
+                    }
+                    if !trimmed.starts_with("--") {
+                        body.push(line);
+                    }
+                }
+                None => {
+                    if !trimmed.is_empty() && !trimmed.starts_with("--") {
+                        return Err(RdbcError::Registry(
+                            "sql found before the first -- name: header".to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+        if let Some((name, dialect, body)) = current.take() {
+            self.add(name, body.join("\n"), dialect)?;
+        }
+        Ok(self)
+    }
+
+    /// Registers a statement, or a dialect override of it when `dialect` is set.
+    pub fn add<N, S>(&mut self, name: N, sql: S, dialect: Option<DataBase>) -> RdbcResult<&mut Self>
+    where
+        N: ToString,
+        S: AsRef<str>,
+    {
+        let name = name.to_string();
+        let sql = sql.as_ref().trim().trim_end_matches(';').trim_end();
+        if sql.is_empty() {
+            return Err(RdbcError::Registry(format!("statement {} is empty", name)));
+        }
+        let template = RdbcSqlTemplate::parse(sql)?;
+        let named = self.statements.entry(name.clone()).or_default();
+        let slot = match dialect {
+            Some(db_type) => named.dialects.get_mut(&db_type),
+            None => named.default.as_mut(),
+        };
+        if slot.is_some() {
+            return Err(RdbcError::Registry(match dialect {
+                Some(db_type) => format!("duplicate statement {} for {:?}", name, db_type),
+                None => format!("duplicate statement {}", name),
+            }));
+        }
+        match dialect {
+            Some(db_type) => {
+                named.dialects.insert(db_type, template);
+            }
+            None => named.default = Some(template),
+        }
+        Ok(self)
+    }
+
+    /// Allows `${key}` substitutions with `values` in every variant of `name`.
+    pub fn allow_raw<K, I, V>(&mut self, name: &str, key: K, values: I) -> RdbcResult<&mut Self>
+    where
+        K: ToString,
+        I: IntoIterator<Item = V>,
+        V: ToString,
+    {
+        let named = self
+            .statements
+            .get_mut(name)
+            .ok_or_else(|| RdbcError::Registry(format!("statement {} not found", name)))?;
+        let key = key.to_string();
+        let values: Vec<String> = values.into_iter().map(|v| v.to_string()).collect();
+        for template in named.default.iter_mut().chain(named.dialects.values_mut()) {
+            template.allow_raw(key.as_str(), values.iter());
+        }
+        Ok(self)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.statements.contains_key(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.statements.keys().map(|k| k.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    /// Returns the statement for `db_type`, falling back to the default one.
+    pub fn get(&self, name: &str, db_type: DataBase) -> RdbcResult<&RdbcSqlTemplate> {
+        let named = self
+            .statements
+            .get(name)
+            .ok_or_else(|| RdbcError::Registry(format!("statement {} not found", name)))?;
+        named
+            .dialects
+            .get(&db_type)
+            .or(named.default.as_ref())
+            .ok_or_else(|| {
+                RdbcError::Registry(format!("statement {} has no sql for {:?}", name, db_type))
+            })
+    }
+
+    pub fn render(
+        &self,
+        name: &str,
+        params: &HashMap<String, RdbcValue>,
+        db_type: DataBase,
+    ) -> RdbcResult<(String, Vec<RdbcValue>)> {
+        self.get(name, db_type)?.render(params, db_type)
+    }
+}
+
+/// Parses `-- key: value` header comments.
+fn header_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix("--")?.trim_start();
+    let rest = rest.strip_prefix(key)?.trim_start();
+    let value = rest.strip_prefix(':')?.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
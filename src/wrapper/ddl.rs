@@ -1,14 +1,290 @@
+use crate::{RdbcQueryWrapper, RdbcValue};
+
+/// Column data types understood by the DDL renderers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RdbcColumnType {
+    Char(u32),
+    Varchar(u32),
+    Text,
+    LongText,
+    SmallInt,
+    Int,
+    BigInt,
+    Float,
+    Double,
+    Decimal(u32, u32),
+    Boolean,
+    Date,
+    DateTime,
+    Time,
+    Timestamp,
+    Bytes,
+    Json,
+    Uuid,
+    /// Vendor type rendered verbatim.
+    Custom(String),
+}
+
+/// Default value of a column.
+#[derive(Debug, Clone)]
+pub enum ColumnDefault {
+    /// Literal value, rendered with the dialect's quoting.
+    Value(RdbcValue),
+    /// SQL expression such as `CURRENT_TIMESTAMP`, rendered verbatim.
+    Expression(String),
+}
+
+/// Identity or auto-increment numbering of a column.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnIdentity {
+    pub start: Option<i64>,
+    pub increment: Option<i64>,
+    /// `GENERATED ALWAYS` instead of `BY DEFAULT` where the dialect has both.
+    pub always: bool,
+}
+
+/// Column definition used by CREATE TABLE and ALTER TABLE.
 #[derive(Debug, Clone)]
-pub struct TableDDLWrapper {}
+pub struct ColumnDDLWrapper {
+    pub name: String,
+    pub column_type: RdbcColumnType,
+    pub nullable: bool,
+    pub default_value: Option<ColumnDefault>,
+    pub identity: Option<ColumnIdentity>,
+    pub primary_key: bool,
+    pub unique: bool,
+    pub check: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// Table level constraints.
 #[derive(Debug, Clone)]
-pub struct ColumnDDLWrapper {}
+pub enum TableConstraint {
+    PrimaryKey {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    Unique {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    Check {
+        name: Option<String>,
+        expression: String,
+    },
+    ForeignKey(ForeignKeyDDLWrapper),
+}
+
+/// Action taken on referencing rows when the referenced row changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferentialAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+/// FOREIGN KEY constraint.
+#[derive(Debug, Clone)]
+pub struct ForeignKeyDDLWrapper {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub ref_table: String,
+    pub ref_columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+}
+
+/// CREATE TABLE definition.
 #[derive(Debug, Clone)]
-pub struct CommentDDLWrapper {}
+pub struct TableDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub columns: Vec<ColumnDDLWrapper>,
+    pub constraints: Vec<TableConstraint>,
+    pub if_not_exists: bool,
+    pub comment: Option<String>,
+}
 
+/// A single ALTER TABLE change.
 #[derive(Debug, Clone)]
-pub struct IndexDDLWrapper {}
+pub enum AlterTableAction {
+    AddColumn(ColumnDDLWrapper),
+    DropColumn(String),
+    /// Changes the flagged aspects of an existing column to match `column`.
+    AlterColumn(AlterColumn),
+    RenameColumn {
+        from: String,
+        to: String,
+    },
+    RenameTable(String),
+    AddConstraint(TableConstraint),
+    DropConstraint(String),
+}
 
+/// Target definition of a changed column and which aspects changed.
 #[derive(Debug, Clone)]
-pub struct ViewDDLWrapper {}
+pub struct AlterColumn {
+    pub column: ColumnDDLWrapper,
+    pub change_type: bool,
+    pub change_nullable: bool,
+    pub change_default: bool,
+    pub change_unique: bool,
+}
 
-pub enum RdbcDdlWrapper {}
+/// ALTER TABLE statement, rendered as one statement per action.
+#[derive(Debug, Clone)]
+pub struct AlterTableDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub actions: Vec<AlterTableAction>,
+}
+
+/// DROP TABLE statement.
+#[derive(Debug, Clone)]
+pub struct DropTableDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub if_exists: bool,
+    pub cascade: bool,
+}
+
+/// Comment on a table, or on one of its columns when `column` is set.
+/// An empty comment removes the existing one.
+#[derive(Debug, Clone)]
+pub struct CommentDDLWrapper {
+    pub schema: String,
+    pub table: String,
+    pub column: Option<String>,
+    pub comment: String,
+    /// Full column definition, required by MySQL which restates the column
+    /// to change its comment.
+    pub column_definition: Option<ColumnDDLWrapper>,
+}
+
+/// Indexed column or expression.
+#[derive(Debug, Clone)]
+pub enum IndexColumn {
+    Column(String),
+    Expression(String),
+}
+
+/// CREATE INDEX statement.
+#[derive(Debug, Clone)]
+pub struct IndexDDLWrapper {
+    pub schema: String,
+    pub table: String,
+    pub name: String,
+    pub columns: Vec<IndexColumn>,
+    pub unique: bool,
+    /// Predicate of a partial index.
+    pub where_condition: Option<String>,
+    /// PostgreSQL `CONCURRENTLY`, ignored by the other dialects.
+    pub concurrently: bool,
+    pub if_not_exists: bool,
+}
+
+/// DROP INDEX statement. `table` is required by MySQL only.
+#[derive(Debug, Clone)]
+pub struct DropIndexDDLWrapper {
+    pub schema: String,
+    pub table: String,
+    pub name: String,
+    pub concurrently: bool,
+    pub if_exists: bool,
+}
+
+/// CREATE VIEW statement. The query params are inlined as literals.
+#[derive(Debug, Clone)]
+pub struct ViewDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub columns: Vec<String>,
+    pub query: RdbcQueryWrapper,
+    pub or_replace: bool,
+    /// PostgreSQL materialized view.
+    pub materialized: bool,
+}
+
+/// DROP VIEW statement.
+#[derive(Debug, Clone)]
+pub struct DropViewDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub materialized: bool,
+    pub if_exists: bool,
+    pub cascade: bool,
+}
+
+/// PostgreSQL `REFRESH MATERIALIZED VIEW`.
+#[derive(Debug, Clone)]
+pub struct RefreshViewDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub concurrently: bool,
+}
+
+/// CREATE SEQUENCE statement, for PostgreSQL and Oracle.
+#[derive(Debug, Clone)]
+pub struct SequenceDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub start: Option<i64>,
+    pub increment: Option<i64>,
+    pub min_value: Option<i64>,
+    pub max_value: Option<i64>,
+    pub cache: Option<i64>,
+    pub cycle: bool,
+    pub if_not_exists: bool,
+}
+
+/// ALTER SEQUENCE statement; unset options are left unchanged.
+#[derive(Debug, Clone)]
+pub struct AlterSequenceDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub restart: Option<i64>,
+    pub increment: Option<i64>,
+    pub min_value: Option<i64>,
+    pub max_value: Option<i64>,
+    pub cache: Option<i64>,
+    pub cycle: Option<bool>,
+}
+
+/// DROP SEQUENCE statement.
+#[derive(Debug, Clone)]
+pub struct DropSequenceDDLWrapper {
+    pub schema: String,
+    pub name: String,
+    pub if_exists: bool,
+    pub cascade: bool,
+}
+
+/// `INSERT INTO .. SELECT` copying the listed columns from one table into
+/// another, used when a table has to be rebuilt.
+#[derive(Debug, Clone)]
+pub struct CopyTableDDLWrapper {
+    pub schema: String,
+    pub from: String,
+    pub to: String,
+    pub columns: Vec<String>,
+}
+
+/// DDL statement accepted by `render_ddl`.
+#[derive(Debug, Clone)]
+pub enum RdbcDdlWrapper {
+    CreateTable(TableDDLWrapper),
+    AlterTable(AlterTableDDLWrapper),
+    DropTable(DropTableDDLWrapper),
+    CreateIndex(IndexDDLWrapper),
+    DropIndex(DropIndexDDLWrapper),
+    Comment(CommentDDLWrapper),
+    CreateView(ViewDDLWrapper),
+    DropView(DropViewDDLWrapper),
+    RefreshView(RefreshViewDDLWrapper),
+    CopyTable(CopyTableDDLWrapper),
+    CreateSequence(SequenceDDLWrapper),
+    AlterSequence(AlterSequenceDDLWrapper),
+    DropSequence(DropSequenceDDLWrapper),
+}
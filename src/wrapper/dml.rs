@@ -25,6 +25,7 @@ pub struct RdbcUpdateWrapper {
     pub(crate) limit_count: Option<u64>,
     pub(crate) offset_count: Option<u64>,
     pub(crate) params: HashMap<String, RdbcValue>,
+    pub(crate) allow_full_table: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -39,6 +40,7 @@ pub struct RdbcDeleteWrapper {
     pub(crate) limit_count: Option<u64>,
     pub(crate) offset_count: Option<u64>,
     pub(crate) params: HashMap<String, RdbcValue>,
+    pub(crate) allow_full_table: bool,
 }
 
 #[derive(Debug, Clone)]
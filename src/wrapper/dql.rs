@@ -23,6 +23,7 @@ pub enum RdbcTable {
     SchemaTable(SchemaTable),
     SQLTable(SQLTable),
     QueryTable(QueryTable),
+    RawTable(RawTable),
 }
 
 impl RdbcTable {
@@ -44,8 +45,17 @@ impl RdbcTable {
             RdbcTable::SchemaTable(schema_table) => schema_table.table_alias.clone(),
             RdbcTable::SQLTable(sql_table) => sql_table.table_alias.clone(),
             RdbcTable::QueryTable(query_table) => query_table.table_alias.clone(),
+            RdbcTable::RawTable(raw_table) => raw_table.table_alias.clone(),
         }
     }
+
+    /// Constructs a table from a raw SQL fragment, such as a set-returning function.
+    pub fn raw(raw: RdbcRawSql, alias: impl Into<String>) -> Self {
+        RdbcTable::RawTable(RawTable {
+            raw: Box::new(raw),
+            table_alias: alias.into(),
+        })
+    }
 }
 
 /// Schema table structure.
@@ -70,6 +80,40 @@ pub struct QueryTable {
     pub table_alias: String,
 }
 
+/// Raw SQL table structure with bound params.
+#[derive(Clone, Debug)]
+pub struct RawTable {
+    pub raw: Box<RdbcRawSql>,
+    pub table_alias: String,
+}
+
+/// Raw SQL fragment carrying its own `#{}` params.
+///
+/// Placeholders missing from `params` are left for the wrapper params.
+#[derive(Clone, Debug)]
+pub struct RdbcRawSql {
+    pub sql: String,
+    pub params: HashMap<String, RdbcValue>,
+}
+
+impl RdbcRawSql {
+    pub fn new(sql: impl Into<String>) -> Self {
+        RdbcRawSql {
+            sql: sql.into(),
+            params: HashMap::new(),
+        }
+    }
+    /// Adds a param referenced by `#{key}` in the fragment.
+    pub fn param<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: ToString,
+        RdbcValue: From<V>,
+    {
+        self.params.insert(key.to_string(), RdbcValue::from(value));
+        self
+    }
+}
+
 /// Join table structure for join operations.
 #[derive(Clone, Debug)]
 pub struct JoinTable {
@@ -108,6 +152,17 @@ pub enum RdbcColumn {
     QueryColumn(QueryColumn),
     FuncColumn(FuncColumn),
     ValueColumn(ValueColumn),
+    RawColumn(RawColumn),
+}
+
+impl RdbcColumn {
+    /// Constructs a column from a raw SQL expression.
+    pub fn raw(raw: RdbcRawSql, alias: impl Into<String>) -> Self {
+        RdbcColumn::RawColumn(RawColumn {
+            raw,
+            column_alias: alias.into(),
+        })
+    }
 }
 
 /// Table column representation.
@@ -141,6 +196,13 @@ pub struct ValueColumn {
     pub column_alias: String,
 }
 
+/// Raw SQL expression column representation.
+#[derive(Clone, Debug)]
+pub struct RawColumn {
+    pub raw: RdbcRawSql,
+    pub column_alias: String,
+}
+
 /// Supported SQL functions.
 #[derive(Clone, Debug)]
 pub enum RdbcFunc {
@@ -189,6 +251,7 @@ pub enum ConditionKind {
 pub enum ConditionColumn {
     Compare(CompareColumn),
     SubCondition(RdbcCondition),
+    Raw(Box<RdbcRawSql>),
 }
 
 /// Comparison column structure.
@@ -258,7 +321,22 @@ pub enum RdbcColumnValue {
     ColumnValue(RdbcColumn),
     StaticValue(RdbcValue),
     ScriptValue(String),
+    RawValue(Box<RdbcRawSql>),
     NullValue,
+    /// Next value of the named sequence, see [`RdbcColumnValue::next_val`].
+    NextVal(String),
+}
+
+impl RdbcColumnValue {
+    /// Next value of `sequence`: `nextval('seq')` on PostgreSQL and
+    /// `seq.NEXTVAL` on Oracle. MySQL and SQLite have no sequences and render
+    /// NULL, which makes an auto-increment column take its next value.
+    pub fn next_val<S>(sequence: S) -> Self
+    where
+        S: ToString,
+    {
+        RdbcColumnValue::NextVal(sequence.to_string())
+    }
 }
 
 /// Order by clause structure.
@@ -309,3 +387,21 @@ where
         RdbcColumnValue::StaticValue(RdbcValue::from(value))
     }
 }
+
+impl From<RdbcRawSql> for RdbcTable {
+    fn from(raw: RdbcRawSql) -> Self {
+        RdbcTable::raw(raw, "")
+    }
+}
+
+impl From<RdbcRawSql> for RdbcColumn {
+    fn from(raw: RdbcRawSql) -> Self {
+        RdbcColumn::raw(raw, "")
+    }
+}
+
+impl From<RdbcRawSql> for RdbcColumnValue {
+    fn from(raw: RdbcRawSql) -> Self {
+        RdbcColumnValue::RawValue(Box::new(raw))
+    }
+}
@@ -0,0 +1,28 @@
+//! Fixtures shared by the executor tests.
+#![allow(dead_code)]
+
+use bmbp_sql::{FromRdbcRow, RdbcInsertWrapper, RdbcValue};
+
+#[derive(FromRdbcRow)]
+pub struct Member {
+    pub id: i64,
+    pub name: String,
+    pub enabled: bool,
+    pub joined: Option<chrono::NaiveDate>,
+}
+
+/// Member row `id` of `table`, named `member{id}`.
+pub fn insert(table: &str, id: i64) -> RdbcInsertWrapper {
+    let mut insert = RdbcInsertWrapper::new(table);
+    insert
+        .value("id", id)
+        .value("name", format!("member{}", id));
+    insert
+}
+
+pub fn field<'a>(row: &'a RdbcValue, name: &str) -> &'a RdbcValue {
+    match row {
+        RdbcValue::Object(map) => &map[name],
+        _ => panic!("row is not an object"),
+    }
+}
@@ -0,0 +1,187 @@
+use bmbp_sql::{DataBase, RdbcDataSource, RdbcDataSourceConfig, RdbcError};
+
+#[cfg(feature = "config")]
+#[test]
+fn test_datasource_config() {
+    let config = RdbcDataSourceConfig::from_toml(
+        r#"
+        [datasources.main]
+        dialect = "postgresql"
+        host = "db.local"
+        port = 5433
+        database = "app"
+        user = "app"
+        password = "secret"
+        pool_min = 2
+        pool_max = 20
+        acquire_timeout = 5
+
+        [datasources.report]
+        dialect = "mysql"
+        database = "report"
+        "#,
+    )
+    .unwrap();
+    let main = &config.datasources["main"];
+    assert_eq!(main.dialect, DataBase::Postgres);
+    assert_eq!(main.port, Some(5433));
+    assert_eq!((main.pool_min, main.pool_max), (2, 20));
+    assert_eq!(main.acquire_timeout, Some(5));
+    let report = &config.datasources["report"];
+    assert_eq!(report.dialect, DataBase::MySql);
+    assert_eq!(report.host, "localhost");
+    assert_eq!(report.connect_timeout, Some(30));
+
+    let round_trip = RdbcDataSource::from_json(&serde_json::to_string(main).unwrap()).unwrap();
+    assert_eq!(&round_trip, main);
+
+    assert!(matches!(
+        RdbcDataSource::from_toml("dialect = \"db2\""),
+        Err(RdbcError::DataSource(_))
+    ));
+}
+
+#[test]
+fn test_datasource_json_and_env() {
+    let json =
+        RdbcDataSource::from_json(r#"{"dialect": "sqlite", "database": ":memory:"}"#).unwrap();
+    assert_eq!(json.dialect, DataBase::Sqlite);
+    assert_eq!(json.database, ":memory:");
+
+    std::env::set_var("BMBP_TEST_DS_MAIN_DIALECT", "pg");
+    std::env::set_var("BMBP_TEST_DS_MAIN_PORT", "6543");
+    std::env::set_var("BMBP_TEST_DS_MAIN_POOL_MAX", "4");
+    let env = RdbcDataSourceConfig::from_env("BMBP_TEST_DS", ["main"]).unwrap();
+    let main = &env.datasources["main"];
+    assert_eq!(main.dialect, DataBase::Postgres);
+    assert_eq!(main.port, Some(6543));
+    assert_eq!(main.pool_max, 4);
+    std::env::set_var("BMBP_TEST_DS_BAD_PORT", "x");
+    assert!(matches!(
+        RdbcDataSource::from_env("BMBP_TEST_DS_BAD"),
+        Err(RdbcError::DataSource(_))
+    ));
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_datasource_registry_errors() {
+    let mut registry = bmbp_sql::RdbcDataSourceRegistry::new();
+    assert!(matches!(
+        registry
+            .register("legacy", &RdbcDataSource::new(DataBase::Oracle))
+            .await,
+        Err(RdbcError::Unsupported(_))
+    ));
+    assert!(matches!(
+        registry.executor("main").await,
+        Err(RdbcError::DataSource(_))
+    ));
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs BMBP_SQL_PG_URL"]
+async fn test_datasource_registry() {
+    use bmbp_sql::{RdbcDataSourceRegistry, RdbcQueryWrapper, RdbcRawSql, RdbcValue};
+
+    // A scratch PostgreSQL database, see tests/test_pg_executor.rs.
+    let url = std::env::var("BMBP_SQL_PG_URL").expect("BMBP_SQL_PG_URL is not set");
+    let mut registry = RdbcDataSourceRegistry::new();
+    let pg: tokio_postgres::Config = url.parse().unwrap();
+    let mut data_source = RdbcDataSource::new(DataBase::Postgres);
+    if let Some(tokio_postgres::config::Host::Tcp(host)) = pg.get_hosts().first() {
+        data_source.host(host.as_str());
+    }
+    if let Some(port) = pg.get_ports().first() {
+        data_source.port(*port);
+    }
+    data_source
+        .user(pg.get_user().unwrap_or("postgres"))
+        .database(pg.get_dbname().unwrap_or("postgres"))
+        .pool_size(1, 2)
+        .acquire_timeout(5);
+    registry.register("main", &data_source).await.unwrap();
+    assert_eq!(registry.names(), vec!["main"]);
+    let pool = registry.pool("main").unwrap();
+    assert_eq!(pool.database(), DataBase::Postgres);
+
+    let executor = registry.executor("main").await.unwrap();
+    assert_eq!(executor.database(), DataBase::Postgres);
+    executor
+        .execute(&RdbcRawSql::new("DROP TABLE IF EXISTS t_datasource"))
+        .await
+        .unwrap();
+    executor
+        .execute(&RdbcRawSql::new("CREATE TABLE t_datasource (id BIGINT)"))
+        .await
+        .unwrap();
+    let other = registry.executor("main").await.unwrap();
+    let mut query = RdbcQueryWrapper::with_columns(["id"]);
+    query.from("t_datasource");
+    assert!(other.query_list(&query).await.unwrap().is_empty());
+    drop(other);
+    executor
+        .execute(&RdbcRawSql::new("DROP TABLE t_datasource"))
+        .await
+        .unwrap();
+    let row = executor
+        .query_one(&RdbcRawSql::new("SELECT 1::int8 AS one"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        row.as_object().unwrap()["one"],
+        RdbcValue::BigInt(1)
+    ));
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_datasource_registry_sqlite() {
+    use bmbp_sql::{RdbcDataSourceRegistry, RdbcQueryWrapper, RdbcRawSql};
+
+    let path = std::env::temp_dir().join(format!("bmbp_sql_{}.db", uuid::Uuid::new_v4()));
+    let mut data_source = RdbcDataSource::new(DataBase::Sqlite);
+    data_source.database(path.to_str().unwrap()).pool_size(1, 2);
+    let mut registry = RdbcDataSourceRegistry::new();
+    registry.register("local", &data_source).await.unwrap();
+    assert_eq!(registry.pool("local").unwrap().database(), DataBase::Sqlite);
+
+    let executor = registry.executor("local").await.unwrap();
+    assert_eq!(executor.database(), DataBase::Sqlite);
+    executor
+        .execute(&RdbcRawSql::new("CREATE TABLE t_datasource (id BIGINT)"))
+        .await
+        .unwrap();
+    executor
+        .execute(&RdbcRawSql::new("INSERT INTO t_datasource (id) VALUES (1)"))
+        .await
+        .unwrap();
+    let other = registry.executor("local").await.unwrap();
+    let mut query = RdbcQueryWrapper::with_columns(["id"]);
+    query.from("t_datasource");
+    assert_eq!(other.query_list(&query).await.unwrap().len(), 1);
+    drop((executor, other));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "mysql")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_datasource_mysql_checkout_timeout() {
+    // Accepts TCP connections but never sends the server handshake.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut data_source = RdbcDataSource::new(DataBase::MySql);
+    data_source
+        .host("127.0.0.1")
+        .port(listener.local_addr().unwrap().port())
+        .database("test")
+        .user("root")
+        .pool_size(1, 1)
+        .connect_timeout(1)
+        .acquire_timeout(0);
+    let started = std::time::Instant::now();
+    let result = bmbp_sql::RdbcPool::connect(&data_source).await;
+    assert!(matches!(result, Err(RdbcError::DataSource(_))));
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+}
@@ -0,0 +1,464 @@
+use bmbp_sql::{
+    render_ddl, render_insert, AlterSequenceDDLWrapper, AlterTableDDLWrapper, ColumnDDLWrapper,
+    CommentDDLWrapper, DataBase, DropIndexDDLWrapper, DropSequenceDDLWrapper, DropTableDDLWrapper,
+    DropViewDDLWrapper, IndexDDLWrapper, RdbcColumnType, RdbcColumnValue, RdbcError,
+    RdbcInsertWrapper, RdbcQueryWrapper, RdbcValue, RdbcWhereCondition, ReferentialAction,
+    RefreshViewDDLWrapper, SequenceDDLWrapper, TableDDLWrapper, ViewDDLWrapper,
+};
+
+fn user_table() -> TableDDLWrapper {
+    let mut table = TableDDLWrapper::new("t_user");
+    table.if_not_exists();
+    table.add_column("id", RdbcColumnType::BigInt).primary_key();
+    table
+        .add_column("name", RdbcColumnType::Varchar(64))
+        .not_null()
+        .default_value("it's");
+    table
+        .add_column("email", RdbcColumnType::Varchar(128))
+        .unique();
+    table
+        .add_column("age", RdbcColumnType::Int)
+        .check("age >= 0");
+    table
+        .add_column("enabled", RdbcColumnType::Boolean)
+        .default_value(true);
+    table
+        .add_column("create_time", RdbcColumnType::DateTime)
+        .default_expr("CURRENT_TIMESTAMP");
+    table.unique(&["name", "email"]);
+    table
+}
+
+#[test]
+pub fn test_create_table_per_dialect() {
+    let ddl = user_table().into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec![
+            "CREATE TABLE IF NOT EXISTS t_user (\n  \
+              id BIGINT NOT NULL,\n  \
+              name VARCHAR(64) DEFAULT 'it''s' NOT NULL,\n  \
+              email VARCHAR(128) UNIQUE,\n  \
+              age INTEGER CHECK (age >= 0),\n  \
+              enabled BOOLEAN DEFAULT TRUE,\n  \
+              create_time TIMESTAMP DEFAULT CURRENT_TIMESTAMP,\n  \
+              PRIMARY KEY (id),\n  \
+              UNIQUE (name, email)\n)"
+        ]
+    );
+    let oracle = render_ddl(&ddl, DataBase::Oracle).unwrap();
+    assert!(oracle[0].contains("id NUMBER(19) NOT NULL"));
+    assert!(oracle[0].contains("name VARCHAR2(64) DEFAULT 'it''s' NOT NULL"));
+    assert!(oracle[0].contains("enabled NUMBER(1) DEFAULT 1"));
+    let mysql = render_ddl(&ddl, DataBase::MySql).unwrap();
+    assert!(mysql[0].contains("create_time DATETIME DEFAULT CURRENT_TIMESTAMP"));
+    let sqlite = render_ddl(&ddl, DataBase::Sqlite).unwrap();
+    assert!(sqlite[0].contains("enabled BOOLEAN DEFAULT 1"));
+}
+
+#[test]
+pub fn test_table_and_column_primary_key() {
+    let mut table = TableDDLWrapper::new("t_role");
+    table.add_column("id", RdbcColumnType::BigInt).primary_key();
+    table.add_column("code", RdbcColumnType::Varchar(32));
+    table.primary_key(&["id"]);
+    let ddl = table.clone().into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec![
+            "CREATE TABLE t_role (\n  \
+              id BIGINT NOT NULL,\n  \
+              code VARCHAR(32),\n  \
+              PRIMARY KEY (id)\n)"
+        ]
+    );
+
+    table.constraints.clear();
+    table.primary_key(&["code"]);
+    let ddl = table.into();
+    for db_type in [DataBase::Postgres, DataBase::MySql, DataBase::Oracle] {
+        assert!(matches!(
+            render_ddl(&ddl, db_type),
+            Err(RdbcError::InvalidParam(_))
+        ));
+    }
+}
+
+#[test]
+pub fn test_alter_table_per_dialect() {
+    let mut alter = AlterTableDDLWrapper::new("t_user");
+    alter.schema("app");
+    let mut nick = ColumnDDLWrapper::new("nick", RdbcColumnType::Varchar(32));
+    nick.default_value("");
+    alter.add_column(nick);
+    let mut name = ColumnDDLWrapper::new("name", RdbcColumnType::Varchar(128));
+    name.not_null();
+    alter.alter_column(name).change_type().change_nullable();
+    alter.rename_column("age", "user_age");
+    let ddl = alter.into();
+
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec![
+            "ALTER TABLE app.t_user ADD COLUMN nick VARCHAR(32) DEFAULT ''",
+            "ALTER TABLE app.t_user ALTER COLUMN name TYPE VARCHAR(128)",
+            "ALTER TABLE app.t_user ALTER COLUMN name SET NOT NULL",
+            "ALTER TABLE app.t_user RENAME COLUMN age TO user_age",
+        ]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::MySql).unwrap()[1],
+        "ALTER TABLE app.t_user MODIFY COLUMN name VARCHAR(128) NOT NULL"
+    );
+    let oracle = render_ddl(&ddl, DataBase::Oracle).unwrap();
+    assert_eq!(
+        oracle[0],
+        "ALTER TABLE app.t_user ADD (nick VARCHAR2(32) DEFAULT '')"
+    );
+    assert_eq!(
+        oracle[1],
+        "ALTER TABLE app.t_user MODIFY (name VARCHAR2(128) NOT NULL)"
+    );
+    assert!(matches!(
+        render_ddl(&ddl, DataBase::Sqlite),
+        Err(RdbcError::Unsupported(_))
+    ));
+}
+
+#[test]
+pub fn test_drop_table_per_dialect() {
+    let mut drop = DropTableDDLWrapper::new("t_user");
+    drop.if_exists().cascade();
+    let ddl = drop.into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec!["DROP TABLE IF EXISTS t_user CASCADE"]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Sqlite).unwrap(),
+        vec!["DROP TABLE IF EXISTS t_user"]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Oracle).unwrap(),
+        vec!["DROP TABLE IF EXISTS t_user CASCADE CONSTRAINTS"]
+    );
+}
+
+#[test]
+pub fn test_create_and_drop_index_per_dialect() {
+    let mut index = IndexDDLWrapper::new("t_user", "idx_user_email");
+    index
+        .schema("app")
+        .unique()
+        .column("tenant_id")
+        .expression("lower(email)")
+        .where_condition("deleted = false")
+        .concurrently()
+        .if_not_exists();
+    let ddl = index.clone().into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec!["CREATE UNIQUE INDEX CONCURRENTLY IF NOT EXISTS idx_user_email ON app.t_user (tenant_id, (lower(email))) WHERE deleted = false"]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Sqlite).unwrap(),
+        vec!["CREATE UNIQUE INDEX IF NOT EXISTS app.idx_user_email ON t_user (tenant_id, (lower(email))) WHERE deleted = false"]
+    );
+    assert!(matches!(
+        render_ddl(&ddl, DataBase::MySql),
+        Err(RdbcError::Unsupported(_))
+    ));
+
+    index.where_condition = None;
+    index.if_not_exists = false;
+    let ddl = index.into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::MySql).unwrap(),
+        vec!["CREATE UNIQUE INDEX idx_user_email ON app.t_user (tenant_id, (lower(email)))"]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Oracle).unwrap(),
+        vec!["CREATE UNIQUE INDEX app.idx_user_email ON app.t_user (tenant_id, lower(email))"]
+    );
+
+    let mut drop = DropIndexDDLWrapper::new("t_user", "idx_user_email");
+    drop.concurrently().if_exists();
+    let ddl = drop.into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec!["DROP INDEX CONCURRENTLY IF EXISTS idx_user_email"]
+    );
+    let mut drop = DropIndexDDLWrapper::new("t_user", "idx_user_email");
+    drop.schema("app");
+    assert_eq!(
+        render_ddl(&drop.into(), DataBase::MySql).unwrap(),
+        vec!["DROP INDEX idx_user_email ON app.t_user"]
+    );
+}
+
+#[test]
+pub fn test_foreign_key_per_dialect() {
+    let mut table = TableDDLWrapper::new("t_order");
+    table.add_column("id", RdbcColumnType::BigInt).primary_key();
+    table.add_column("user_id", RdbcColumnType::BigInt);
+    table
+        .foreign_key(&["user_id"], "t_user", &["id"])
+        .name("fk_order_user")
+        .on_delete(ReferentialAction::Cascade);
+    let ddl = table.clone().into();
+    let expected =
+        "CONSTRAINT fk_order_user FOREIGN KEY (user_id) REFERENCES t_user (id) ON DELETE CASCADE";
+    for db in [
+        DataBase::Postgres,
+        DataBase::MySql,
+        DataBase::Sqlite,
+        DataBase::Oracle,
+    ] {
+        assert!(render_ddl(&ddl, db).unwrap()[0].contains(expected));
+    }
+
+    let mut alter = AlterTableDDLWrapper::new("t_order");
+    alter.add_constraint(table.constraints[0].clone());
+    let ddl = alter.into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec![format!("ALTER TABLE t_order ADD {}", expected)]
+    );
+
+    if let bmbp_sql::TableConstraint::ForeignKey(foreign_key) = &mut table.constraints[0] {
+        foreign_key.on_update(ReferentialAction::SetDefault);
+    }
+    let ddl = table.into();
+    assert!(render_ddl(&ddl, DataBase::Postgres).unwrap()[0].ends_with("ON UPDATE SET DEFAULT\n)"));
+    assert!(matches!(
+        render_ddl(&ddl, DataBase::MySql),
+        Err(RdbcError::Unsupported(_))
+    ));
+    assert!(matches!(
+        render_ddl(&ddl, DataBase::Oracle),
+        Err(RdbcError::Unsupported(_))
+    ));
+}
+
+#[test]
+pub fn test_comments_per_dialect() {
+    let mut table = TableDDLWrapper::new("t_user");
+    table.schema("app").comment("User's accounts");
+    table.add_column("id", RdbcColumnType::BigInt).primary_key();
+    table
+        .add_column("name", RdbcColumnType::Varchar(64))
+        .comment("Display name, e.g. 'bmbp'");
+    let ddl = table.into();
+
+    let pg = render_ddl(&ddl, DataBase::Postgres).unwrap();
+    assert_eq!(
+        &pg[1..],
+        [
+            "COMMENT ON TABLE app.t_user IS 'User''s accounts'",
+            "COMMENT ON COLUMN app.t_user.name IS 'Display name, e.g. ''bmbp'''",
+        ]
+    );
+    assert_eq!(render_ddl(&ddl, DataBase::Oracle).unwrap().len(), 3);
+    let mysql = render_ddl(&ddl, DataBase::MySql).unwrap();
+    assert_eq!(mysql.len(), 1);
+    assert!(mysql[0].contains("name VARCHAR(64) COMMENT 'Display name, e.g. ''bmbp'''"));
+    assert!(mysql[0].ends_with(") COMMENT = 'User''s accounts'"));
+    assert_eq!(render_ddl(&ddl, DataBase::Sqlite).unwrap().len(), 1);
+
+    let mut comment = CommentDDLWrapper::on_column("t_user", "name", r"C:\temp");
+    assert!(matches!(
+        render_ddl(&comment.clone().into(), DataBase::MySql),
+        Err(RdbcError::Unsupported(_))
+    ));
+    let mut name = ColumnDDLWrapper::new("name", RdbcColumnType::Varchar(64));
+    name.not_null();
+    comment.column_definition(name);
+    let ddl = comment.into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::MySql).unwrap(),
+        vec![r"ALTER TABLE t_user MODIFY COLUMN name VARCHAR(64) NOT NULL COMMENT 'C:\\temp'"]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec![r"COMMENT ON COLUMN t_user.name IS 'C:\temp'"]
+    );
+
+    let ddl = CommentDDLWrapper::on_table("t_user", "").into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec!["COMMENT ON TABLE t_user IS NULL"]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Oracle).unwrap(),
+        vec!["COMMENT ON TABLE t_user IS ''"]
+    );
+}
+
+#[test]
+pub fn test_view_inlines_params() {
+    let mut query = RdbcQueryWrapper::new();
+    query.select("id").select("name").from("t_user");
+    query.eq("status", "it's").in_script("role", "roles");
+    query.param(
+        "roles",
+        RdbcValue::Array(vec![RdbcValue::from("admin"), RdbcValue::from(1)]),
+    );
+    let mut view = ViewDDLWrapper::new("v_user", query);
+    view.or_replace().columns(&["user_id", "user_name"]);
+    let ddl = view.clone().into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec!["CREATE OR REPLACE VIEW v_user (user_id, user_name) AS SELECT id,name \n FROM t_user \n WHERE status = 'it''s' AND role IN ('admin',1)"]
+    );
+    assert!(matches!(
+        render_ddl(&ddl, DataBase::Sqlite),
+        Err(RdbcError::Unsupported(_))
+    ));
+
+    view.or_replace = false;
+    view.materialized();
+    let sql = render_ddl(&view.into(), DataBase::Postgres).unwrap();
+    assert!(sql[0].starts_with("CREATE MATERIALIZED VIEW v_user (user_id, user_name) AS SELECT"));
+
+    let mut refresh = RefreshViewDDLWrapper::new("v_user");
+    refresh.concurrently();
+    assert_eq!(
+        render_ddl(&refresh.into(), DataBase::Postgres).unwrap(),
+        vec!["REFRESH MATERIALIZED VIEW CONCURRENTLY v_user"]
+    );
+    let mut drop = DropViewDDLWrapper::new("v_user");
+    drop.materialized().if_exists();
+    let ddl = drop.into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec!["DROP MATERIALIZED VIEW IF EXISTS v_user"]
+    );
+    assert!(matches!(
+        render_ddl(&ddl, DataBase::MySql),
+        Err(RdbcError::Unsupported(_))
+    ));
+}
+
+#[test]
+pub fn test_sequence_per_dialect() {
+    let mut sequence = SequenceDDLWrapper::new("seq_user");
+    sequence.start(100).increment(10).cache(20);
+    let create = sequence.into();
+    assert_eq!(
+        render_ddl(&create, DataBase::Postgres).unwrap(),
+        vec!["CREATE SEQUENCE seq_user START WITH 100 INCREMENT BY 10 CACHE 20"]
+    );
+    assert_eq!(
+        render_ddl(&create, DataBase::Oracle).unwrap(),
+        vec!["CREATE SEQUENCE seq_user START WITH 100 INCREMENT BY 10 CACHE 20"]
+    );
+    for db_type in [DataBase::MySql, DataBase::Sqlite] {
+        assert!(matches!(
+            render_ddl(&create, db_type),
+            Err(RdbcError::Unsupported(_))
+        ));
+    }
+
+    let mut alter = AlterSequenceDDLWrapper::new("seq_user");
+    alter.restart(1).cycle(false);
+    let alter = alter.into();
+    assert_eq!(
+        render_ddl(&alter, DataBase::Postgres).unwrap(),
+        vec!["ALTER SEQUENCE seq_user NO CYCLE RESTART WITH 1"]
+    );
+    assert_eq!(
+        render_ddl(&alter, DataBase::Oracle).unwrap(),
+        vec!["ALTER SEQUENCE seq_user NOCYCLE RESTART START WITH 1"]
+    );
+
+    let mut drop = DropSequenceDDLWrapper::new("seq_user");
+    drop.if_exists().cascade();
+    let drop = drop.into();
+    assert_eq!(
+        render_ddl(&drop, DataBase::Postgres).unwrap(),
+        vec!["DROP SEQUENCE IF EXISTS seq_user CASCADE"]
+    );
+    assert!(matches!(
+        render_ddl(&drop, DataBase::Oracle),
+        Err(RdbcError::Unsupported(_))
+    ));
+}
+
+#[test]
+pub fn test_identity_column_per_dialect() {
+    let mut table = TableDDLWrapper::new("t_log");
+    table
+        .add_column("id", RdbcColumnType::BigInt)
+        .primary_key()
+        .identity();
+    table.add_column("message", RdbcColumnType::Text);
+    let ddl = table.into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec![
+            "CREATE TABLE t_log (\n  \
+              id BIGINT GENERATED BY DEFAULT AS IDENTITY NOT NULL,\n  \
+              message TEXT,\n  \
+              PRIMARY KEY (id)\n)"
+        ]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::MySql).unwrap(),
+        vec![
+            "CREATE TABLE t_log (\n  \
+              id BIGINT AUTO_INCREMENT NOT NULL,\n  \
+              message TEXT,\n  \
+              PRIMARY KEY (id)\n)"
+        ]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Sqlite).unwrap(),
+        vec![
+            "CREATE TABLE t_log (\n  \
+              id INTEGER PRIMARY KEY AUTOINCREMENT,\n  \
+              message TEXT\n)"
+        ]
+    );
+
+    let mut table = TableDDLWrapper::new("t_log");
+    table
+        .add_column("id", RdbcColumnType::BigInt)
+        .primary_key()
+        .identity_start(1000, 5)
+        .identity_always();
+    let ddl = table.into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Oracle).unwrap(),
+        vec!["CREATE TABLE t_log (\n  \
+              id NUMBER(19) GENERATED ALWAYS AS IDENTITY (START WITH 1000 INCREMENT BY 5) NOT NULL,\n  \
+              PRIMARY KEY (id)\n)"]
+    );
+    for db_type in [DataBase::MySql, DataBase::Sqlite] {
+        assert!(matches!(
+            render_ddl(&ddl, db_type),
+            Err(RdbcError::Unsupported(_))
+        ));
+    }
+}
+
+#[test]
+pub fn test_insert_next_value() {
+    let mut insert = RdbcInsertWrapper::new("t_user");
+    insert
+        .value("id", RdbcColumnValue::next_val("seq_user"))
+        .value("name", "alice");
+    let (sql, params) = render_insert(&insert, DataBase::Postgres).unwrap();
+    assert_eq!(
+        sql,
+        "INSERT INTO t_user\n(id,name)\nVALUES (nextval('seq_user'),$1)"
+    );
+    assert_eq!(params.len(), 1);
+    let (sql, params) = render_insert(&insert, DataBase::Oracle).unwrap();
+    assert!(sql.contains("seq_user.NEXTVAL"), "{}", sql);
+    assert_eq!(
+        sql,
+        "INSERT INTO t_user\n(id,name)\nVALUES (seq_user.NEXTVAL,:1)"
+    );
+    assert_eq!(params.len(), 1);
+}
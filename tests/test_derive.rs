@@ -0,0 +1,132 @@
+#![cfg(feature = "derive")]
+
+use bmbp_sql::{
+    render_ddl, render_query, DataBase, FromRdbcRow, RdbcColumnIdent, RdbcColumnType,
+    RdbcQueryWrapper, RdbcTable, RdbcTableIdent, RdbcValue, RdbcWhereCondition,
+};
+use std::collections::HashMap;
+
+#[allow(dead_code)]
+#[derive(RdbcTable)]
+#[rdbc(table = "t_user", schema = "app", alias = "u")]
+pub struct User {
+    #[rdbc(primary_key, length = 36)]
+    id: String,
+    #[rdbc(column = "user_name", length = 64)]
+    name: String,
+    age: Option<i32>,
+    #[rdbc(column_type = "Decimal(10, 2)", default = "0")]
+    balance: f64,
+    #[rdbc(ignore)]
+    cache: Vec<String>,
+}
+
+#[derive(Debug, FromRdbcRow)]
+pub struct UserRow {
+    id: i64,
+    #[rdbc(column = "user_name")]
+    name: String,
+    age: Option<i32>,
+    balance: f64,
+    created: chrono::NaiveDateTime,
+    #[rdbc(ignore)]
+    cache: Vec<String>,
+}
+
+#[allow(dead_code)]
+#[derive(RdbcTable)]
+pub struct UserRole {
+    #[rdbc(primary_key)]
+    user_id: i64,
+    #[rdbc(primary_key)]
+    role_id: i64,
+}
+
+#[test]
+pub fn test_derive_table_ident() {
+    assert_eq!(User::name(), "t_user");
+    assert_eq!(User::alias(), "u");
+    assert_eq!(UserColumn::Name.name(), "user_name");
+    assert_eq!(
+        UserColumn::Balance.column_type(),
+        RdbcColumnType::Decimal(10, 2)
+    );
+    assert_eq!(User::columns().len(), 4);
+
+    let ddl = User::table_ddl().into();
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec![
+            "CREATE TABLE app.t_user (\n  \
+              id VARCHAR(36) NOT NULL,\n  \
+              user_name VARCHAR(64) NOT NULL,\n  \
+              age INTEGER,\n  \
+              balance NUMERIC(10,2) DEFAULT 0 NOT NULL,\n  \
+              PRIMARY KEY (id)\n)"
+        ]
+    );
+
+    assert_eq!(UserRole::name(), "user_role");
+    let ddl = UserRole::table_ddl().into();
+    assert!(render_ddl(&ddl, DataBase::Sqlite).unwrap()[0]
+        .ends_with("PRIMARY KEY (user_id, role_id)\n)"));
+}
+
+#[test]
+pub fn test_derive_typed_columns_in_builders() {
+    let mut query = RdbcQueryWrapper::new();
+    query
+        .select(UserColumn::Id)
+        .select(UserColumn::Name)
+        .from(User::name());
+    query.eq(UserColumn::Age, 18);
+    let (sql, params) = render_query(&query, DataBase::Postgres).unwrap();
+    assert_eq!(sql, "SELECT id,user_name \n FROM t_user \n WHERE age = $1");
+    assert_eq!(params.len(), 1);
+}
+
+#[test]
+pub fn test_derive_from_row() {
+    let mut row = HashMap::new();
+    row.insert("ID".to_string(), RdbcValue::BigInt(7));
+    row.insert("user_name".to_string(), RdbcValue::from("alice"));
+    row.insert("balance".to_string(), RdbcValue::from("12.50"));
+    row.insert(
+        "created".to_string(),
+        RdbcValue::DateTime(
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_opt(10, 20, 30)
+                .unwrap(),
+        ),
+    );
+    let user = UserRow::from_rdbc_row(row.clone()).unwrap();
+    assert_eq!(user.id, 7);
+    assert_eq!(user.name, "alice");
+    assert_eq!(user.age, None);
+    assert_eq!(user.balance, 12.5);
+    assert_eq!(user.created.to_string(), "2024-02-29 10:20:30");
+    assert!(user.cache.is_empty());
+
+    row.insert("age".to_string(), RdbcValue::from("old"));
+    let err = UserRow::from_rdbc_row(row.clone()).unwrap_err().to_string();
+    assert!(
+        err.contains("column age expects Option<i32>, found Varchar"),
+        "{}",
+        err
+    );
+
+    row.remove("age");
+    row.remove("user_name");
+    let err = UserRow::from_rdbc_row(row).unwrap_err().to_string();
+    assert!(
+        err.contains("column user_name expects String, found Null"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+pub fn test_derive_compile_errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
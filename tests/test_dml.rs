@@ -0,0 +1,76 @@
+use bmbp_sql::{
+    render_delete, render_update, CompareColumn, CompareKind, ConditionColumn, DataBase,
+    RdbcColumnValue, RdbcCondition, RdbcDeleteWrapper, RdbcError, RdbcUpdateWrapper,
+    RdbcWhereCondition,
+};
+
+#[test]
+pub fn test_delete_without_where_is_rejected() {
+    let mut delete = RdbcDeleteWrapper::default();
+    delete.from("t_user");
+    let result = render_delete(&delete, DataBase::Postgres);
+    assert_eq!(
+        result.unwrap_err(),
+        RdbcError::FullTableStatement("DELETE".to_string())
+    );
+    assert!(render_delete(&delete, DataBase::Oracle).is_err());
+
+    delete.allow_full_table();
+    let (sql, params) = render_delete(&delete, DataBase::Postgres).unwrap();
+    assert_eq!(sql, "DELETE FROM t_user");
+    assert!(params.is_empty());
+}
+
+#[test]
+pub fn test_update_with_empty_condition_is_rejected() {
+    let mut update = RdbcUpdateWrapper::default();
+    update.from("t_user");
+    update.set("name", "bmbp");
+    let mut condition = RdbcCondition::new();
+    condition
+        .column
+        .push(bmbp_sql::ConditionColumn::SubCondition(RdbcCondition::new()));
+    update.where_condition = Some(condition);
+    assert!(render_update(&update, DataBase::Postgres).is_err());
+
+    update.eq("id", 1);
+    let (sql, params) = render_update(&update, DataBase::Postgres).unwrap();
+    assert!(sql.starts_with("UPDATE t_user\nSET name=$"));
+    assert!(sql.contains("WHERE id = $"));
+    assert_eq!(params.len(), 2);
+}
+
+#[test]
+pub fn test_next_value_outside_insert_without_sequences() {
+    let mut update = RdbcUpdateWrapper::default();
+    update.from("t_user");
+    update
+        .set("id", RdbcColumnValue::next_val("seq_user"))
+        .eq("id", 1);
+    let mut delete = RdbcDeleteWrapper::default();
+    delete.from("t_user");
+    delete.eq("id", 1);
+    let condition = delete.where_condition.as_mut().unwrap();
+    condition
+        .column
+        .push(ConditionColumn::Compare(CompareColumn {
+            column: "code".into(),
+            kind: CompareKind::Equal,
+            value: RdbcColumnValue::next_val("seq_user"),
+            ignore_null: false,
+        }));
+    for db_type in [DataBase::MySql, DataBase::Sqlite] {
+        assert!(matches!(
+            render_update(&update, db_type),
+            Err(RdbcError::Unsupported(_))
+        ));
+        assert!(matches!(
+            render_delete(&delete, db_type),
+            Err(RdbcError::Unsupported(_))
+        ));
+    }
+    let (sql, _) = render_update(&update, DataBase::Postgres).unwrap();
+    assert!(sql.contains("id=nextval('seq_user')"), "{}", sql);
+    let (sql, _) = render_delete(&delete, DataBase::Postgres).unwrap();
+    assert!(sql.contains("code = nextval('seq_user')"), "{}", sql);
+}
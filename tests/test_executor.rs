@@ -0,0 +1,144 @@
+#![cfg(all(feature = "derive", any(feature = "sqlite", feature = "postgres")))]
+
+use bmbp_sql::{
+    FromRdbcRow, RdbcError, RdbcExecutor, RdbcExecutorExt, RdbcInsertWrapper, RdbcQueryWrapper,
+    RdbcResult, RdbcTransactionOptions, RdbcUpdateWrapper, RdbcWhereCondition,
+};
+
+#[derive(FromRdbcRow)]
+struct Account {
+    id: i64,
+    owner: String,
+    balance: i64,
+}
+
+/// A repository that knows nothing of the backend it runs on.
+struct AccountRepository;
+
+impl AccountRepository {
+    async fn create(&self, executor: &dyn RdbcExecutor, id: i64, balance: i64) -> RdbcResult<u64> {
+        let mut insert = RdbcInsertWrapper::new("t_account");
+        insert
+            .value("id", id)
+            .value("owner", format!("owner{}", id))
+            .value("balance", balance);
+        executor.insert(&insert).await
+    }
+
+    async fn set_balance(
+        &self,
+        executor: &dyn RdbcExecutor,
+        id: i64,
+        balance: i64,
+    ) -> RdbcResult<u64> {
+        let mut update = RdbcUpdateWrapper::default();
+        update.from("t_account");
+        update.set("balance", balance).eq("id", id);
+        executor.update(&update).await
+    }
+
+    async fn find(&self, executor: &dyn RdbcExecutor, id: i64) -> RdbcResult<Option<Account>> {
+        let mut query = RdbcQueryWrapper::with_columns(["id", "owner", "balance"]);
+        query.from("t_account");
+        query.eq("id", id);
+        executor.query_one_as(&query).await
+    }
+}
+
+async fn count<E: RdbcExecutor + ?Sized>(executor: &E) -> u64 {
+    let mut query = RdbcQueryWrapper::with_columns(["id"]);
+    query.from("t_account");
+    executor.query_page(&query, 1, 10).await.unwrap().total
+}
+
+/// Runs the repository on an executor holding an empty `t_account` table.
+async fn run_repository<E: RdbcExecutor>(mut executor: E) {
+    let repository = AccountRepository;
+    assert_eq!(repository.create(&executor, 1, 100).await.unwrap(), 1);
+    let account = repository.find(&executor, 1).await.unwrap().unwrap();
+    assert_eq!((account.id, account.owner.as_str()), (1, "owner1"));
+    assert_eq!(account.balance, 100);
+
+    let mut transaction = executor.begin().await.unwrap();
+    repository.create(&*transaction, 2, 20).await.unwrap();
+    {
+        let nested = transaction.begin().await.unwrap();
+        repository.create(&*nested, 3, 30).await.unwrap();
+        nested.rollback().await.unwrap();
+    }
+    let mut read_only = RdbcTransactionOptions::default();
+    read_only.read_only(true);
+    assert!(matches!(
+        transaction.begin_with(&read_only).await,
+        Err(RdbcError::Unsupported(_))
+    ));
+    repository.set_balance(&*transaction, 1, 90).await.unwrap();
+    transaction.commit().await.unwrap();
+    assert_eq!(count(&executor).await, 2);
+    let account = repository.find(&executor, 1).await.unwrap().unwrap();
+    assert_eq!(account.balance, 90);
+
+    {
+        let transaction = executor.begin().await.unwrap();
+        repository.create(&*transaction, 4, 40).await.unwrap();
+        // Dropped without commit.
+    }
+    assert_eq!(count(&executor).await, 2);
+
+    let failed = executor
+        .with_transaction(&RdbcTransactionOptions::default(), async |tx| {
+            repository.create(tx, 5, 50).await?;
+            repository.create(tx, 5, 50).await
+        })
+        .await;
+    assert!(matches!(failed, Err(RdbcError::Database(_))));
+    let created = executor
+        .with_transaction(&RdbcTransactionOptions::default(), async |tx| {
+            repository.create(tx, 5, 50).await
+        })
+        .await
+        .unwrap();
+    assert_eq!(created, 1);
+    assert_eq!(count(&executor).await, 3);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_executor_sqlite() {
+    let executor = bmbp_sql::RdbcSqliteExecutor::open_in_memory().unwrap();
+    executor
+        .execute_sql(
+            "CREATE TABLE t_account (id INTEGER PRIMARY KEY, owner TEXT NOT NULL, \
+             balance INTEGER NOT NULL)",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        RdbcExecutor::database(&executor),
+        bmbp_sql::DataBase::Sqlite
+    );
+    run_repository(executor).await;
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs BMBP_SQL_PG_URL"]
+async fn test_executor_postgres() {
+    // A scratch PostgreSQL database, see tests/test_pg_executor.rs.
+    let url = std::env::var("BMBP_SQL_PG_URL").expect("BMBP_SQL_PG_URL is not set");
+    let executor = bmbp_sql::RdbcPgExecutor::connect(&url).await.unwrap();
+    executor
+        .execute_sql("DROP TABLE IF EXISTS t_account", &[])
+        .await
+        .unwrap();
+    executor
+        .execute_sql(
+            "CREATE TABLE t_account (id BIGINT PRIMARY KEY, owner VARCHAR(64) NOT NULL, \
+             balance BIGINT NOT NULL)",
+            &[],
+        )
+        .await
+        .unwrap();
+    run_repository(executor).await;
+}
@@ -0,0 +1,90 @@
+use bmbp_sql::{
+    render_ddl, ColumnDefault, DataBase, RdbcColumnIdent, RdbcColumnType, RdbcTableIdent, RdbcValue,
+};
+
+pub struct User;
+
+pub enum UserColumn {
+    Id,
+    Name,
+    Age,
+    CreateTime,
+}
+
+impl RdbcColumnIdent for UserColumn {
+    fn name(&self) -> String {
+        match self {
+            UserColumn::Id => "id",
+            UserColumn::Name => "name",
+            UserColumn::Age => "age",
+            UserColumn::CreateTime => "create_time",
+        }
+        .to_string()
+    }
+    fn column_type(&self) -> RdbcColumnType {
+        match self {
+            UserColumn::Id => RdbcColumnType::BigInt,
+            UserColumn::Age => RdbcColumnType::Int,
+            UserColumn::CreateTime => RdbcColumnType::DateTime,
+            UserColumn::Name => RdbcColumnType::Varchar(self.length()),
+        }
+    }
+    fn length(&self) -> u32 {
+        64
+    }
+    fn nullable(&self) -> bool {
+        !matches!(self, UserColumn::Name)
+    }
+    fn default_value(&self) -> Option<ColumnDefault> {
+        match self {
+            UserColumn::Age => Some(ColumnDefault::Value(RdbcValue::from(0))),
+            UserColumn::CreateTime => {
+                Some(ColumnDefault::Expression("CURRENT_TIMESTAMP".to_string()))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl RdbcTableIdent for User {
+    fn name() -> String {
+        "t_user".to_string()
+    }
+    fn alias() -> String {
+        "u".to_string()
+    }
+    fn columns() -> Vec<impl RdbcColumnIdent> {
+        vec![
+            UserColumn::Id,
+            UserColumn::Name,
+            UserColumn::Age,
+            UserColumn::CreateTime,
+        ]
+    }
+    fn primary_key() -> impl RdbcColumnIdent {
+        UserColumn::Id
+    }
+}
+
+#[test]
+pub fn test_table_ddl_from_ident() {
+    let ddl = User::table_ddl().into();
+    let expected = "CREATE TABLE t_user (\n  \
+                    id BIGINT NOT NULL,\n  \
+                    name VARCHAR(64) NOT NULL,\n  \
+                    age INTEGER DEFAULT 0,\n  \
+                    create_time {} DEFAULT CURRENT_TIMESTAMP,\n  \
+                    PRIMARY KEY (id)\n)";
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Postgres).unwrap(),
+        vec![expected.replace("{}", "TIMESTAMP")]
+    );
+    assert_eq!(
+        render_ddl(&ddl, DataBase::Sqlite).unwrap(),
+        vec![expected.replace("{}", "DATETIME")]
+    );
+    assert_eq!(
+        "code".column_ddl().column_type,
+        RdbcColumnType::Varchar(255)
+    );
+}
@@ -0,0 +1,384 @@
+use bmbp_sql::{
+    render_ddl, ColumnDefault, DataBase, IndexColumn, IndexDDLWrapper, RdbcColumnType,
+    RdbcIntrospector, RdbcSchemaDiff, RdbcValue, TableDDLWrapper,
+};
+use rusqlite::types::ValueRef;
+use std::collections::HashMap;
+
+fn sqlite_rows(
+    conn: &rusqlite::Connection,
+    (sql, params): (String, Vec<RdbcValue>),
+) -> Vec<HashMap<String, RdbcValue>> {
+    let params: Vec<String> = params.iter().map(|v| v.to_string()).collect();
+    let mut stmt = conn.prepare(&sql).unwrap();
+    let names: Vec<String> = stmt.column_names().iter().map(|n| n.to_string()).collect();
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(params.iter()))
+        .unwrap();
+    let mut result = vec![];
+    while let Some(row) = rows.next().unwrap() {
+        let mut map = HashMap::new();
+        for (idx, name) in names.iter().enumerate() {
+            let value = match row.get_ref(idx).unwrap() {
+                ValueRef::Null => RdbcValue::Null,
+                ValueRef::Integer(v) => RdbcValue::BigInt(v),
+                ValueRef::Real(v) => RdbcValue::BigDouble(v),
+                ValueRef::Text(v) => RdbcValue::Varchar(String::from_utf8_lossy(v).to_string()),
+                ValueRef::Blob(v) => RdbcValue::Bytes(v.to_vec()),
+            };
+            map.insert(name.clone(), value);
+        }
+        result.push(map);
+    }
+    result
+}
+
+#[test]
+pub fn test_introspect_sqlite_round_trip() {
+    let mut table = TableDDLWrapper::new("t_user");
+    table.add_column("id", RdbcColumnType::BigInt).primary_key();
+    table
+        .add_column("name", RdbcColumnType::Varchar(64))
+        .not_null()
+        .default_value("it's");
+    table
+        .add_column("enabled", RdbcColumnType::Boolean)
+        .default_value(true);
+    table
+        .add_column("create_time", RdbcColumnType::DateTime)
+        .default_expr("CURRENT_TIMESTAMP");
+    table
+        .add_column("email", RdbcColumnType::Varchar(64))
+        .unique();
+    let mut index = IndexDDLWrapper::new("t_user", "idx_user_name");
+    index.column("name").unique().where_condition("enabled = 1");
+
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    for sql in render_ddl(&table.into(), DataBase::Sqlite)
+        .unwrap()
+        .into_iter()
+        .chain(render_ddl(&index.into(), DataBase::Sqlite).unwrap())
+    {
+        conn.execute_batch(&sql).unwrap();
+    }
+
+    let introspector = RdbcIntrospector::new(DataBase::Sqlite);
+    let missing = sqlite_rows(&conn, introspector.table_query("", "t_missing"));
+    assert!(introspector
+        .parse_table("", "t_missing", &missing, &[])
+        .unwrap()
+        .is_none());
+
+    let table_rows = sqlite_rows(&conn, introspector.table_query("", "t_user"));
+    let column_rows = sqlite_rows(&conn, introspector.columns_query("", "t_user"));
+    let table = introspector
+        .parse_table("", "t_user", &table_rows, &column_rows)
+        .unwrap()
+        .unwrap();
+    let columns: Vec<(&str, &RdbcColumnType, bool, bool)> = table
+        .columns
+        .iter()
+        .map(|c| (c.name.as_str(), &c.column_type, c.nullable, c.primary_key))
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            ("id", &RdbcColumnType::BigInt, false, true),
+            ("name", &RdbcColumnType::Varchar(64), false, false),
+            ("enabled", &RdbcColumnType::Boolean, true, false),
+            ("create_time", &RdbcColumnType::DateTime, true, false),
+            ("email", &RdbcColumnType::Varchar(64), true, false),
+        ]
+    );
+    let unique: Vec<bool> = table.columns.iter().map(|c| c.unique).collect();
+    assert_eq!(unique, vec![false, false, false, false, true]);
+    assert!(matches!(
+        &table.columns[1].default_value,
+        Some(ColumnDefault::Value(RdbcValue::Varchar(v))) if v == "it's"
+    ));
+    assert!(matches!(
+        &table.columns[2].default_value,
+        Some(ColumnDefault::Value(RdbcValue::Boolean(true)))
+    ));
+    assert!(matches!(
+        &table.columns[3].default_value,
+        Some(ColumnDefault::Expression(v)) if v == "CURRENT_TIMESTAMP"
+    ));
+
+    let index_rows = sqlite_rows(&conn, introspector.indexes_query("", "t_user"));
+    let indexes = introspector
+        .parse_indexes("", "t_user", &index_rows)
+        .unwrap();
+    assert_eq!(indexes.len(), 1);
+    assert_eq!(indexes[0].name, "idx_user_name");
+    assert!(indexes[0].unique);
+    assert!(matches!(&indexes[0].columns[..], [IndexColumn::Column(c)] if c == "name"));
+    assert_eq!(indexes[0].where_condition.as_deref(), Some("enabled = 1"));
+}
+
+fn row(values: &[(&str, RdbcValue)]) -> HashMap<String, RdbcValue> {
+    values
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect()
+}
+
+#[test]
+pub fn test_introspect_parse_server_rows() {
+    let pg = RdbcIntrospector::new(DataBase::Postgres);
+    let (sql, params) = pg.columns_query("public", "t_user");
+    assert!(sql.contains("information_schema.columns"));
+    assert_eq!(params.len(), 2);
+    let table = pg
+        .parse_table(
+            "public",
+            "t_user",
+            &[row(&[("table_comment", RdbcValue::from("users"))])],
+            &[
+                row(&[
+                    ("column_name", RdbcValue::from("price")),
+                    ("data_type", RdbcValue::from("numeric")),
+                    ("num_precision", RdbcValue::Int(10)),
+                    ("num_scale", RdbcValue::Int(2)),
+                    ("is_nullable", RdbcValue::from("NO")),
+                    ("column_default", RdbcValue::from("0.5")),
+                    ("is_primary", RdbcValue::Int(0)),
+                ]),
+                row(&[
+                    ("column_name", RdbcValue::from("name")),
+                    ("data_type", RdbcValue::from("character varying")),
+                    ("char_length", RdbcValue::Int(32)),
+                    ("is_nullable", RdbcValue::from("YES")),
+                    ("column_default", RdbcValue::from("'x'::character varying")),
+                    ("column_comment", RdbcValue::from("user name")),
+                ]),
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(table.comment.as_deref(), Some("users"));
+    assert_eq!(table.columns[0].column_type, RdbcColumnType::Decimal(10, 2));
+    assert!(!table.columns[0].nullable);
+    assert_eq!(table.columns[1].column_type, RdbcColumnType::Varchar(32));
+    assert_eq!(table.columns[1].comment.as_deref(), Some("user name"));
+    assert!(matches!(
+        &table.columns[1].default_value,
+        Some(ColumnDefault::Value(RdbcValue::Varchar(v))) if v == "x"
+    ));
+
+    let indexes = pg
+        .parse_indexes(
+            "public",
+            "t_user",
+            &[
+                row(&[
+                    ("index_name", RdbcValue::from("idx_a")),
+                    ("column_name", RdbcValue::from("name")),
+                    ("is_unique", RdbcValue::Int(0)),
+                ]),
+                row(&[
+                    ("index_name", RdbcValue::from("idx_a")),
+                    ("expression", RdbcValue::from("lower(email)")),
+                    ("is_unique", RdbcValue::Int(0)),
+                ]),
+            ],
+        )
+        .unwrap();
+    assert_eq!(indexes.len(), 1);
+    assert!(matches!(
+        &indexes[0].columns[..],
+        [IndexColumn::Column(c), IndexColumn::Expression(e)] if c == "name" && e == "lower(email)"
+    ));
+
+    let oracle = RdbcIntrospector::new(DataBase::Oracle);
+    let table = oracle
+        .parse_table(
+            "",
+            "t_user",
+            &[row(&[("table_comment", RdbcValue::Null)])],
+            &[row(&[
+                ("COLUMN_NAME", RdbcValue::from("enabled")),
+                ("DATA_TYPE", RdbcValue::from("NUMBER")),
+                ("NUM_PRECISION", RdbcValue::Int(1)),
+                ("NUM_SCALE", RdbcValue::Int(0)),
+                ("IS_NULLABLE", RdbcValue::from("YES")),
+                ("COLUMN_DEFAULT", RdbcValue::from("1 ")),
+            ])],
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(table.comment, None);
+    assert_eq!(table.columns[0].column_type, RdbcColumnType::Boolean);
+    assert!(matches!(
+        &table.columns[0].default_value,
+        Some(ColumnDefault::Value(RdbcValue::Boolean(true)))
+    ));
+
+    let mysql = RdbcIntrospector::new(DataBase::MySql);
+    let table = mysql
+        .parse_table(
+            "",
+            "t_user",
+            &[row(&[("table_comment", RdbcValue::Null)])],
+            &[row(&[
+                ("column_name", RdbcValue::from("status")),
+                ("data_type", RdbcValue::from("varchar(16)")),
+                ("is_nullable", RdbcValue::from("NO")),
+                ("column_default", RdbcValue::from("active")),
+            ])],
+        )
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        &table.columns[0].default_value,
+        Some(ColumnDefault::Value(RdbcValue::Varchar(v))) if v == "active"
+    ));
+}
+
+#[test]
+pub fn test_diff_rebuilds_sqlite_table() {
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE t_user (id BIGINT NOT NULL, name VARCHAR(64), legacy TEXT, PRIMARY KEY (id));
+         CREATE INDEX idx_user_legacy ON t_user (legacy);
+         INSERT INTO t_user (id, name, legacy) VALUES (1, 'a', 'x');",
+    )
+    .unwrap();
+
+    let mut desired = TableDDLWrapper::new("t_user");
+    desired
+        .add_column("id", RdbcColumnType::BigInt)
+        .primary_key();
+    desired
+        .add_column("name", RdbcColumnType::Varchar(64))
+        .not_null()
+        .default_value("");
+    desired
+        .add_column("age", RdbcColumnType::Int)
+        .default_value(0);
+    let mut index = IndexDDLWrapper::new("t_user", "idx_user_name");
+    index.column("name");
+
+    let introspector = RdbcIntrospector::new(DataBase::Sqlite);
+    let introspect = |conn: &rusqlite::Connection| {
+        let table_rows = sqlite_rows(conn, introspector.table_query("", "t_user"));
+        let column_rows = sqlite_rows(conn, introspector.columns_query("", "t_user"));
+        let index_rows = sqlite_rows(conn, introspector.indexes_query("", "t_user"));
+        (
+            introspector
+                .parse_table("", "t_user", &table_rows, &column_rows)
+                .unwrap(),
+            introspector
+                .parse_indexes("", "t_user", &index_rows)
+                .unwrap(),
+        )
+    };
+    let (actual, actual_indexes) = introspect(&conn);
+    let statements = RdbcSchemaDiff::new(DataBase::Sqlite)
+        .diff_table(&desired, &[index.clone()], actual.as_ref(), &actual_indexes)
+        .unwrap();
+    for ddl in statements.iter() {
+        for sql in render_ddl(ddl, DataBase::Sqlite).unwrap() {
+            conn.execute_batch(&sql).unwrap();
+        }
+    }
+
+    let (actual, actual_indexes) = introspect(&conn);
+    assert!(RdbcSchemaDiff::new(DataBase::Sqlite)
+        .diff_table(&desired, &[index], actual.as_ref(), &actual_indexes)
+        .unwrap()
+        .is_empty());
+    let (name, age): (String, i64) = conn
+        .query_row("SELECT name, age FROM t_user WHERE id = 1", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!((name.as_str(), age), ("a", 0));
+}
+
+/// Table and indexes of `table` as the PostgreSQL behind `executor` has them.
+#[cfg(feature = "postgres")]
+async fn pg_introspect(
+    executor: &bmbp_sql::RdbcPgExecutor,
+    table: &str,
+) -> (Option<TableDDLWrapper>, Vec<IndexDDLWrapper>) {
+    let introspector = RdbcIntrospector::new(DataBase::Postgres);
+    let mut rows = vec![];
+    for (sql, params) in [
+        introspector.table_query("", table),
+        introspector.columns_query("", table),
+        introspector.indexes_query("", table),
+    ] {
+        let result = executor.query_sql(&sql, &params).await.unwrap();
+        let maps: Vec<HashMap<String, RdbcValue>> = result
+            .into_iter()
+            .map(|row| match row {
+                RdbcValue::Object(map) => map,
+                _ => panic!("row is not an object"),
+            })
+            .collect();
+        rows.push(maps);
+    }
+    (
+        introspector
+            .parse_table("", table, &rows[0], &rows[1])
+            .unwrap(),
+        introspector.parse_indexes("", table, &rows[2]).unwrap(),
+    )
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs BMBP_SQL_PG_URL"]
+async fn test_diff_keeps_unique_constraint_postgres() {
+    // A scratch PostgreSQL database, see tests/test_pg_executor.rs.
+    let url = std::env::var("BMBP_SQL_PG_URL").expect("BMBP_SQL_PG_URL is not set");
+    let executor = bmbp_sql::RdbcPgExecutor::connect(&url).await.unwrap();
+    let mut desired = TableDDLWrapper::new("t_rev_user");
+    desired
+        .add_column("id", RdbcColumnType::BigInt)
+        .primary_key();
+    desired
+        .add_column("email", RdbcColumnType::Varchar(64))
+        .unique();
+    executor
+        .execute_sql("DROP TABLE IF EXISTS t_rev_user", &[])
+        .await
+        .unwrap();
+    for sql in render_ddl(&desired.clone().into(), DataBase::Postgres).unwrap() {
+        executor.execute_sql(&sql, &[]).await.unwrap();
+    }
+
+    let diff =
+        |desired: &TableDDLWrapper,
+         (actual, indexes): &(Option<TableDDLWrapper>, Vec<IndexDDLWrapper>)| {
+            let statements = RdbcSchemaDiff::new(DataBase::Postgres)
+                .diff_table(desired, &[], actual.as_ref(), indexes)
+                .unwrap();
+            statements
+                .iter()
+                .flat_map(|ddl| render_ddl(ddl, DataBase::Postgres).unwrap())
+                .collect::<Vec<String>>()
+        };
+    let actual = pg_introspect(&executor, "t_rev_user").await;
+    assert!(actual.1.is_empty());
+    assert!(actual.0.as_ref().unwrap().columns[1].unique);
+    let sql = diff(&desired, &actual);
+    assert!(sql.is_empty(), "{:?}", sql);
+
+    desired.columns[1].unique = false;
+    let sql = diff(&desired, &actual);
+    assert_eq!(
+        sql,
+        vec!["ALTER TABLE t_rev_user DROP CONSTRAINT t_rev_user_email_key"]
+    );
+    for sql in sql.iter() {
+        executor.execute_sql(sql, &[]).await.unwrap();
+    }
+    let sql = diff(&desired, &pg_introspect(&executor, "t_rev_user").await);
+    assert!(sql.is_empty(), "{:?}", sql);
+    executor
+        .execute_sql("DROP TABLE t_rev_user", &[])
+        .await
+        .unwrap();
+}
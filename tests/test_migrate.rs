@@ -0,0 +1,162 @@
+#![cfg(feature = "sqlite")]
+
+use bmbp_sql::{
+    DataBase, DropTableDDLWrapper, RdbcColumnType, RdbcError, RdbcKeyCase, RdbcMigration,
+    RdbcMigrator, RdbcSqliteExecutor, TableDDLWrapper,
+};
+use std::fs;
+
+fn user_migration() -> RdbcMigration {
+    let mut table = TableDDLWrapper::new("t_user");
+    table.add_column("id", RdbcColumnType::BigInt).primary_key();
+    table.add_column("name", RdbcColumnType::Varchar(64));
+    let mut migration = RdbcMigration::ddl(1, "create user", vec![table.into()]);
+    migration.down_ddl(vec![DropTableDDLWrapper::new("t_user").into()]);
+    migration
+}
+
+fn table_names(executor: &RdbcSqliteExecutor) -> Vec<String> {
+    let conn = executor.connection();
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+        .unwrap();
+    stmt.query_map([], |row| row.get(0))
+        .unwrap()
+        .map(|name| name.unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_migrate_and_rollback_on_sqlite() {
+    let dir = std::env::temp_dir().join(format!("bmbp_sql_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("V2__seed_users.sql"),
+        "-- seed\nINSERT INTO t_user (id, name) VALUES (1, 'a;b');\nINSERT INTO t_user (id, name) VALUES (2, 'c');\n",
+    )
+    .unwrap();
+    fs::write(dir.join("U2__seed_users.sql"), "DELETE FROM t_user;").unwrap();
+    fs::write(
+        dir.join("V3__add_age.postgres.sql"),
+        "ALTER TABLE t_user ADD COLUMN age INTEGER",
+    )
+    .unwrap();
+
+    let mut migrator = RdbcMigrator::new(DataBase::Sqlite);
+    migrator.migration(user_migration());
+    migrator.load_dir(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(migrator.migrations().len(), 2);
+
+    let mut executor = RdbcSqliteExecutor::open_in_memory().unwrap();
+    let dry_run = migrator.dry_run(&mut executor).await.unwrap();
+    assert_eq!(dry_run[0], "-- 1 create user");
+    assert_eq!(
+        dry_run[3],
+        "INSERT INTO t_user (id, name) VALUES (1, 'a;b')"
+    );
+    assert!(table_names(&executor).is_empty());
+
+    assert_eq!(migrator.migrate(&mut executor).await.unwrap(), vec![1, 2]);
+    assert_eq!(
+        migrator.migrate(&mut executor).await.unwrap(),
+        Vec::<u64>::new()
+    );
+    let applied = migrator.applied(&mut executor).await.unwrap();
+    assert_eq!(applied.len(), 2);
+    assert_eq!(applied[1].description, "seed users");
+    assert_eq!(
+        applied[0].checksum,
+        user_migration().checksum(DataBase::Sqlite).unwrap()
+    );
+    let count: i64 = executor
+        .connection()
+        .query_row("SELECT COUNT(*) FROM t_user", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 2);
+
+    assert_eq!(
+        migrator.rollback(&mut executor, 0).await.unwrap(),
+        vec![2, 1]
+    );
+    assert_eq!(
+        table_names(&executor),
+        vec!["rdbc_migration_history", "rdbc_migration_lock"]
+    );
+    assert!(migrator.applied(&mut executor).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_migrate_refuses_lock_and_changed_checksum() {
+    let mut executor = RdbcSqliteExecutor::open_in_memory().unwrap();
+    executor.key_case(RdbcKeyCase::CamelCase);
+    let mut migrator = RdbcMigrator::new(DataBase::Sqlite);
+    migrator.migration(user_migration());
+    migrator.migrate(&mut executor).await.unwrap();
+
+    executor
+        .connection()
+        .execute(
+            "INSERT INTO rdbc_migration_lock (lock_id, locked_by, locked_at) VALUES (1, 'other', '2024-01-01 00:00:00')",
+            [],
+        )
+        .unwrap();
+    let err = migrator.migrate(&mut executor).await.unwrap_err();
+    assert_eq!(
+        err,
+        RdbcError::Migration(
+            "migration lock is held by other since 2024-01-01 00:00:00".to_string()
+        )
+    );
+    migrator.unlock(&mut executor).await.unwrap();
+
+    let mut changed = RdbcMigrator::new(DataBase::Sqlite);
+    changed.migration(RdbcMigration::sql(
+        1,
+        "create user",
+        "CREATE TABLE t_user (id BIGINT)",
+    ));
+    let err = changed.migrate(&mut executor).await.unwrap_err();
+    assert_eq!(
+        err,
+        RdbcError::Migration("checksum of applied version 1 changed".to_string())
+    );
+
+    let mut failing = RdbcMigrator::new(DataBase::Sqlite);
+    failing.migration(user_migration());
+    failing.migration(RdbcMigration::sql(
+        2,
+        "broken",
+        "CREATE TABLE t_role (id BIGINT); INSERT INTO t_missing VALUES (1)",
+    ));
+    assert!(failing.migrate(&mut executor).await.is_err());
+    let tables: i64 = executor
+        .connection()
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name IN ('t_role', 'rdbc_migration_lock') AND type = 'table'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(tables, 1);
+    assert_eq!(failing.applied(&mut executor).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_migrate_failure_survives_failed_rollback() {
+    let mut executor = RdbcSqliteExecutor::open_in_memory().unwrap();
+    let mut migrator = RdbcMigrator::new(DataBase::Sqlite);
+    // The COMMIT ends the migration transaction, so the ROLLBACK after the
+    // failing INSERT fails as well.
+    migrator.migration(RdbcMigration::sql(
+        1,
+        "broken",
+        "CREATE TABLE t_role (id BIGINT); COMMIT; INSERT INTO t_missing VALUES (1)",
+    ));
+    let err = migrator.migrate(&mut executor).await.unwrap_err();
+    assert!(
+        matches!(&err, RdbcError::Migration(msg) if msg.starts_with("version 1 failed at `INSERT INTO t_missing")),
+        "{:?}",
+        err
+    );
+}
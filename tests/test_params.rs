@@ -0,0 +1,82 @@
+use bmbp_sql::{
+    render_delete, render_query, render_script, DataBase, RdbcDeleteWrapper, RdbcError,
+    RdbcQueryWrapper, RdbcValue, RdbcWhereCondition,
+};
+use std::collections::HashMap;
+
+#[test]
+pub fn test_script_params_are_bound() {
+    let mut user = HashMap::new();
+    user.insert("id".to_string(), RdbcValue::from("u-1"));
+    let mut query = RdbcQueryWrapper::new();
+    query.select("name").from("t_user");
+    query.eq_script("id", "user.id");
+    query.param("user", RdbcValue::Object(user));
+    let (sql, params) = render_query(&query, DataBase::Postgres).unwrap();
+    assert_eq!(sql, "SELECT name \n FROM t_user \n WHERE id = $1 ");
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].to_string(), "u-1");
+}
+
+#[test]
+pub fn test_script_array_is_expanded_for_in() {
+    let mut delete = RdbcDeleteWrapper::default();
+    delete.from("t_user");
+    delete.in_script("id", "ids");
+    delete.param(
+        "ids",
+        RdbcValue::Array(vec![
+            RdbcValue::from(1),
+            RdbcValue::from(2),
+            RdbcValue::from(3),
+        ]),
+    );
+    let (sql, params) = render_delete(&delete, DataBase::Postgres).unwrap();
+    assert_eq!(sql, "DELETE FROM t_user\n WHERE id IN ($1,$2,$3)");
+    assert_eq!(params.len(), 3);
+
+    delete.param("ids", RdbcValue::Array(vec![]));
+    assert!(matches!(
+        render_delete(&delete, DataBase::Postgres),
+        Err(RdbcError::InvalidParam(_))
+    ));
+}
+
+#[test]
+pub fn test_unknown_script_param_is_rejected() {
+    let mut query = RdbcQueryWrapper::new();
+    query.select("name").from("t_user");
+    query.eq_script("id", "user.id");
+    assert_eq!(
+        render_query(&query, DataBase::Postgres).unwrap_err(),
+        RdbcError::UnknownParam("user.id".to_string())
+    );
+}
+
+#[test]
+pub fn test_script_array_is_not_expanded_outside_in() {
+    let mut params = HashMap::new();
+    params.insert(
+        "ids".to_string(),
+        RdbcValue::Array(vec![RdbcValue::from(1), RdbcValue::from(2)]),
+    );
+    let (sql, values) =
+        render_script("SELECT MIN(#{ids}) FROM t", &params, DataBase::Postgres).unwrap();
+    assert_eq!(sql, "SELECT MIN($1) FROM t");
+    assert_eq!(values.len(), 1);
+    let (sql, _) = render_script(
+        "SELECT * FROM t JOIN (#{ids}) x ON true",
+        &params,
+        DataBase::Postgres,
+    )
+    .unwrap();
+    assert_eq!(sql, "SELECT * FROM t JOIN ($1) x ON true");
+    let (sql, values) = render_script(
+        "SELECT * FROM t WHERE id in(#{ids})",
+        &params,
+        DataBase::Postgres,
+    )
+    .unwrap();
+    assert_eq!(sql, "SELECT * FROM t WHERE id in($1,$2)");
+    assert_eq!(values.len(), 2);
+}
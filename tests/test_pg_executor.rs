@@ -0,0 +1,369 @@
+#![cfg(all(feature = "derive", feature = "postgres"))]
+
+mod common;
+
+use bmbp_sql::{
+    FromRdbcRow, RdbcDeleteWrapper, RdbcError, RdbcExecutorExt, RdbcInsertWrapper,
+    RdbcIsolationLevel, RdbcKeyCase, RdbcPgExecutor, RdbcQueryWrapper, RdbcRawSql,
+    RdbcTransactionOptions, RdbcUpdateWrapper, RdbcValue, RdbcWhereCondition,
+};
+use common::field;
+
+/// Connection string of a scratch PostgreSQL database, e.g.
+/// `host=localhost port=5432 user=postgres`. Tests needing it are ignored,
+/// run them with `cargo test -- --ignored`.
+fn pg_config() -> Option<String> {
+    std::env::var("BMBP_SQL_PG_URL").ok()
+}
+
+#[derive(FromRdbcRow)]
+struct ExecutorUser {
+    id: i64,
+    name: String,
+    note: Option<String>,
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs BMBP_SQL_PG_URL"]
+async fn test_pg_executor_crud() {
+    let config = pg_config().expect("BMBP_SQL_PG_URL is not set");
+    let executor = RdbcPgExecutor::connect(&config).await.unwrap();
+    executor
+        .execute(&RdbcRawSql::new("DROP TABLE IF EXISTS t_executor_user"))
+        .await
+        .unwrap();
+    executor
+        .execute_sql(
+            "CREATE TABLE t_executor_user (id BIGINT PRIMARY KEY, name TEXT, note TEXT)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    for id in 1..=5i64 {
+        let mut insert = RdbcInsertWrapper::new("t_executor_user");
+        insert
+            .value("id", id)
+            .value("name", format!("user{}", id))
+            .value("note", RdbcValue::Null);
+        assert_eq!(executor.execute(&insert).await.unwrap(), 1);
+    }
+
+    let mut update = RdbcUpdateWrapper::default();
+    update.from("t_executor_user");
+    update.set("name", "alice").eq("id", 1i64);
+    assert_eq!(executor.execute(&update).await.unwrap(), 1);
+
+    let mut query = RdbcQueryWrapper::with_columns(["id", "name", "note"]);
+    query.from("t_executor_user");
+    query.eq("id", 1i64);
+    let row = executor.query_one(&query).await.unwrap().unwrap();
+    assert!(matches!(field(&row, "id"), RdbcValue::BigInt(1)));
+    assert_eq!(field(&row, "name").to_string(), "alice");
+    assert!(matches!(field(&row, "note"), RdbcValue::Null));
+
+    let user: ExecutorUser = executor.query_one_as(&query).await.unwrap().unwrap();
+    assert_eq!(user.id, 1);
+    assert_eq!(user.name, "alice");
+    assert_eq!(user.note, None);
+
+    let mut all = RdbcQueryWrapper::with_columns(["id", "name"]);
+    all.from("t_executor_user");
+    assert_eq!(executor.query_list(&all).await.unwrap().len(), 5);
+    let users: Vec<ExecutorUser> = executor.query_as(&all).await.unwrap();
+    assert_eq!(users.len(), 5);
+    let page = executor.query_page(&all, 2, 2).await.unwrap();
+    assert_eq!(page.total, 5);
+    assert_eq!(page.page_count(), 3);
+    assert_eq!(page.data.len(), 2);
+    let last = executor.query_page(&all, 3, 2).await.unwrap();
+    assert_eq!(last.data.len(), 1);
+
+    let mut delete = RdbcDeleteWrapper::default();
+    delete.from("t_executor_user");
+    delete.eq("id", 5i64);
+    assert_eq!(executor.execute(&delete).await.unwrap(), 1);
+    let mut missing = RdbcQueryWrapper::with_columns(["id"]);
+    missing.from("t_executor_user");
+    missing.eq("id", 5i64);
+    assert!(executor.query_one(&missing).await.unwrap().is_none());
+    executor
+        .execute_sql("DROP TABLE t_executor_user", &[])
+        .await
+        .unwrap();
+}
+
+#[test]
+fn test_key_case() {
+    assert_eq!(RdbcKeyCase::Column.key("create_time"), "create_time");
+    assert_eq!(RdbcKeyCase::CamelCase.key("create_time"), "createTime");
+    assert_eq!(RdbcKeyCase::CamelCase.key("CREATE_TIME"), "createTime");
+    assert_eq!(RdbcKeyCase::CamelCase.key("_row_id"), "rowId");
+    assert_eq!(RdbcKeyCase::CamelCase.key("userName"), "userName");
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs BMBP_SQL_PG_URL"]
+async fn test_pg_value_types() {
+    let config = pg_config().expect("BMBP_SQL_PG_URL is not set");
+    let mut executor = RdbcPgExecutor::connect(&config).await.unwrap();
+    executor.key_case(RdbcKeyCase::CamelCase);
+    let rows = executor
+        .query_sql(
+            "SELECT 1::int2 AS small_value, 2::int4 AS int_value, 3::int8 AS big_value, \
+             1.5::float4 AS float_value, 2.25::float8 AS double_value, \
+             -1234.0500::numeric AS numeric_value, 0.000012::numeric AS tiny_numeric, \
+             'a'::text AS text_value, 'b'::varchar AS varchar_value, 'c'::char(2) AS char_value, \
+             true AS bool_value, DATE '2024-02-29' AS date_value, \
+             TIMESTAMP '2024-02-29 10:20:30' AS timestamp_value, \
+             TIMESTAMPTZ '2024-02-29 10:20:30+02' AS timestamptz_value, \
+             TIME '10:20:30' AS time_value, '\\x0102'::bytea AS bytes_value, \
+             '{\"a\": [1, \"x\", null]}'::jsonb AS json_value, \
+             ARRAY[1, NULL, 3]::int4[] AS array_value, \
+             '6f1c3b2e-7c0a-4a43-9d0e-2f1b9d2b7c11'::uuid AS uuid_value, \
+             NULL::text AS null_value",
+            &[],
+        )
+        .await
+        .unwrap();
+    let row = &rows[0];
+    assert!(matches!(field(row, "smallValue"), RdbcValue::Int(1)));
+    assert!(matches!(field(row, "intValue"), RdbcValue::Int(2)));
+    assert!(matches!(field(row, "bigValue"), RdbcValue::BigInt(3)));
+    assert!(matches!(field(row, "floatValue"), RdbcValue::Double(v) if *v == 1.5));
+    assert!(matches!(field(row, "doubleValue"), RdbcValue::BigDouble(v) if *v == 2.25));
+    assert_eq!(field(row, "numericValue").to_string(), "-1234.0500");
+    assert_eq!(field(row, "tinyNumeric").to_string(), "0.000012");
+    assert_eq!(field(row, "textValue").to_string(), "a");
+    assert_eq!(field(row, "varcharValue").to_string(), "b");
+    assert_eq!(field(row, "charValue").to_string(), "c ");
+    assert!(matches!(field(row, "boolValue"), RdbcValue::Boolean(true)));
+    assert_eq!(field(row, "dateValue").to_string(), "2024-02-29");
+    assert_eq!(
+        field(row, "timestampValue").to_string(),
+        "2024-02-29 10:20:30"
+    );
+    assert_eq!(
+        field(row, "timestamptzValue").to_string(),
+        "2024-02-29 08:20:30"
+    );
+    assert_eq!(field(row, "timeValue").to_string(), "10:20:30");
+    assert!(matches!(field(row, "bytesValue"), RdbcValue::Bytes(v) if v == &vec![1u8, 2]));
+    let json = field(row, "jsonValue").as_object().unwrap();
+    let items = json["a"].as_array().unwrap();
+    assert!(matches!(items[0], RdbcValue::BigInt(1)));
+    assert_eq!(items[1].to_string(), "x");
+    assert!(items[2].is_null());
+    let array = field(row, "arrayValue").as_array().unwrap();
+    assert!(matches!(
+        array[..],
+        [RdbcValue::Int(1), RdbcValue::Null, RdbcValue::Int(3)]
+    ));
+    assert_eq!(
+        field(row, "uuidValue").to_string(),
+        "6f1c3b2e-7c0a-4a43-9d0e-2f1b9d2b7c11"
+    );
+    assert!(field(row, "nullValue").is_null());
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs BMBP_SQL_PG_URL"]
+async fn test_pg_param_coercion() {
+    let config = pg_config().expect("BMBP_SQL_PG_URL is not set");
+    let executor = RdbcPgExecutor::connect(&config).await.unwrap();
+    executor
+        .execute_sql("DROP TABLE IF EXISTS t_executor_types", &[])
+        .await
+        .unwrap();
+    executor
+        .execute_sql(
+            "CREATE TABLE t_executor_types (small_value int2, int_value int4, \
+             numeric_value numeric, uuid_value uuid, json_value jsonb, \
+             created timestamp, created_tz timestamptz, tags int4[], note text)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let mut object = std::collections::HashMap::new();
+    object.insert("a".to_string(), RdbcValue::BigInt(1));
+    let mut insert = RdbcInsertWrapper::new("t_executor_types");
+    insert
+        .value("small_value", RdbcValue::BigInt(7))
+        .value("int_value", "42")
+        .value("numeric_value", "-1234.0500")
+        .value("uuid_value", "6f1c3b2e-7c0a-4a43-9d0e-2f1b9d2b7c11")
+        .value("json_value", RdbcValue::Object(object))
+        .value("created", RdbcValue::TimeStamp(1_709_202_030))
+        .value("created_tz", RdbcValue::TimeStamp(1_709_202_030))
+        .value(
+            "tags",
+            RdbcValue::Array(vec![RdbcValue::BigInt(1), RdbcValue::Null]),
+        )
+        .value("note", RdbcValue::Int(5));
+    assert_eq!(executor.execute(&insert).await.unwrap(), 1);
+
+    let rows = executor
+        .query_sql(
+            "SELECT small_value, int_value, numeric_value, uuid_value::text AS uuid_text, \
+             json_value->>'a' AS json_a, created, created_tz, tags, note \
+             FROM t_executor_types",
+            &[],
+        )
+        .await
+        .unwrap();
+    let row = &rows[0];
+    assert!(matches!(field(row, "small_value"), RdbcValue::Int(7)));
+    assert!(matches!(field(row, "int_value"), RdbcValue::Int(42)));
+    assert_eq!(field(row, "numeric_value").to_string(), "-1234.0500");
+    assert_eq!(
+        field(row, "uuid_text").to_string(),
+        "6f1c3b2e-7c0a-4a43-9d0e-2f1b9d2b7c11"
+    );
+    assert_eq!(field(row, "json_a").to_string(), "1");
+    assert_eq!(field(row, "created").to_string(), "2024-02-29 10:20:30");
+    assert_eq!(field(row, "created_tz").to_string(), "2024-02-29 10:20:30");
+    assert!(matches!(
+        field(row, "tags").as_array().unwrap()[..],
+        [RdbcValue::Int(1), RdbcValue::Null]
+    ));
+    assert_eq!(field(row, "note").to_string(), "5");
+
+    // A cast on the param makes PostgreSQL expect a numeric.
+    for numeric in ["0", "0.000012", "100000000", "12345.6789", "-0.5", "NaN"] {
+        let rows = executor
+            .query_sql(
+                "SELECT $1::numeric::text AS value",
+                &[RdbcValue::from(numeric)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(field(&rows[0], "value").to_string(), numeric);
+    }
+    let rows = executor
+        .query_sql(
+            "SELECT $1::numeric::text AS value",
+            &[RdbcValue::BigDouble(2.5)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(field(&rows[0], "value").to_string(), "2.5");
+
+    let rejected = [
+        ("small_value", RdbcValue::BigInt(70_000)),
+        ("int_value", RdbcValue::Boolean(true)),
+        ("uuid_value", RdbcValue::from("not-a-uuid")),
+        ("numeric_value", RdbcValue::from("1.2.3")),
+    ];
+    for (column, value) in rejected {
+        let mut insert = RdbcInsertWrapper::new("t_executor_types");
+        insert.value(column, value);
+        assert!(matches!(
+            executor.execute(&insert).await,
+            Err(RdbcError::Database(_))
+        ));
+    }
+    executor
+        .execute_sql("DROP TABLE t_executor_types", &[])
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs BMBP_SQL_PG_URL"]
+async fn test_pg_transaction() {
+    let config = pg_config().expect("BMBP_SQL_PG_URL is not set");
+    let mut executor = RdbcPgExecutor::connect(&config).await.unwrap();
+    executor
+        .execute_sql("DROP TABLE IF EXISTS t_executor_tx", &[])
+        .await
+        .unwrap();
+    executor
+        .execute_sql("CREATE TABLE t_executor_tx (id BIGINT PRIMARY KEY)", &[])
+        .await
+        .unwrap();
+    let insert = |id: i64| {
+        let mut insert = RdbcInsertWrapper::new("t_executor_tx");
+        insert.value("id", id);
+        insert
+    };
+    let mut count = RdbcQueryWrapper::with_columns(["id"]);
+    count.from("t_executor_tx");
+
+    let mut tx = executor.begin().await.unwrap();
+    tx.execute(&insert(1)).await.unwrap();
+    {
+        let sp = tx.savepoint("sp_two").await.unwrap();
+        sp.execute(&insert(2)).await.unwrap();
+        assert_eq!(sp.query_list(&count).await.unwrap().len(), 2);
+        sp.rollback().await.unwrap();
+    }
+    {
+        let sp = tx.savepoint("sp_three").await.unwrap();
+        sp.execute(&insert(3)).await.unwrap();
+        sp.commit().await.unwrap();
+    }
+    tx.commit().await.unwrap();
+    assert_eq!(executor.query_list(&count).await.unwrap().len(), 2);
+
+    {
+        let tx = executor.begin().await.unwrap();
+        tx.execute(&insert(4)).await.unwrap();
+        // Dropped without commit.
+    }
+    assert_eq!(executor.query_list(&count).await.unwrap().len(), 2);
+
+    let mut read_only = RdbcTransactionOptions::default();
+    read_only
+        .isolation(RdbcIsolationLevel::RepeatableRead)
+        .read_only(true);
+    let tx = executor.begin_with(&read_only).await.unwrap();
+    assert!(matches!(
+        tx.execute(&insert(5)).await,
+        Err(RdbcError::Database(_))
+    ));
+    tx.rollback().await.unwrap();
+
+    let mut options = RdbcTransactionOptions::default();
+    options
+        .isolation(RdbcIsolationLevel::Serializable)
+        .retries(2);
+    let mut attempts = 0;
+    let inserted = executor
+        .with_transaction(&options, async |tx| {
+            attempts += 1;
+            tx.insert(&insert(6)).await?;
+            if attempts < 3 {
+                tx.execute_sql(
+                    "DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = '40001'; END $$",
+                    &[],
+                )
+                .await?;
+            }
+            Ok(attempts)
+        })
+        .await
+        .unwrap();
+    assert_eq!(inserted, 3);
+    assert_eq!(executor.query_list(&count).await.unwrap().len(), 3);
+
+    let mut attempts = 0;
+    let failed = executor
+        .with_transaction(&options, async |tx| {
+            attempts += 1;
+            tx.insert(&insert(7)).await?;
+            tx.execute_sql(
+                "DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = '40001'; END $$",
+                &[],
+            )
+            .await
+        })
+        .await;
+    assert!(matches!(failed, Err(RdbcError::Serialization(_))));
+    assert_eq!(attempts, 3);
+    assert_eq!(executor.query_list(&count).await.unwrap().len(), 3);
+    executor
+        .execute_sql("DROP TABLE t_executor_tx", &[])
+        .await
+        .unwrap();
+}
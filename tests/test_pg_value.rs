@@ -0,0 +1,188 @@
+#![cfg(feature = "postgres")]
+
+use bmbp_sql::RdbcValue;
+use tokio_postgres::types::private::BytesMut;
+use tokio_postgres::types::{FromSql, ToSql, Type};
+
+/// Binary `numeric`: digit count, weight, sign and display scale, followed
+/// by the base 10000 digits.
+fn numeric(weight: i16, sign: u16, dscale: u16, digits: &[u16]) -> Vec<u8> {
+    let mut raw = vec![];
+    raw.extend_from_slice(&(digits.len() as u16).to_be_bytes());
+    raw.extend_from_slice(&weight.to_be_bytes());
+    raw.extend_from_slice(&sign.to_be_bytes());
+    raw.extend_from_slice(&dscale.to_be_bytes());
+    for digit in digits {
+        raw.extend_from_slice(&digit.to_be_bytes());
+    }
+    raw
+}
+
+fn numeric_text(raw: &[u8]) -> String {
+    match RdbcValue::from_sql(&Type::NUMERIC, raw).unwrap() {
+        RdbcValue::Varchar(text) => text,
+        value => panic!("numeric read as {:?}", value),
+    }
+}
+
+#[test]
+fn test_pg_numeric_from_sql() {
+    assert_eq!(
+        numeric_text(&numeric(1, 0, 3, &[1, 2345, 6780])),
+        "12345.678"
+    );
+    assert_eq!(
+        numeric_text(&numeric(-1, 0x4000, 5, &[1, 2000])),
+        "-0.00012"
+    );
+    assert_eq!(numeric_text(&numeric(-2, 0, 8, &[1])), "0.00000001");
+    assert_eq!(numeric_text(&numeric(1, 0, 0, &[100])), "1000000");
+    assert_eq!(numeric_text(&numeric(2, 0, 2, &[7])), "700000000.00");
+    assert_eq!(numeric_text(&numeric(0, 0, 2, &[])), "0.00");
+    assert_eq!(numeric_text(&numeric(0, 0xC000, 0, &[])), "NaN");
+    assert_eq!(numeric_text(&numeric(0, 0xD000, 0, &[])), "Infinity");
+    assert_eq!(numeric_text(&numeric(0, 0xF000, 0, &[])), "-Infinity");
+    assert!(RdbcValue::from_sql(&Type::NUMERIC, &[0, 1]).is_err());
+    assert!(RdbcValue::from_sql(&Type::NUMERIC, &numeric(0, 0, 0, &[1])[..8]).is_err());
+}
+
+#[test]
+fn test_pg_scalars_from_sql() {
+    assert!(matches!(
+        RdbcValue::from_sql(&Type::INT2, &(-2i16).to_be_bytes()).unwrap(),
+        RdbcValue::Int(-2)
+    ));
+    assert!(matches!(
+        RdbcValue::from_sql(&Type::INT8, &i64::MIN.to_be_bytes()).unwrap(),
+        RdbcValue::BigInt(i64::MIN)
+    ));
+
+    // Microseconds since 2000-01-01 00:00:00 UTC.
+    let micros: i64 = 86_400_000_000 + 1_500_000;
+    match RdbcValue::from_sql(&Type::TIMESTAMPTZ, &micros.to_be_bytes()).unwrap() {
+        RdbcValue::DateTime(v) => assert_eq!(v.to_string(), "2000-01-02 00:00:01.500"),
+        value => panic!("timestamptz read as {:?}", value),
+    }
+
+    let uuid: Vec<u8> = (0u8..16).collect();
+    match RdbcValue::from_sql(&Type::UUID, &uuid).unwrap() {
+        RdbcValue::Varchar(v) => assert_eq!(v, "00010203-0405-0607-0809-0a0b0c0d0e0f"),
+        value => panic!("uuid read as {:?}", value),
+    }
+}
+
+#[test]
+fn test_pg_array_from_sql() {
+    // One dimension with a NULL member, element type int4, lower bound 1.
+    let mut raw = vec![];
+    for header in [1i32, 1, 23, 3, 1] {
+        raw.extend_from_slice(&header.to_be_bytes());
+    }
+    for member in [Some(1i32), None, Some(3)] {
+        match member {
+            Some(v) => {
+                raw.extend_from_slice(&4i32.to_be_bytes());
+                raw.extend_from_slice(&v.to_be_bytes());
+            }
+            None => raw.extend_from_slice(&(-1i32).to_be_bytes()),
+        }
+    }
+    match RdbcValue::from_sql(&Type::INT4_ARRAY, &raw).unwrap() {
+        RdbcValue::Array(members) => assert!(matches!(
+            members.as_slice(),
+            [RdbcValue::Int(1), RdbcValue::Null, RdbcValue::Int(3)]
+        )),
+        value => panic!("int4[] read as {:?}", value),
+    }
+}
+
+fn encode(value: RdbcValue, ty: &Type) -> Result<Vec<u8>, String> {
+    let mut w = BytesMut::new();
+    value.to_sql(ty, &mut w).map_err(|e| e.to_string())?;
+    Ok(w.to_vec())
+}
+
+#[test]
+fn test_pg_numeric_to_sql() {
+    for text in [
+        "12345.678",
+        "-0.00012",
+        "0.00000001",
+        "1000000",
+        "700000000.00",
+        "-12345.6780",
+        "0.00",
+        "NaN",
+        "Infinity",
+        "-Infinity",
+    ] {
+        let raw = encode(RdbcValue::from(text), &Type::NUMERIC).unwrap();
+        assert_eq!(numeric_text(&raw), text);
+    }
+    assert_eq!(
+        encode(RdbcValue::from("12345.678"), &Type::NUMERIC).unwrap(),
+        numeric(1, 0, 3, &[1, 2345, 6780])
+    );
+    assert_eq!(
+        encode(RdbcValue::from("+000100.5"), &Type::NUMERIC).unwrap(),
+        numeric(0, 0, 1, &[100, 5000])
+    );
+    assert_eq!(
+        numeric_text(&encode(RdbcValue::BigInt(-42), &Type::NUMERIC).unwrap()),
+        "-42"
+    );
+    assert_eq!(
+        numeric_text(&encode(RdbcValue::BigDouble(f64::INFINITY), &Type::NUMERIC).unwrap()),
+        "Infinity"
+    );
+    assert_eq!(
+        numeric_text(&encode(RdbcValue::Double(f32::NEG_INFINITY), &Type::NUMERIC).unwrap()),
+        "-Infinity"
+    );
+    for text in ["", ".", "1.2.3", "1e5", "abc"] {
+        assert!(
+            encode(RdbcValue::from(text), &Type::NUMERIC).is_err(),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn test_pg_scalars_to_sql() {
+    assert_eq!(
+        encode(RdbcValue::BigInt(-2), &Type::INT2).unwrap(),
+        (-2i16).to_be_bytes()
+    );
+    assert_eq!(
+        encode(RdbcValue::from(" 7 "), &Type::INT4).unwrap(),
+        7i32.to_be_bytes()
+    );
+    assert_eq!(
+        encode(RdbcValue::Int(1), &Type::FLOAT4).unwrap(),
+        1f32.to_be_bytes()
+    );
+    assert_eq!(
+        encode(
+            RdbcValue::from("00010203-0405-0607-0809-0a0b0c0d0e0f"),
+            &Type::UUID
+        )
+        .unwrap(),
+        (0u8..16).collect::<Vec<u8>>()
+    );
+
+    let error = encode(RdbcValue::BigInt(40_000), &Type::INT2).unwrap_err();
+    assert!(error.contains("out of range"), "{}", error);
+    let error = encode(RdbcValue::TimeStamp(u64::MAX), &Type::INT8).unwrap_err();
+    assert!(error.contains("out of range"), "{}", error);
+    let error = encode(RdbcValue::BigDouble(1e40), &Type::FLOAT4).unwrap_err();
+    assert!(error.contains("out of range"), "{}", error);
+    assert!(encode(RdbcValue::BigDouble(f64::INFINITY), &Type::FLOAT4).is_ok());
+
+    let error = encode(RdbcValue::Boolean(true), &Type::INT4).unwrap_err();
+    assert!(error.contains("cannot bind"), "{}", error);
+    assert!(encode(RdbcValue::Int(1), &Type::UUID).is_err());
+    assert!(encode(RdbcValue::from("abc"), &Type::UUID).is_err());
+    assert!(encode(RdbcValue::from("maybe"), &Type::BOOL).is_err());
+    assert!(encode(RdbcValue::Boolean(true), &Type::NUMERIC).is_err());
+}
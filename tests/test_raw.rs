@@ -0,0 +1,47 @@
+use bmbp_sql::{
+    render_query, render_update, DataBase, RdbcColumn, RdbcQueryWrapper, RdbcRawSql, RdbcTable,
+    RdbcUpdateWrapper, RdbcWhereCondition,
+};
+
+#[test]
+pub fn test_raw_column_table_and_condition() {
+    let mut query = RdbcQueryWrapper::new();
+    query
+        .select("s.n")
+        .select_column(RdbcColumn::raw(
+            RdbcRawSql::new("to_char(now(), #{fmt})").param("fmt", "YYYY-MM-DD"),
+            "today",
+        ))
+        .from(RdbcTable::raw(
+            RdbcRawSql::new("generate_series(1, #{max})").param("max", 3),
+            "s(n)",
+        ));
+    query.raw(RdbcRawSql::new("s.n % #{fmt} = 0").param("fmt", 2));
+    let (sql, params) = render_query(&query, DataBase::Postgres).unwrap();
+    assert_eq!(
+        sql,
+        "SELECT s.n,to_char(now(), $1) AS today \n FROM generate_series(1, $2) AS s(n) \n WHERE (s.n % $3 = 0)"
+    );
+    let params: Vec<String> = params.iter().map(|v| v.to_string()).collect();
+    assert_eq!(params, vec!["YYYY-MM-DD", "3", "2"]);
+}
+
+#[test]
+pub fn test_raw_value_falls_back_to_wrapper_params() {
+    let mut update = RdbcUpdateWrapper::default();
+    update.from("t_user");
+    update.set(
+        "tags",
+        RdbcRawSql::new("array_append(tags, #{tag})").param("tag", "vip"),
+    );
+    update.set("updated_by", RdbcRawSql::new("lower(#{user})"));
+    update.eq("id", 7);
+    update.param("user", "ADMIN");
+    let (sql, params) = render_update(&update, DataBase::Postgres).unwrap();
+    assert_eq!(
+        sql,
+        "UPDATE t_user\nSET tags=array_append(tags, $1),updated_by=lower($2)\nWHERE id = $3"
+    );
+    let params: Vec<String> = params.iter().map(|v| v.to_string()).collect();
+    assert_eq!(params, vec!["vip", "ADMIN", "7"]);
+}
@@ -0,0 +1,75 @@
+use bmbp_sql::{DataBase, RdbcError, RdbcSqlRegistry, RdbcValue};
+use std::collections::HashMap;
+use std::fs;
+
+#[test]
+pub fn test_registry_load_dir_with_dialect_override() {
+    let dir = std::env::temp_dir().join(format!("bmbp_sql_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(dir.join("report")).unwrap();
+    fs::write(
+        dir.join("user.sql"),
+        "-- user queries\n\
+         -- name: find_user\n\
+         SELECT id, name FROM t_user WHERE id = #{id};\n\
+         \n\
+         -- name: page_user\n\
+         SELECT id, name FROM t_user ORDER BY id LIMIT #{size} OFFSET #{offset};\n\
+         -- name: page_user\n\
+         -- dialect: oracle\n\
+         SELECT id, name FROM t_user ORDER BY id OFFSET #{offset} ROWS FETCH NEXT #{size} ROWS ONLY;\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("report").join("daily.postgres.sql"),
+        "-- name: find_user\nSELECT id, name FROM t_user WHERE id = #{id}::uuid\n",
+    )
+    .unwrap();
+
+    let mut registry = RdbcSqlRegistry::new();
+    registry.load_dir(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(registry.names(), vec!["find_user", "page_user"]);
+
+    let mut params = HashMap::new();
+    params.insert("id".to_string(), RdbcValue::from("u-1"));
+    params.insert("size".to_string(), RdbcValue::from(10));
+    params.insert("offset".to_string(), RdbcValue::from(20));
+
+    let (sql, values) = registry
+        .render("find_user", &params, DataBase::Postgres)
+        .unwrap();
+    assert_eq!(sql, "SELECT id, name FROM t_user WHERE id = $1::uuid");
+    assert_eq!(values.len(), 1);
+    let (sql, _) = registry
+        .render("find_user", &params, DataBase::MySql)
+        .unwrap();
+    assert_eq!(sql, "SELECT id, name FROM t_user WHERE id = ?");
+
+    let (sql, values) = registry
+        .render("page_user", &params, DataBase::Oracle)
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT id, name FROM t_user ORDER BY id OFFSET :1 ROWS FETCH NEXT :2 ROWS ONLY"
+    );
+    assert_eq!(values[0].to_string(), "20");
+    assert_eq!(values[1].to_string(), "10");
+}
+
+#[test]
+pub fn test_registry_errors() {
+    let mut registry = RdbcSqlRegistry::new();
+    registry.load_str("-- name: a\nSELECT 1", None).unwrap();
+    assert!(matches!(
+        registry.load_str("-- name: a\nSELECT 2", None),
+        Err(RdbcError::Registry(_))
+    ));
+    assert!(matches!(
+        registry.load_str("SELECT 3", None),
+        Err(RdbcError::Registry(_))
+    ));
+    assert!(matches!(
+        registry.render("missing", &HashMap::new(), DataBase::Sqlite),
+        Err(RdbcError::Registry(_))
+    ));
+}
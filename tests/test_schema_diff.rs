@@ -0,0 +1,249 @@
+use bmbp_sql::{
+    render_ddl, DataBase, IndexDDLWrapper, RdbcColumnType, RdbcDdlWrapper, RdbcError,
+    RdbcSchemaDiff, TableDDLWrapper,
+};
+
+fn desired_table() -> TableDDLWrapper {
+    let mut table = TableDDLWrapper::new("t_user");
+    table.comment("users");
+    table.add_column("id", RdbcColumnType::BigInt).primary_key();
+    table
+        .add_column("name", RdbcColumnType::Varchar(128))
+        .not_null();
+    table
+        .add_column("age", RdbcColumnType::Int)
+        .default_value(0)
+        .comment("age in years");
+    table.add_column("email", RdbcColumnType::Varchar(64));
+    table
+}
+
+fn actual_table() -> TableDDLWrapper {
+    let mut table = TableDDLWrapper::new("t_user");
+    table.comment("users");
+    table.add_column("id", RdbcColumnType::BigInt).primary_key();
+    table
+        .add_column("name", RdbcColumnType::Varchar(64))
+        .not_null();
+    table
+        .add_column("age", RdbcColumnType::Int)
+        .default_value(0i64);
+    table.add_column("legacy", RdbcColumnType::Text);
+    table
+}
+
+fn render_all(statements: &[RdbcDdlWrapper], db_type: DataBase) -> Vec<String> {
+    statements
+        .iter()
+        .flat_map(|ddl| render_ddl(ddl, db_type).unwrap())
+        .collect()
+}
+
+#[test]
+pub fn test_diff_alters_columns_indexes_and_comments() {
+    let mut wanted_index = IndexDDLWrapper::new("t_user", "idx_user_name");
+    wanted_index.column("name").unique();
+    let mut current_index = IndexDDLWrapper::new("t_user", "idx_user_name");
+    current_index.column("name");
+    let mut stale_index = IndexDDLWrapper::new("t_user", "idx_user_legacy");
+    stale_index.column("legacy");
+
+    let statements = RdbcSchemaDiff::new(DataBase::Postgres)
+        .diff_table(
+            &desired_table(),
+            &[wanted_index.clone()],
+            Some(&actual_table()),
+            &[current_index.clone(), stale_index.clone()],
+        )
+        .unwrap();
+    assert_eq!(
+        render_all(&statements, DataBase::Postgres),
+        vec![
+            "DROP INDEX idx_user_name",
+            "DROP INDEX idx_user_legacy",
+            "ALTER TABLE t_user ADD COLUMN email VARCHAR(64)",
+            "ALTER TABLE t_user ALTER COLUMN name TYPE VARCHAR(128)",
+            "ALTER TABLE t_user DROP COLUMN legacy",
+            "COMMENT ON COLUMN t_user.age IS 'age in years'",
+            "CREATE UNIQUE INDEX idx_user_name ON t_user (name)",
+        ]
+    );
+    let mysql = render_all(
+        &RdbcSchemaDiff::new(DataBase::MySql)
+            .diff_table(&desired_table(), &[], Some(&actual_table()), &[])
+            .unwrap(),
+        DataBase::MySql,
+    );
+    assert!(
+        mysql.contains(&"ALTER TABLE t_user MODIFY COLUMN name VARCHAR(128) NOT NULL".to_string())
+    );
+    assert!(mysql.contains(
+        &"ALTER TABLE t_user MODIFY COLUMN age INT DEFAULT 0 COMMENT 'age in years'".to_string()
+    ));
+    let oracle = render_all(
+        &RdbcSchemaDiff::new(DataBase::Oracle)
+            .diff_table(&desired_table(), &[], Some(&actual_table()), &[])
+            .unwrap(),
+        DataBase::Oracle,
+    );
+    assert!(oracle.contains(&"ALTER TABLE t_user MODIFY (name VARCHAR2(128))".to_string()));
+
+    let unchanged = RdbcSchemaDiff::new(DataBase::Postgres)
+        .diff_table(
+            &desired_table(),
+            &[wanted_index.clone()],
+            Some(&desired_table()),
+            &[wanted_index],
+        )
+        .unwrap();
+    assert!(unchanged.is_empty());
+}
+
+#[test]
+pub fn test_diff_create_and_safe_mode() {
+    let mut index = IndexDDLWrapper::new("t_user", "idx_user_name");
+    index.column("name");
+    let statements = RdbcSchemaDiff::new(DataBase::MySql)
+        .diff_table(&desired_table(), &[index], None, &[])
+        .unwrap();
+    assert!(matches!(
+        &statements[..],
+        [
+            RdbcDdlWrapper::CreateTable(_),
+            RdbcDdlWrapper::CreateIndex(_)
+        ]
+    ));
+
+    let err = RdbcSchemaDiff::new(DataBase::Postgres)
+        .safe()
+        .diff_table(&desired_table(), &[], Some(&actual_table()), &[])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        RdbcError::Destructive("drop column t_user.legacy".to_string())
+    );
+    let mut without_legacy = actual_table();
+    without_legacy
+        .columns
+        .retain(|column| column.name != "legacy");
+    let err = RdbcSchemaDiff::new(DataBase::Postgres)
+        .safe()
+        .diff_table(&desired_table(), &[], Some(&without_legacy), &[])
+        .unwrap_err();
+    assert!(matches!(err, RdbcError::Destructive(msg) if msg.contains("t_user.name")));
+}
+
+#[test]
+pub fn test_diff_unique_columns() {
+    let mut desired = desired_table();
+    desired.columns[3].unique = true;
+    let mut actual = desired_table();
+    let diff = |desired: &TableDDLWrapper, actual: &TableDDLWrapper, db_type: DataBase| {
+        let statements = RdbcSchemaDiff::new(db_type)
+            .diff_table(desired, &[], Some(actual), &[])
+            .unwrap();
+        render_all(&statements, db_type)
+    };
+    assert_eq!(
+        diff(&desired, &actual, DataBase::Postgres),
+        vec!["ALTER TABLE t_user ADD CONSTRAINT t_user_email_key UNIQUE (email)"]
+    );
+    assert_eq!(
+        diff(&desired, &actual, DataBase::MySql),
+        vec!["ALTER TABLE t_user ADD UNIQUE (email)"]
+    );
+    assert_eq!(
+        diff(&desired, &actual, DataBase::Oracle),
+        vec!["ALTER TABLE t_user ADD UNIQUE (email)"]
+    );
+
+    actual.columns[3].unique = true;
+    assert!(diff(&desired, &actual, DataBase::Postgres).is_empty());
+    let mut table_unique = desired_table();
+    table_unique.unique(&["email"]);
+    assert!(diff(&table_unique, &actual, DataBase::Postgres).is_empty());
+    assert_eq!(
+        diff(&desired_table(), &actual, DataBase::Postgres),
+        vec!["ALTER TABLE t_user DROP CONSTRAINT t_user_email_key"]
+    );
+    assert_eq!(
+        diff(&desired_table(), &actual, DataBase::MySql),
+        vec!["ALTER TABLE t_user DROP INDEX email"]
+    );
+
+    // The index behind the constraint, as a database listing all indexes
+    // reports it, is kept.
+    let mut backing_index = IndexDDLWrapper::new("t_user", "t_user_email_key");
+    backing_index.column("email").unique();
+    for table in [&desired, &table_unique] {
+        assert!(RdbcSchemaDiff::new(DataBase::Postgres)
+            .diff_table(table, &[], Some(&actual), &[backing_index.clone()])
+            .unwrap()
+            .is_empty());
+    }
+}
+
+#[test]
+pub fn test_diff_not_null_column_without_default() {
+    let mut desired = desired_table();
+    desired
+        .add_column("code", RdbcColumnType::Varchar(16))
+        .not_null();
+    let actual = desired_table();
+    for db_type in [DataBase::Postgres, DataBase::Sqlite] {
+        let err = RdbcSchemaDiff::new(db_type)
+            .safe()
+            .diff_table(&desired, &[], Some(&actual), &[])
+            .unwrap_err();
+        assert!(matches!(err, RdbcError::Unsupported(msg) if msg.contains("t_user.code")));
+    }
+    let statements = RdbcSchemaDiff::new(DataBase::Postgres)
+        .diff_table(&desired, &[], Some(&actual), &[])
+        .unwrap();
+    assert_eq!(
+        render_all(&statements, DataBase::Postgres),
+        vec!["ALTER TABLE t_user ADD COLUMN code VARCHAR(16) NOT NULL"]
+    );
+
+    desired.columns[4].default_value("");
+    assert!(RdbcSchemaDiff::new(DataBase::Postgres)
+        .safe()
+        .diff_table(&desired, &[], Some(&actual), &[])
+        .is_ok());
+}
+
+#[test]
+pub fn test_diff_primary_key() {
+    let desired = desired_table();
+    let mut actual = desired_table();
+    actual.columns[0].primary_key = false;
+    actual.columns[1].primary_key = true;
+    for db_type in [DataBase::Postgres, DataBase::Sqlite] {
+        let err = RdbcSchemaDiff::new(db_type)
+            .safe()
+            .diff_table(&desired, &[], Some(&actual), &[])
+            .unwrap_err();
+        assert!(matches!(err, RdbcError::Unsupported(msg) if msg.contains("primary key")));
+    }
+    assert!(matches!(
+        RdbcSchemaDiff::new(DataBase::MySql).diff_table(&desired, &[], Some(&actual), &[]),
+        Err(RdbcError::Unsupported(_))
+    ));
+    let statements = RdbcSchemaDiff::new(DataBase::Sqlite)
+        .diff_table(&desired, &[], Some(&actual), &[])
+        .unwrap();
+    assert!(matches!(
+        &statements[..],
+        [RdbcDdlWrapper::CreateTable(rebuild), ..] if rebuild.columns[0].primary_key
+    ));
+
+    // A table constraint naming the same columns is the same key.
+    let mut table_key = desired_table();
+    table_key.columns[0].primary_key = false;
+    table_key.primary_key(&["ID"]);
+    assert!(RdbcSchemaDiff::new(DataBase::Postgres)
+        .safe()
+        .diff_table(&table_key, &[], Some(&desired), &[])
+        .unwrap()
+        .is_empty());
+}
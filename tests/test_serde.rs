@@ -0,0 +1,132 @@
+use bmbp_sql::{
+    from_rdbc_value, render_script, to_rdbc_params, to_rdbc_value, DataBase, RdbcError, RdbcValue,
+};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Status {
+    Active,
+    Locked { reason: String },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct User {
+    id: i64,
+    name: String,
+    nick: Option<String>,
+    score: f64,
+    enabled: bool,
+    birthday: NaiveDate,
+    created: NaiveDateTime,
+    alarm: NaiveTime,
+    avatar: Vec<u8>,
+    tags: Vec<String>,
+    status: Status,
+    locked: Status,
+}
+
+fn user() -> User {
+    User {
+        id: 7,
+        name: "2024-01-01".to_string(),
+        nick: None,
+        score: 9.5,
+        enabled: true,
+        birthday: NaiveDate::from_ymd_opt(2000, 2, 29).unwrap(),
+        created: NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_milli_opt(10, 20, 30, 250)
+            .unwrap(),
+        alarm: NaiveTime::from_hms_opt(7, 30, 0).unwrap(),
+        avatar: vec![0, 255],
+        tags: vec!["a".to_string(), "b".to_string()],
+        status: Status::Active,
+        locked: Status::Locked {
+            reason: "spam".to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_struct_to_rdbc_value() {
+    let value = to_rdbc_value(&user()).unwrap();
+    let object = value.as_object().unwrap();
+    assert!(matches!(object["id"], RdbcValue::BigInt(7)));
+    // Strings stay strings, only chrono's formatted values become dates.
+    assert!(matches!(object["name"], RdbcValue::Varchar(_)));
+    assert!(object["nick"].is_null());
+    assert!(matches!(object["birthday"], RdbcValue::Date(_)));
+    assert_eq!(object["created"].to_string(), "2024-02-29 10:20:30.250");
+    assert!(matches!(object["alarm"], RdbcValue::Time(_)));
+    assert_eq!(object["avatar"].as_array().unwrap().len(), 2);
+    assert_eq!(object["status"].to_string(), "Active");
+    assert_eq!(
+        object["locked"].as_object().unwrap()["Locked"]
+            .as_object()
+            .unwrap()["reason"]
+            .to_string(),
+        "spam"
+    );
+}
+
+#[test]
+fn test_rdbc_value_round_trip() {
+    let value = to_rdbc_value(&user()).unwrap();
+    let back: User = from_rdbc_value(value).unwrap();
+    assert_eq!(back, user());
+}
+
+#[test]
+fn test_row_to_struct() {
+    #[derive(Debug, Deserialize)]
+    struct Row {
+        amount: f64,
+        enabled: bool,
+        created: NaiveDateTime,
+        data: Vec<u8>,
+        note: Option<String>,
+    }
+    let mut row = HashMap::new();
+    // numeric columns are read as their exact text
+    row.insert("amount".to_string(), RdbcValue::from("12.50"));
+    row.insert("enabled".to_string(), RdbcValue::Int(1));
+    row.insert(
+        "created".to_string(),
+        RdbcValue::DateTime(
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_opt(10, 20, 30)
+                .unwrap(),
+        ),
+    );
+    row.insert("data".to_string(), RdbcValue::Bytes(vec![1, 2]));
+    row.insert("note".to_string(), RdbcValue::Null);
+    let row: Row = from_rdbc_value(RdbcValue::Object(row)).unwrap();
+    assert_eq!(row.amount, 12.5);
+    assert!(row.enabled);
+    assert_eq!(row.created.to_string(), "2024-02-29 10:20:30");
+    assert_eq!(row.data, vec![1, 2]);
+    assert!(row.note.is_none());
+
+    let result: Result<i32, RdbcError> = from_rdbc_value(RdbcValue::from("x"));
+    assert!(matches!(result, Err(RdbcError::Serde(_))));
+}
+
+#[test]
+fn test_struct_as_params() {
+    let params = to_rdbc_params(&user()).unwrap();
+    let (sql, values) = render_script(
+        "SELECT * FROM t_user WHERE id = #{id} AND birthday = #{birthday}",
+        &params,
+        DataBase::Postgres,
+    )
+    .unwrap();
+    assert_eq!(sql, "SELECT * FROM t_user WHERE id = $1 AND birthday = $2");
+    assert!(matches!(
+        values[..],
+        [RdbcValue::BigInt(7), RdbcValue::Date(_)]
+    ));
+    assert!(matches!(to_rdbc_params(&7), Err(RdbcError::Serde(_))));
+}
@@ -0,0 +1,178 @@
+#![cfg(all(feature = "derive", feature = "sqlite"))]
+
+mod common;
+
+use bmbp_sql::{
+    render_query, DataBase, RdbcDeleteWrapper, RdbcError, RdbcExecutorExt, RdbcKeyCase,
+    RdbcQueryWrapper, RdbcSqliteExecutor, RdbcTransactionOptions, RdbcUpdateWrapper, RdbcValue,
+    RdbcWhereCondition,
+};
+use common::{field, insert, Member};
+
+async fn executor() -> RdbcSqliteExecutor {
+    let executor = RdbcSqliteExecutor::open_in_memory().unwrap();
+    executor
+        .execute_sql(
+            "CREATE TABLE t_member (id INTEGER PRIMARY KEY, name TEXT NOT NULL, \
+             enabled BOOLEAN DEFAULT 1, joined DATE, seen DATETIME, avatar BLOB)",
+            &[],
+        )
+        .await
+        .unwrap();
+    executor
+}
+
+#[test]
+fn test_sqlite_render() {
+    let mut query = RdbcQueryWrapper::with_columns(["id", "name"]);
+    query.from("t_member");
+    query.eq("id", 1i64).like("name", "mem");
+    let (sql, params) = render_query(&query, DataBase::Sqlite).unwrap();
+    assert!(sql.contains("id = ?"), "{}", sql);
+    assert!(
+        sql.contains("LIKE CONCAT('%',CAST(? AS TEXT),'%')"),
+        "{}",
+        sql
+    );
+    assert_eq!(params.len(), 2);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_sqlite_executor_crud() {
+    let mut executor = executor().await;
+    for id in 1..=5 {
+        assert_eq!(executor.execute(&insert("t_member", id)).await.unwrap(), 1);
+    }
+    let mut update = RdbcUpdateWrapper::default();
+    update.from("t_member");
+    update
+        .set("name", "alice")
+        .set("enabled", false)
+        .set(
+            "joined",
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+        )
+        .set("avatar", vec![1u8, 2])
+        .eq("id", 1i64);
+    assert_eq!(executor.execute(&update).await.unwrap(), 1);
+
+    let mut query = RdbcQueryWrapper::with_columns(["id", "name", "enabled", "joined", "avatar"]);
+    query.from("t_member");
+    query.eq("id", 1i64);
+    let row = executor.query_one(&query).await.unwrap().unwrap();
+    assert!(matches!(field(&row, "id"), RdbcValue::BigInt(1)));
+    assert_eq!(field(&row, "name").to_string(), "alice");
+    assert!(matches!(field(&row, "enabled"), RdbcValue::Boolean(false)));
+    assert!(matches!(field(&row, "joined"), RdbcValue::Date(_)));
+    assert!(matches!(field(&row, "avatar"), RdbcValue::Bytes(v) if v == &vec![1u8, 2]));
+    let member: Member = executor.query_one_as(&query).await.unwrap().unwrap();
+    assert_eq!((member.id, member.name.as_str()), (1, "alice"));
+    assert!(!member.enabled);
+    assert_eq!(member.joined.unwrap().to_string(), "2024-02-29");
+
+    let mut like = RdbcQueryWrapper::with_columns(["id", "name", "enabled", "joined"]);
+    like.from("t_member");
+    like.like("name", "member");
+    let members: Vec<Member> = executor.query_as(&like).await.unwrap();
+    assert_eq!(members.len(), 4);
+    assert!(members.iter().all(|member| member.enabled));
+    let page = executor.query_page(&like, 2, 3).await.unwrap();
+    assert_eq!((page.total, page.data.len()), (4, 1));
+
+    let mut delete = RdbcDeleteWrapper::default();
+    delete.from("t_member");
+    delete.eq("id", 5i64);
+    assert_eq!(executor.execute(&delete).await.unwrap(), 1);
+
+    executor.key_case(RdbcKeyCase::CamelCase);
+    let rows = executor
+        .query_sql(
+            "SELECT seen AS last_seen, json_extract(?, '$.a') AS json_a FROM t_member \
+             WHERE id = ?",
+            &[
+                RdbcValue::from(serde_json::json!({"a": 1})),
+                RdbcValue::BigInt(2),
+            ],
+        )
+        .await
+        .unwrap();
+    assert!(field(&rows[0], "lastSeen").is_null());
+    assert!(matches!(field(&rows[0], "jsonA"), RdbcValue::BigInt(1)));
+
+    let seen = executor
+        .query_sql("SELECT ? AS seen", &[RdbcValue::TimeStamp(i64::MAX as u64)])
+        .await
+        .unwrap();
+    assert!(matches!(
+        field(&seen[0], "seen"),
+        RdbcValue::BigInt(i64::MAX)
+    ));
+    let overflow = executor
+        .query_sql("SELECT ?", &[RdbcValue::TimeStamp(u64::MAX)])
+        .await;
+    assert!(matches!(overflow, Err(RdbcError::Database(ref e)) if e.contains("out of range")));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_sqlite_transaction() {
+    let mut executor = executor().await;
+    let mut count = RdbcQueryWrapper::with_columns(["id"]);
+    count.from("t_member");
+
+    let mut tx = executor.begin().await.unwrap();
+    tx.execute(&insert("t_member", 1)).await.unwrap();
+    {
+        let sp = tx.savepoint("sp_two").await.unwrap();
+        sp.execute(&insert("t_member", 2)).await.unwrap();
+        assert_eq!(sp.query_list(&count).await.unwrap().len(), 2);
+        sp.rollback().await.unwrap();
+    }
+    {
+        let sp = tx.savepoint("sp_three").await.unwrap();
+        sp.execute(&insert("t_member", 3)).await.unwrap();
+        // Dropped without commit.
+    }
+    {
+        let sp = tx.savepoint("sp_four").await.unwrap();
+        sp.execute(&insert("t_member", 4)).await.unwrap();
+        sp.commit().await.unwrap();
+    }
+    tx.commit().await.unwrap();
+    assert_eq!(executor.query_list(&count).await.unwrap().len(), 2);
+
+    {
+        let tx = executor.begin().await.unwrap();
+        tx.execute(&insert("t_member", 5)).await.unwrap();
+    }
+    assert_eq!(executor.query_list(&count).await.unwrap().len(), 2);
+
+    let mut read_only = RdbcTransactionOptions::default();
+    read_only.read_only(true);
+    let tx = executor.begin_with(&read_only).await.unwrap();
+    assert_eq!(tx.query_list(&count).await.unwrap().len(), 2);
+    assert!(matches!(
+        tx.execute(&insert("t_member", 6)).await,
+        Err(RdbcError::Database(_))
+    ));
+    tx.rollback().await.unwrap();
+    executor.execute(&insert("t_member", 6)).await.unwrap();
+
+    let mut attempts = 0;
+    let failed = executor
+        .with_transaction(&RdbcTransactionOptions::default(), async |tx| {
+            attempts += 1;
+            tx.insert(&insert("t_member", 7)).await?;
+            tx.insert(&insert("t_member", 7)).await
+        })
+        .await;
+    assert!(matches!(failed, Err(RdbcError::Database(_))));
+    assert_eq!(attempts, 1);
+    let inserted = executor
+        .with_transaction(&RdbcTransactionOptions::default(), async |tx| {
+            tx.insert(&insert("t_member", 7)).await
+        })
+        .await
+        .unwrap();
+    assert_eq!(inserted, 1);
+    assert_eq!(executor.query_list(&count).await.unwrap().len(), 4);
+}
@@ -0,0 +1,117 @@
+use bmbp_sql::{DataBase, RdbcError, RdbcSqlTemplate, RdbcValue};
+use std::collections::HashMap;
+
+fn user_params() -> HashMap<String, RdbcValue> {
+    let mut params = HashMap::new();
+    params.insert("name".to_string(), RdbcValue::from("bmbp"));
+    params.insert("age".to_string(), RdbcValue::from(18));
+    params.insert("email".to_string(), RdbcValue::Null);
+    params.insert(
+        "ids".to_string(),
+        RdbcValue::Array(vec![RdbcValue::from(1), RdbcValue::from(2)]),
+    );
+    params
+}
+
+#[test]
+pub fn test_template_where_if_foreach() {
+    let template = RdbcSqlTemplate::parse(
+        r#"<select id="findUser">
+        SELECT * FROM t_user
+        <where>
+            <if test="name != null and name != ''">AND name = #{name}</if>
+            <if test="email != null">AND email = #{email}</if>
+            <if test="age &gt;= 18">AND age &gt;= #{age}</if>
+            <if test="ids != null and ids.size() > 0">
+                AND id IN
+                <foreach collection="ids" item="id" open="(" separator="," close=")">#{id}</foreach>
+            </if>
+        </where>
+        </select>"#,
+    )
+    .unwrap();
+    let (sql, params) = template.render(&user_params(), DataBase::Postgres).unwrap();
+    assert_eq!(
+        sql,
+        "SELECT * FROM t_user WHERE name = $1 AND age >= $2 AND id IN ($3,$4)"
+    );
+    assert_eq!(params.len(), 4);
+    assert_eq!(params[0].to_string(), "bmbp");
+    assert_eq!(params[3].to_string(), "2");
+
+    let (sql, _) = template.render(&HashMap::new(), DataBase::MySql).unwrap();
+    assert_eq!(sql, "SELECT * FROM t_user");
+}
+
+#[test]
+pub fn test_template_set_choose() {
+    let template = RdbcSqlTemplate::parse(
+        r#"UPDATE t_user
+        <set>
+            <if test="name != null">name = #{name},</if>
+            <choose>
+                <when test="age > 60">level = 'senior',</when>
+                <when test="age > 17">level = 'adult',</when>
+                <otherwise>level = 'minor',</otherwise>
+            </choose>
+        </set>
+        WHERE id = #{ids.0}"#,
+    )
+    .unwrap();
+    let (sql, params) = template.render(&user_params(), DataBase::Sqlite).unwrap();
+    assert_eq!(
+        sql,
+        "UPDATE t_user SET name = ?, level = 'adult' WHERE id = ?"
+    );
+    assert_eq!(params.len(), 2);
+    assert_eq!(params[1].to_string(), "1");
+}
+
+#[test]
+pub fn test_template_raw_allowlist() {
+    let mut template =
+        RdbcSqlTemplate::parse("SELECT * FROM t_user ORDER BY ${sort} DESC").unwrap();
+    let mut params = HashMap::new();
+    params.insert(
+        "sort".to_string(),
+        RdbcValue::from("name; DROP TABLE t_user"),
+    );
+    assert!(matches!(
+        template.render(&params, DataBase::Postgres),
+        Err(RdbcError::InvalidParam(_))
+    ));
+
+    template.allow_raw("sort", ["name", "create_time"]);
+    params.insert("sort".to_string(), RdbcValue::from("create_time"));
+    let (sql, params) = template.render(&params, DataBase::Oracle).unwrap();
+    assert_eq!(sql, "SELECT * FROM t_user ORDER BY create_time DESC");
+    assert!(params.is_empty());
+}
+
+#[test]
+pub fn test_template_errors() {
+    assert!(matches!(
+        RdbcSqlTemplate::parse("SELECT 1 <if test=\"a\">x"),
+        Err(RdbcError::Template(_))
+    ));
+    let template = RdbcSqlTemplate::parse("SELECT * FROM t WHERE a = #{missing}").unwrap();
+    assert_eq!(
+        template
+            .render(&HashMap::new(), DataBase::Postgres)
+            .unwrap_err(),
+        RdbcError::UnknownParam("missing".to_string())
+    );
+}
+
+#[test]
+pub fn test_template_comments_are_dropped() {
+    let template = RdbcSqlTemplate::parse(
+        "SELECT * FROM t_user -- all users\nWHERE id = #{id} /* by id */ AND name <> '--x'",
+    )
+    .unwrap();
+    let mut params = HashMap::new();
+    params.insert("id".to_string(), RdbcValue::from(1));
+    let (sql, values) = template.render(&params, DataBase::Postgres).unwrap();
+    assert_eq!(sql, "SELECT * FROM t_user WHERE id = $1 AND name <> '--x'");
+    assert_eq!(values.len(), 1);
+}
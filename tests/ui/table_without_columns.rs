@@ -0,0 +1,9 @@
+use bmbp_sql::RdbcTable;
+
+#[derive(RdbcTable)]
+pub struct Empty {
+    #[rdbc(ignore)]
+    cache: String,
+}
+
+fn main() {}